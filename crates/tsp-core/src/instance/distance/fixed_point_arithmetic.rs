@@ -1,13 +1,23 @@
-use std::{
+use core::{
     iter::Sum,
     ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
 };
 
 use super::Distance;
 
-pub(crate) const FIXED_POINT_FRACTIONAL_BITS: u32 = 5;
-
+/// Number of fractional bits of a [`ScaledDistance`], i.e. distances are scaled by `2^5 = 32`.
+///
+/// This is a tradeoff between the precision and the range of the scaled arithmetic of the
+/// Lagrangian relaxation in the Held-Karp solvers. More bits allow finer node penalties, which can
+/// tighten the lower bounds, but each additional bit halves the largest cost of a tour or 1-tree
+/// which can be represented, see [`Distance::MAX`]. With 5 bits, costs up to about 67 million are
+/// supported.
+pub const FIXED_POINT_FRACTIONAL_BITS: u32 = 5;
+
+/// A distance in fixed-point representation with [`FIXED_POINT_FRACTIONAL_BITS`] fractional bits,
+/// used for the node penalties and reduced costs of the Lagrangian relaxation.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScaledDistance(pub i32);
 
 impl ScaledDistance {
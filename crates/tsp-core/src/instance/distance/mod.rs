@@ -1,19 +1,125 @@
-use std::{
+use alloc::string::ToString;
+use core::{
     fmt::Display,
     iter::Sum,
     ops::{Add, AddAssign, Mul, Sub},
 };
 
+use crate::instance::{matrix::ReadDistance, node::Node};
+
 mod fixed_point_arithmetic;
-use fixed_point_arithmetic::FIXED_POINT_FRACTIONAL_BITS;
-pub use fixed_point_arithmetic::ScaledDistance;
+pub use fixed_point_arithmetic::{FIXED_POINT_FRACTIONAL_BITS, ScaledDistance};
+
+/// Read access to the distances between the nodes of an instance, independent of how they are
+/// stored.
+///
+/// The distances are read through [`ReadDistance`], this trait adds what is known about the
+/// instance as a whole. Implemented for [`Matrix<Distance>`](crate::instance::matrix::Matrix) and
+/// [`MatrixSym<Distance>`](crate::instance::matrix::MatrixSym).
+pub trait DistanceSource: ReadDistance<Data = Distance> {
+    /// Returns the number of nodes.
+    fn dimension(&self) -> usize;
+
+    /// Returns the distance from `from` to `to`, see [`ReadDistance::get`].
+    #[inline(always)]
+    fn distance(&self, from: Node, to: Node) -> Distance {
+        self.get(from, to)
+    }
+
+    /// Returns the number of distances actually stored, e.g. only the lower triangle for
+    /// symmetric storage.
+    fn stored_distances(&self) -> usize;
+
+    /// Returns whether the distance from `from` to `to` equals the one from `to` to `from` for all
+    /// pairs of nodes.
+    ///
+    /// By default, all pairs are compared. Containers which can only store symmetric distances
+    /// should override this.
+    fn is_symmetric(&self) -> bool {
+        (0..self.dimension()).all(|from| {
+            (0..from).all(|to| {
+                self.distance(Node(from), Node(to)) == self.distance(Node(to), Node(from))
+            })
+        })
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Distance(pub i32);
 
 impl Distance {
     pub const MAX: Distance = Distance(i32::MAX >> (FIXED_POINT_FRACTIONAL_BITS));
     pub const MIN: Distance = Distance(i32::MIN + (1 << FIXED_POINT_FRACTIONAL_BITS));
+
+    /// Adds the distances, returning `None` if the sum overflows `i32`.
+    pub fn checked_add(self, other: Distance) -> Option<Distance> {
+        self.0.checked_add(other.0).map(Distance)
+    }
+
+    /// Adds the distances, saturating at the bounds of `i32` instead of overflowing.
+    ///
+    /// Note that the result may exceed [`Distance::MAX`], which the solvers treat as a missing
+    /// edge.
+    pub fn saturating_add(self, other: Distance) -> Distance {
+        Distance(self.0.saturating_add(other.0))
+    }
+
+    /// Sums up the distances like [`Sum`], but accumulates in `i64` and saturates the result at
+    /// the bounds of `i32`, so that e.g. the cost of a long tour cannot silently wrap around to a
+    /// negative value.
+    pub fn saturating_sum(distances: impl IntoIterator<Item = Distance>) -> Distance {
+        let sum: i64 = distances
+            .into_iter()
+            .map(|distance| i64::from(distance.0))
+            .sum();
+        Distance(sum.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32)
+    }
+
+    /// Converts the value to a distance, returning an error if it is negative.
+    ///
+    /// Note that the solvers treat distances above [`Distance::MAX`] as missing edges.
+    pub fn from_nonneg_i32(value: i32) -> Result<Distance, DistanceConversionError> {
+        if value < 0 {
+            return Err(DistanceConversionError("the value is negative"));
+        }
+        Ok(Distance(value))
+    }
+}
+
+/// Error returned by the conversions between [`Distance`] and other integer types if the value
+/// does not fit into the target type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceConversionError(&'static str);
+
+impl Display for DistanceConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "DistanceConversionError: {}", self.0)
+    }
+}
+
+impl core::error::Error for DistanceConversionError {}
+
+/// Converts the value to a distance, returning an error if it exceeds `i32::MAX`.
+///
+/// Note that the solvers treat distances above [`Distance::MAX`] as missing edges.
+impl TryFrom<u32> for Distance {
+    type Error = DistanceConversionError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        i32::try_from(value)
+            .map(Distance)
+            .map_err(|_| DistanceConversionError("the value exceeds i32::MAX"))
+    }
+}
+
+/// Converts the distance to an unsigned integer, returning an error if it is negative.
+impl TryFrom<Distance> for u32 {
+    type Error = DistanceConversionError;
+
+    fn try_from(distance: Distance) -> Result<Self, Self::Error> {
+        u32::try_from(distance.0).map_err(|_| DistanceConversionError("the distance is negative"))
+    }
 }
 
 impl Add for Distance {
@@ -59,8 +165,27 @@ impl Mul<Distance> for i32 {
 }
 
 impl Display for Distance {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s = self.0.to_string();
         f.pad(&s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u32_conversions() {
+        assert_eq!(Distance::try_from(42u32), Ok(Distance(42)));
+        assert_eq!(Distance::try_from(i32::MAX as u32), Ok(Distance(i32::MAX)));
+        assert!(Distance::try_from(i32::MAX as u32 + 1).is_err());
+
+        assert_eq!(u32::try_from(Distance(0)), Ok(0));
+        assert_eq!(u32::try_from(Distance::MAX), Ok(Distance::MAX.0 as u32));
+        assert!(u32::try_from(Distance(-1)).is_err());
+
+        assert_eq!(Distance::from_nonneg_i32(7), Ok(Distance(7)));
+        assert!(Distance::from_nonneg_i32(-7).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::instance::{distance::Distance, node::Node};
 
 /// An undirected edge between two nodes.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnEdge {
     pub from: Node,
     pub to: Node,
@@ -23,13 +24,13 @@ impl PartialEq for UnEdge {
 impl Eq for UnEdge {}
 
 impl PartialOrd for UnEdge {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for UnEdge {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         let (min_self, max_self) = if self.from <= self.to {
             (self.from, self.to)
         } else {
@@ -42,7 +43,7 @@ impl Ord for UnEdge {
         };
 
         match min_self.cmp(&min_other) {
-            std::cmp::Ordering::Equal => max_self.cmp(&max_other),
+            core::cmp::Ordering::Equal => max_self.cmp(&max_other),
             ord => ord,
         }
     }
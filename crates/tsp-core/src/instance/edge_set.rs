@@ -0,0 +1,124 @@
+//! Sets of undirected edges with constant-time membership tests.
+
+use alloc::vec::Vec;
+
+use crate::{
+    instance::{edge::UnEdge, node::Node},
+    triangular::get_lower_triangle_matrix_entry,
+};
+
+/// Set of undirected edges, e.g. the edges which have to stay part of a tour during local search
+/// or the edges of a tour, see [`UnTour::edge_set`](crate::instance::UnTour::edge_set).
+///
+/// Stored as a bitset over the lower triangle (including the diagonal) of the adjacency matrix,
+/// such that inserting and querying an edge takes `O(1)` regardless of its orientation. The
+/// bitset only grows up to the largest inserted edge, so an empty set does not allocate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixedEdgeSet {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl FixedEdgeSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a set containing the given edges.
+    pub fn from_edges(edges: &[UnEdge]) -> Self {
+        let mut set = Self::new();
+        for &edge in edges {
+            set.insert(edge);
+        }
+        set
+    }
+
+    /// Inserts the edge and returns whether it was not yet part of the set.
+    pub fn insert(&mut self, edge: UnEdge) -> bool {
+        let (word, mask) = Self::position(edge.from, edge.to);
+        if word >= self.bits.len() {
+            self.bits.resize(word + 1, 0);
+        }
+        let is_new = self.bits[word] & mask == 0;
+        self.bits[word] |= mask;
+        self.len += usize::from(is_new);
+        is_new
+    }
+
+    /// Returns whether the set contains the edge in either direction.
+    #[inline(always)]
+    pub fn contains(&self, edge: UnEdge) -> bool {
+        self.contains_nodes(edge.from, edge.to)
+    }
+
+    /// Returns whether the set contains the edge between the two nodes.
+    #[inline(always)]
+    pub fn contains_nodes(&self, from: Node, to: Node) -> bool {
+        let (word, mask) = Self::position(from, to);
+        self.bits.get(word).is_some_and(|bits| bits & mask != 0)
+    }
+
+    /// Returns the number of edges in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the set contains no edges.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the word and the mask of the bit of the edge between the two nodes.
+    #[inline(always)]
+    fn position(from: Node, to: Node) -> (usize, u64) {
+        let index = get_lower_triangle_matrix_entry(from.0, to.0);
+        (
+            index / u64::BITS as usize,
+            1 << (index % u64::BITS as usize),
+        )
+    }
+}
+
+impl FromIterator<UnEdge> for FixedEdgeSet {
+    fn from_iter<I: IntoIterator<Item = UnEdge>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for edge in iter {
+            set.insert(edge);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_edge_set_orientation() {
+        let mut set = FixedEdgeSet::new();
+        assert!(set.is_empty());
+        assert!(!set.contains(UnEdge::new(Node(3), Node(70))));
+
+        assert!(set.insert(UnEdge::new(Node(3), Node(70))));
+        assert!(!set.insert(UnEdge::new(Node(70), Node(3))));
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(UnEdge::new(Node(70), Node(3))));
+        assert!(set.contains_nodes(Node(3), Node(70)));
+        assert!(!set.contains_nodes(Node(3), Node(69)));
+        assert!(!set.contains_nodes(Node(1000), Node(2000)));
+    }
+
+    #[test]
+    fn test_fixed_edge_set_from_edges() {
+        let edges: Vec<UnEdge> = (0..100).map(|i| UnEdge::from((i, (i * 7) % 100))).collect();
+        let set = FixedEdgeSet::from_edges(&edges);
+        assert_eq!(set, edges.iter().copied().collect());
+        for from in 0..100 {
+            for to in 0..100 {
+                let edge = UnEdge::from((from, to));
+                assert_eq!(set.contains(edge), edges.contains(&edge));
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Conversion of distance matrices to [`petgraph`] graphs. Only available with the `petgraph`
+//! feature.
+
+use petgraph::graph::{NodeIndex, UnGraph};
+
+use crate::instance::{
+    distance::{Distance, DistanceSource},
+    matrix::{Matrix, MatrixSym, TriangularLayout},
+    node::Node,
+};
+
+impl Matrix<Distance> {
+    /// Converts the distances to a complete undirected graph, in which node `i` has the index `i`
+    /// and each edge is weighted with its distance.
+    ///
+    /// Only the entries below the diagonal are used, so the matrix should be
+    /// [symmetric](Matrix::is_symmetric).
+    pub fn to_petgraph(&self) -> UnGraph<(), Distance> {
+        complete_graph(self)
+    }
+}
+
+impl<Layout: TriangularLayout> MatrixSym<Distance, Layout> {
+    /// Converts the distances to a complete undirected graph, in which node `i` has the index `i`
+    /// and each edge is weighted with its distance.
+    pub fn to_petgraph(&self) -> UnGraph<(), Distance> {
+        complete_graph(self)
+    }
+}
+
+/// Builds the complete graph with one edge `(from, to)` for each `from > to`, weighted with the
+/// distance from `from` to `to`.
+fn complete_graph(distances: &impl DistanceSource) -> UnGraph<(), Distance> {
+    let dimension = distances.dimension();
+    let mut graph = UnGraph::with_capacity(dimension, dimension * dimension.saturating_sub(1) / 2);
+    for _ in 0..dimension {
+        graph.add_node(());
+    }
+    for from in 0..dimension {
+        for to in 0..from {
+            graph.add_edge(
+                NodeIndex::new(from),
+                NodeIndex::new(to),
+                distances.distance(Node(from), Node(to)),
+            );
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_petgraph_complete_graph() {
+        let distances =
+            MatrixSym::new_from_distance_function(4, |from, to| Distance((from.0 * to.0) as i32));
+        let graph = distances.to_petgraph();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 6);
+        for edge in graph.edge_indices() {
+            let (from, to) = graph.edge_endpoints(edge).unwrap();
+            assert_eq!(
+                graph[edge],
+                distances.get_data(Node(from.index()), Node(to.index()))
+            );
+        }
+        assert_eq!(
+            distances.to_non_symmetric().to_petgraph().edge_count(),
+            graph.edge_count()
+        );
+    }
+}
@@ -1,11 +1,56 @@
-use std::fmt::Display;
+use alloc::{format, vec, vec::Vec};
+use core::fmt::Display;
 
-use crate::instance::node::Node;
+use crate::instance::{
+    distance::{Distance, DistanceSource},
+    edge::UnEdge,
+    node::Node,
+};
 
+#[cfg(feature = "petgraph")]
+mod graph;
 pub(crate) mod symmetric;
-pub use symmetric::{
-    MatrixSym, get_lower_triangle_matrix_entry, get_lower_triangle_matrix_entry_row_bigger,
-};
+pub use symmetric::{LowerTriangular, MatrixSym, TriangularLayout, UpperTriangular};
+
+/// Read access to the entries of a matrix (distances or other per-edge data), independent of how
+/// the matrix is stored.
+///
+/// Implementations pick the fastest access for their layout internally, so code generic over this
+/// trait does not have to know whether the full matrix or only the lower triangle is stored. The
+/// layout specific accessors like [`MatrixSym::get_data_from_bigger`] remain available as
+/// optimizations for hot loops which know the relation of the indices.
+///
+/// This is implemented for matrices of any data as well as for views into matrices. Distance
+/// matrices of instances additionally implement [`DistanceSource`] on top of it.
+pub trait ReadDistance {
+    /// The type of the entries.
+    type Data: Copy;
+
+    /// Returns the entry at (from, to).
+    fn get(&self, from: Node, to: Node) -> Self::Data;
+}
+
+/// Error returned by [`Matrix::try_new`] and [`MatrixSym::try_new`] if the length of the data does
+/// not match the dimension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixLengthError {
+    /// The length the data should have had for the given dimension.
+    pub expected: usize,
+    /// The actual length of the data.
+    pub found: usize,
+}
+
+impl core::fmt::Display for MatrixLengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "MatrixLengthError: expected data of length {}, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl core::error::Error for MatrixLengthError {}
 
 #[derive(Debug, Clone)]
 /// Row major full matrix to store arbitrary edge data.
@@ -22,12 +67,27 @@ pub struct Matrix<Data> {
 impl<Data> Matrix<Data> {
     /// Create a new EdgeDataMatrix from raw data and dimension.
     ///
-    /// Panics if the length of data does not equal dimension * dimension.
+    /// Panics if the length of data does not equal dimension * dimension. See [`Matrix::try_new`]
+    /// for a non-panicking version.
     pub fn new(data: Vec<Data>, dimension: usize) -> Matrix<Data> {
         assert_eq!(data.len(), dimension * dimension);
         Matrix { data, dimension }
     }
 
+    /// Create a new EdgeDataMatrix from raw data and dimension.
+    ///
+    /// Returns an error if the length of data does not equal dimension * dimension.
+    pub fn try_new(data: Vec<Data>, dimension: usize) -> Result<Matrix<Data>, MatrixLengthError> {
+        let expected = dimension * dimension;
+        if data.len() != expected {
+            return Err(MatrixLengthError {
+                expected,
+                found: data.len(),
+            });
+        }
+        Ok(Matrix { data, dimension })
+    }
+
     /// Returns the dimension of the matrix. That is, the number of nodes, which is the same as the
     /// number of rows and columns.
     pub fn dimension(&self) -> usize {
@@ -41,6 +101,12 @@ impl<Data> Matrix<Data> {
         &self.data
     }
 
+    /// Iterates over the rows of the matrix, such that the `to`-th entry of the row of `from` is
+    /// the data at (from, to).
+    pub fn rows(&self) -> impl Iterator<Item = &[Data]> + '_ {
+        (0..self.dimension).map(|row| &self.data[row * self.dimension..(row + 1) * self.dimension])
+    }
+
     /// Create a new EdgeDataMatrix from a distance function.
     ///
     /// The distance function must not necessarily be symmetric.
@@ -57,12 +123,49 @@ impl<Data> Matrix<Data> {
     }
 }
 
+impl<Data: PartialEq> Matrix<Data> {
+    /// Returns whether the entries (from, to) and (to, from) are equal for all pairs of nodes.
+    pub fn is_symmetric(&self) -> bool {
+        (0..self.dimension).all(|row| {
+            (0..row).all(|column| {
+                self.data[self.get_index(Node(row), Node(column))]
+                    == self.data[self.get_index(Node(column), Node(row))]
+            })
+        })
+    }
+}
+
+impl<Data: Copy> ReadDistance for Matrix<Data> {
+    type Data = Data;
+
+    #[inline(always)]
+    fn get(&self, from: Node, to: Node) -> Data {
+        self.get_data(from, to)
+    }
+}
+
+impl DistanceSource for Matrix<Distance> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn stored_distances(&self) -> usize {
+        self.data.len()
+    }
+}
+
 impl<Data: Clone> Matrix<Data> {
     /// Create a new EdgeDataMatrix from dimension, filling all entries with the given value.
     pub fn new_from_dimension_with_value(dimension: usize, value: Data) -> Self {
         let size = dimension * dimension;
         Matrix::new(vec![value; size], dimension)
     }
+
+    /// Copies the matrix into one vector per row, e.g. to pass it to libraries which expect
+    /// nested vectors instead of the flat storage. See [`Matrix::rows`] to avoid the copy.
+    pub fn to_dense_rows(&self) -> Vec<Vec<Data>> {
+        self.rows().map(<[Data]>::to_vec).collect()
+    }
 }
 
 impl<Data: Copy> Matrix<Data> {
@@ -92,12 +195,92 @@ impl<Data: Copy> Matrix<Data> {
         &self.data[start_index..start_index + self.dimension]
     }
 
+    /// Iterate over the row of the given 'from' node, yielding each 'to' node together with the
+    /// data at (from, to).
+    ///
+    /// The entry (from, from) is skipped.
+    pub fn iter_row(&self, from: Node) -> impl Iterator<Item = (Node, Data)> + '_ {
+        self.get_adjacency_list(from)
+            .iter()
+            .enumerate()
+            .filter(move |&(to, _)| to != from.0)
+            .map(|(to, &data)| (Node(to), data))
+    }
+
     /// Set data symmetrically. That is, sets both (from, to) and (to, from).
     #[inline(always)]
     pub fn set_data_symmetric(&mut self, from: Node, to: Node, data: Data) {
         self.set_data(from, to, data);
         self.set_data(to, from, data);
     }
+
+    /// Set data symmetrically for each of the given edges, see [`Matrix::set_data_symmetric`].
+    ///
+    /// Useful to fix or exclude a batch of edges at once, e.g. the fixed edges of an instance.
+    pub fn set_edges(&mut self, edges: &[UnEdge], data: Data) {
+        for edge in edges {
+            self.set_data_symmetric(edge.from, edge.to, data);
+        }
+    }
+
+    /// Set the data of all entries in the row of the given 'from' node, that is, (from, to) for
+    /// every node 'to'.
+    ///
+    /// Like [`Matrix::iter_row`], the entry (from, from) is skipped. See
+    /// [`Matrix::set_row_symmetric`] to also set the column of the node.
+    pub fn set_row(&mut self, from: Node, data: Data) {
+        let start_index = self.get_index(from, Node(0));
+        let row = &mut self.data[start_index..start_index + self.dimension];
+        row[..from.0].fill(data);
+        row[from.0 + 1..].fill(data);
+    }
+
+    /// Set the data of all edges incident to the given node symmetrically, that is, both the row
+    /// and the column of the node, skipping the entry (node, node).
+    ///
+    /// Useful to exclude all edges of a node at once, e.g. during edge elimination.
+    pub fn set_row_symmetric(&mut self, node: Node, data: Data) {
+        self.set_row(node, data);
+        for to in (0..self.dimension).filter(|&to| to != node.0) {
+            self.set_data(Node(to), node, data);
+        }
+    }
+
+    /// Returns the submatrix induced by the given nodes, together with the mapping from the nodes
+    /// of the submatrix to the original nodes.
+    ///
+    /// Node `i` of the submatrix corresponds to node `mapping[i]`, which is `nodes[i]`, of this
+    /// matrix. That is, the entry (i, j) of the submatrix is the entry `(nodes[i], nodes[j])`
+    /// of this matrix. Useful for solving parts of an instance separately, as tours on the
+    /// submatrix can be translated back via the mapping.
+    ///
+    /// Panics if one of the nodes is out of bounds.
+    pub fn induced_subgraph(&self, nodes: &[Node]) -> (Matrix<Data>, Vec<Node>) {
+        let submatrix = Matrix::new_from_distance_function(nodes.len(), |from, to| {
+            self.get_data(nodes[from.0], nodes[to.0])
+        });
+        (submatrix, nodes.to_vec())
+    }
+
+    /// Returns the transposed matrix, that is, the entry (from, to) of the result is the entry
+    /// (to, from) of this matrix.
+    ///
+    /// For distances of an asymmetric instance, this reverses the direction of all arcs. Each
+    /// tour of the transposed instance corresponds to the reversed tour of the original instance
+    /// with the same cost, so both have the same optimal cost. Symmetric matrices are their own
+    /// transpose.
+    pub fn transpose(&self) -> Matrix<Data> {
+        Matrix::new_from_distance_function(self.dimension, |from, to| self.get_data(to, from))
+    }
+
+    /// Converts to a [`MatrixSym`] by keeping the lower triangle, including the diagonal. Inverse
+    /// of [`MatrixSym::to_non_symmetric`].
+    ///
+    /// The entries above the diagonal are dropped, so the matrix should be
+    /// [symmetric](Matrix::is_symmetric).
+    pub fn to_symmetric(&self) -> MatrixSym<Data> {
+        MatrixSym::new_from_distance_function(self.dimension, |from, to| self.get_data(from, to))
+    }
 }
 
 impl<Data> Matrix<Data> {
@@ -128,14 +311,13 @@ impl<Data> Matrix<Data> {
 }
 
 impl<Data: Display + Ord + Copy> Display for Matrix<Data> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let max_value = self
             .data
             .iter()
             .max()
             .expect("Matrix should have at least one entry for display");
         let max_len = format!("{}", max_value).len();
-        println!("Max len   : {}", max_len);
         for row in 0..self.dimension {
             for column in 0..self.dimension {
                 let value = self.get_data(Node(row), Node(column));
@@ -147,7 +329,7 @@ impl<Data: Display + Ord + Copy> Display for Matrix<Data> {
     }
 }
 
-/// View of an [EdgeDataMatrix] with the zero-eth row removed.
+/// View of a [`Matrix`] with the zero-eth row removed.
 ///
 /// I.e. a (n-1) x n matrix where row 0 corresponds to node 1, row 1 to node 2, ..., row n-1 to node
 /// n. Data is borrowed from the original matrix, i.e. its lifetime is tied to that of the original
@@ -178,3 +360,138 @@ impl<'a, Data: Copy> MatrixViewZeroRemoved<'a, Data> {
         &self.data[start_index..start_index + self.dimension]
     }
 }
+
+/// Rows are indexed by the nodes of the underlying matrix, i.e. `from` has to be at least 1.
+impl<Data: Copy> ReadDistance for MatrixViewZeroRemoved<'_, Data> {
+    type Data = Data;
+
+    #[inline(always)]
+    fn get(&self, from: Node, to: Node) -> Data {
+        debug_assert!(from.0 >= 1);
+        self.data[(from.0 - 1) * self.dimension + to.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_induced_subgraph_matches_original() {
+        let dimension = 6;
+        let matrix = Matrix::new_from_distance_function(dimension, |from, to| from.0 * 10 + to.0);
+        let nodes = [Node(4), Node(1), Node(5)];
+
+        let (submatrix, mapping) = matrix.induced_subgraph(&nodes);
+        assert_eq!(submatrix.dimension(), nodes.len());
+        assert_eq!(mapping, nodes);
+        for from in 0..nodes.len() {
+            for to in 0..nodes.len() {
+                assert_eq!(
+                    submatrix.get_data(Node(from), Node(to)),
+                    matrix.get_data(mapping[from], mapping[to])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_induced_subgraph_empty() {
+        let matrix = Matrix::new_from_dimension_with_value(3, 1);
+        let (submatrix, mapping) = matrix.induced_subgraph(&[]);
+        assert_eq!(submatrix.dimension(), 0);
+        assert!(mapping.is_empty());
+    }
+
+    #[test]
+    fn test_transpose_swaps_entries() {
+        let matrix = Matrix::new_from_distance_function(4, |from, to| from.0 * 10 + to.0);
+        let transposed = matrix.transpose();
+        assert_eq!(transposed.dimension(), 4);
+        for from in 0..4 {
+            for to in 0..4 {
+                assert_eq!(
+                    transposed.get_data(Node(from), Node(to)),
+                    matrix.get_data(Node(to), Node(from))
+                );
+            }
+        }
+        assert_eq!(transposed.transpose().data(), matrix.data());
+    }
+
+    #[test]
+    fn test_transpose_of_symmetric_matrix_is_no_op() {
+        let matrix =
+            Matrix::new_from_distance_function(5, |from, to| from.0.abs_diff(to.0) * 7 + 1);
+        assert!(matrix.is_symmetric());
+        assert_eq!(matrix.transpose().data(), matrix.data());
+
+        let empty = Matrix::<usize>::new_from_dimension_with_value(0, 0);
+        assert_eq!(empty.transpose().dimension(), 0);
+    }
+
+    #[test]
+    fn test_to_dense_rows() {
+        let distance_function = |from: Node, to: Node| from.0.max(to.0) * 10 + from.0.min(to.0);
+        let matrix = Matrix::new_from_distance_function(3, distance_function);
+        let expected = vec![vec![0, 10, 20], vec![10, 11, 21], vec![20, 21, 22]];
+        assert_eq!(matrix.to_dense_rows(), expected);
+        assert!(matrix.rows().eq(expected.iter().map(Vec::as_slice)));
+
+        let matrix_sym = MatrixSym::new_from_distance_function(3, distance_function);
+        assert_eq!(matrix_sym.to_dense_rows(), expected);
+
+        let empty = Matrix::<usize>::new_from_dimension_with_value(0, 0);
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_set_edges_and_rows() {
+        let mut matrix = Matrix::new_from_dimension_with_value(4, 0);
+        matrix.set_edges(
+            &[UnEdge::new(Node(0), Node(1)), UnEdge::new(Node(3), Node(2))],
+            1,
+        );
+        assert!(matrix.is_symmetric());
+        assert_eq!(matrix.data().iter().filter(|&&data| data == 1).count(), 4);
+        assert_eq!(matrix.get_data(Node(2), Node(3)), 1);
+
+        matrix.set_row(Node(2), 2);
+        assert_eq!(matrix.get_adjacency_list(Node(2)), &[2, 2, 0, 2]);
+        assert_eq!(matrix.get_data(Node(3), Node(2)), 1);
+
+        matrix.set_row_symmetric(Node(1), 3);
+        assert_eq!(matrix.get_adjacency_list(Node(1)), &[3, 0, 3, 3]);
+        assert!((0..4).all(|from| from == 1 || matrix.get_data(Node(from), Node(1)) == 3));
+        assert_eq!(matrix.get_data(Node(1), Node(1)), 0);
+    }
+
+    #[test]
+    fn test_read_distance_independent_of_layout() {
+        fn read_all(
+            matrix: &impl ReadDistance<Data = usize>,
+            nodes: core::ops::Range<usize>,
+        ) -> Vec<usize> {
+            nodes
+                .clone()
+                .flat_map(|from| (0..6).map(move |to| (from, to)))
+                .map(|(from, to)| matrix.get(Node(from), Node(to)))
+                .collect()
+        }
+
+        let distance_function = |from: Node, to: Node| from.0.max(to.0) * 10 + from.0.min(to.0);
+        let matrix = Matrix::new_from_distance_function(6, distance_function);
+        let matrix_sym = MatrixSym::new_from_distance_function(6, distance_function);
+        assert_eq!(read_all(&matrix, 0..6), read_all(&matrix_sym, 0..6));
+
+        // The view without row 0 is still indexed by the original nodes
+        let (_, zero_removed) = matrix.split_first_row();
+        assert_eq!(read_all(&zero_removed, 1..6), read_all(&matrix, 1..6));
+
+        let restricted = matrix_sym.restrict_to_first_n(4);
+        assert_eq!(
+            restricted.get(Node(3), Node(1)),
+            matrix.get(Node(1), Node(3))
+        );
+    }
+}
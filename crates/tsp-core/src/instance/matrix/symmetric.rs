@@ -1,36 +1,115 @@
-use std::fmt::Display;
+use alloc::{format, vec, vec::Vec};
+use core::{fmt::Display, marker::PhantomData};
 
-use crate::instance::{matrix::Matrix, node::Node};
+use crate::{
+    instance::{
+        distance::{Distance, DistanceSource},
+        matrix::{Matrix, MatrixLengthError, ReadDistance},
+        node::Node,
+    },
+    triangular::{
+        get_lower_triangle_matrix_entry, get_lower_triangle_matrix_entry_column_bigger,
+        get_lower_triangle_matrix_entry_row_bigger,
+    },
+};
 
-/// A row-major lower-triangular matrix to store arbitrary symmetric edge data.
+/// The order in which a [`MatrixSym`] packs the entries of one triangle (including the diagonal)
+/// of a symmetric matrix.
+pub trait TriangularLayout {
+    /// Returns the index of the entry (row, column) in the packed data of a matrix with the given
+    /// dimension, assuming 'row' is bigger than or equal to 'column'.
+    fn index(row: usize, column: usize, dimension: usize) -> usize;
+
+    /// Returns the (row, column) pairs with row >= column of all entries in the packed order.
+    fn entries(dimension: usize) -> impl Iterator<Item = (usize, usize)>;
+}
+
+/// Packs the lower triangle in row-major order, i.e. (0, 0), (1, 0), (1, 1), (2, 0), ... This is
+/// the default layout of [`MatrixSym`].
+///
+/// The entries (i, 0), ..., (i, i) of each row are contiguous, and the matrix of the first n
+/// nodes is a prefix of the data, see [`MatrixSym::restrict_to_first_n`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowerTriangular;
+
+/// Packs the upper triangle in row-major order, i.e. (0, 0), (0, 1), ..., (0, n - 1), (1, 1), ...
+///
+/// The entries (i, i), ..., (i, n - 1) of each row are contiguous. Scanning whole rows like in
+/// Prim's algorithm is not faster than with [`LowerTriangular`] (see the `triangular_layout`
+/// benchmark of `tsp-solvers` on d1291), which thus stays the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UpperTriangular;
+
+impl TriangularLayout for LowerTriangular {
+    #[inline(always)]
+    fn index(row: usize, column: usize, _dimension: usize) -> usize {
+        get_lower_triangle_matrix_entry_row_bigger(row, column)
+    }
+
+    fn entries(dimension: usize) -> impl Iterator<Item = (usize, usize)> {
+        (0..dimension).flat_map(|row| (0..=row).map(move |column| (row, column)))
+    }
+}
+
+impl TriangularLayout for UpperTriangular {
+    #[inline(always)]
+    fn index(row: usize, column: usize, dimension: usize) -> usize {
+        debug_assert!(
+            row >= column,
+            "Row {row} has to be bigger than or equal to column {column}"
+        );
+        // The entry (row, column) is stored as (column, row) of the upper triangle, after the
+        // column rows of lengths n, n - 1, ..., n - column + 1
+        column * (2 * dimension - column + 1) / 2 + (row - column)
+    }
+
+    fn entries(dimension: usize) -> impl Iterator<Item = (usize, usize)> {
+        (0..dimension).flat_map(move |column| (column..dimension).map(move |row| (row, column)))
+    }
+}
+
+/// A matrix to store arbitrary symmetric edge data, only storing one triangle (including the
+/// diagonal) in the order given by the layout, by default the [row-major lower
+/// triangle](LowerTriangular).
 ///
 /// The underlying data is guaranteed to have length dimension * (dimension + 1) / 2 where dimension
 /// is the number of nodes.
 #[derive(Debug, Clone)]
-pub struct MatrixSym<Data> {
+pub struct MatrixSym<Data, Layout = LowerTriangular> {
     data: Vec<Data>,
     dimension: usize,
+    layout: PhantomData<Layout>,
 }
 
 impl<Data> MatrixSym<Data> {
     /// Create a new EdgeDataMatrixSym from raw data and dimension.
     ///
-    /// Panics if the length of data does not equal dimension * dimension.
+    /// Panics if the length of data does not equal dimension * (dimension + 1) / 2. See
+    /// [`MatrixSym::try_new`] for a non-panicking version.
     pub fn new(data: Vec<Data>, dimension: usize) -> Self {
-        assert_eq!(data.len(), dimension * (dimension + 1) / 2);
-        Self { data, dimension }
+        Self::new_with_layout(data, dimension)
     }
 
-    /// Returns a reference to the underlying data.
-    pub fn data(&self) -> &Vec<Data> {
-        &self.data
+    /// Create a new EdgeDataMatrixSym from raw data and dimension.
+    ///
+    /// Returns an error if the length of data does not equal dimension * (dimension + 1) / 2.
+    pub fn try_new(data: Vec<Data>, dimension: usize) -> Result<Self, MatrixLengthError> {
+        let expected = dimension * (dimension + 1) / 2;
+        if data.len() != expected {
+            return Err(MatrixLengthError {
+                expected,
+                found: data.len(),
+            });
+        }
+        Ok(Self::new(data, dimension))
     }
 
-    /// Set the data at (from, to).
-    #[inline(always)]
-    pub fn set_data(&mut self, from: Node, to: Node, data: Data) {
-        let index = get_lower_triangle_matrix_entry(from.0, to.0);
-        self.data[index] = data;
+    /// Create a new EdgeDataMatrixSym from a distance function.
+    pub fn new_from_distance_function(
+        dimension: usize,
+        distance_function: impl Fn(Node, Node) -> Data,
+    ) -> Self {
+        Self::new_from_distance_function_with_layout(dimension, distance_function)
     }
 
     /// Set the data at (from, to), assuming 'from' is bigger than or equal to 'to'.
@@ -59,32 +138,125 @@ impl<Data> MatrixSym<Data> {
             dimension: n,
         }
     }
+}
 
-    /// Create a new EdgeDataMatrixSym from a distance function.
-    pub fn new_from_distance_function(
+impl<Data, Layout: TriangularLayout> MatrixSym<Data, Layout> {
+    /// Create a new EdgeDataMatrixSym with the given layout from raw data in the order of the
+    /// layout and dimension.
+    ///
+    /// Panics if the length of data does not equal dimension * (dimension + 1) / 2.
+    pub fn new_with_layout(data: Vec<Data>, dimension: usize) -> Self {
+        assert_eq!(data.len(), dimension * (dimension + 1) / 2);
+        Self {
+            data,
+            dimension,
+            layout: PhantomData,
+        }
+    }
+
+    /// Create a new EdgeDataMatrixSym with the given layout from a distance function.
+    pub fn new_from_distance_function_with_layout(
         dimension: usize,
         distance_function: impl Fn(Node, Node) -> Data,
     ) -> Self {
-        let data: Vec<_> = (0..dimension)
-            .flat_map(|row| (0..=row).map(move |column| (Node(row), Node(column))))
-            .map(|(from, to)| distance_function(from, to))
+        let data: Vec<_> = Layout::entries(dimension)
+            .map(|(row, column)| distance_function(Node(row), Node(column)))
             .collect();
 
-        MatrixSym::new(data, dimension)
+        Self::new_with_layout(data, dimension)
+    }
+
+    /// Returns the dimension of the matrix. That is, the number of nodes, which is the same as the
+    /// number of rows and columns.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns a reference to the underlying data.
+    pub fn data(&self) -> &Vec<Data> {
+        &self.data
+    }
+
+    /// Set the data at (from, to).
+    #[inline(always)]
+    pub fn set_data(&mut self, from: Node, to: Node, data: Data) {
+        let index = Layout::index(from.0.max(to.0), from.0.min(to.0), self.dimension);
+        self.data[index] = data;
     }
 }
 
-impl<Data: Copy> MatrixSym<Data> {
+impl<Data: Copy, Layout: TriangularLayout> ReadDistance for MatrixSym<Data, Layout> {
+    type Data = Data;
+
+    #[inline(always)]
+    fn get(&self, from: Node, to: Node) -> Data {
+        self.get_data(from, to)
+    }
+}
+
+impl<Layout: TriangularLayout> DistanceSource for MatrixSym<Distance, Layout> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn stored_distances(&self) -> usize {
+        self.data.len()
+    }
+
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+}
+
+impl<Data: Copy, Layout: TriangularLayout> MatrixSym<Data, Layout> {
     /// Access the data at (from, to).
     ///
-    /// It might be faster to use `get_data_from_bigger` or `get_data_to_bigger` if you know
-    /// which of 'from' or 'to' is bigger.
+    /// For the default layout, it might be faster to use `get_data_from_bigger` or
+    /// `get_data_to_bigger` if you know which of 'from' or 'to' is bigger.
     #[inline(always)]
     pub fn get_data(&self, from: Node, to: Node) -> Data {
-        let index = get_lower_triangle_matrix_entry(from.0, to.0);
+        let index = Layout::index(from.0.max(to.0), from.0.min(to.0), self.dimension);
         self.data[index]
     }
 
+    /// Converts to a full, non-symmetric [`Matrix`] by duplicating the data, e.g. to use solvers
+    /// which require one.
+    ///
+    /// The resulting matrix stores `n^2` entries, so it takes about twice the memory.
+    pub fn to_non_symmetric(&self) -> Matrix<Data> {
+        Matrix::new_from_distance_function(self.dimension, |from, to| self.get_data(from, to))
+    }
+
+    /// Copies the full (logical) matrix into one vector per row, e.g. to pass it to libraries
+    /// which expect nested vectors instead of the triangular storage.
+    pub fn to_dense_rows(&self) -> Vec<Vec<Data>> {
+        (0..self.dimension)
+            .map(|from| {
+                (0..self.dimension)
+                    .map(|to| self.get_data(Node(from), Node(to)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<Data: Copy> MatrixSym<Data, UpperTriangular> {
+    /// Writes the (logical) row of the given 'from' node into the buffer, such that
+    /// `buffer[to]` contains the data at (from, to), including the diagonal entry.
+    ///
+    /// Like [`MatrixSym::row_into`] of the default layout, but the entries from the diagonal on
+    /// are contiguous, while the ones before it are gathered from the column of 'from'.
+    pub fn row_into(&self, from: Node, buffer: &mut Vec<Data>) {
+        buffer.clear();
+        buffer.reserve(self.dimension);
+        buffer.extend((0..from.0).map(|to| self.get_data(from, Node(to))));
+        // The entries (from, from), ..., (from, n - 1) are stored contiguously
+        let row_start = UpperTriangular::index(from.0, from.0, self.dimension);
+        buffer.extend_from_slice(&self.data[row_start..row_start + self.dimension - from.0]);
+    }
+}
+
+impl<Data: Copy> MatrixSym<Data> {
     /// Access the data at (from, to), assuming 'from' is bigger than or equal to 'to'.
     ///
     /// May return wrong data, if 'from' is smaller than 'to'.
@@ -99,23 +271,46 @@ impl<Data: Copy> MatrixSym<Data> {
     /// May return wrong data, if 'to' is smaller than 'from'.
     #[inline(always)]
     pub fn get_data_to_bigger(&self, from: Node, to: Node) -> Data {
-        let index = get_lower_triangle_matrix_entry_row_bigger(to.0, from.0);
+        let index = get_lower_triangle_matrix_entry_column_bigger(from.0, to.0);
         self.data[index]
     }
 
-    /// Convert to a non-symmetric [crate::instance::edge::data::EdgeDataMatrix] by duplicating the
-    /// data.
-    pub fn to_edge_data_matrix(&self) -> Matrix<Data> {
-        let dimension = self.dimension;
-        let mut data = vec![self.data[0].clone(); dimension * dimension];
-        for row in 0..dimension {
-            for column in 0..row {
-                let value = self.get_data_from_bigger(Node(row), Node(column));
-                data[row * self.dimension + column] = value.clone();
-                data[column * self.dimension + row] = value;
-            }
-        }
-        Matrix::new(data, self.dimension)
+    /// Iterate over the row of the given 'from' node, yielding each 'to' node together with the
+    /// data at (from, to).
+    ///
+    /// The entry (from, from) is skipped. Yields the same (logical) row as
+    /// [`Matrix::iter_row`] would for the full matrix, even though only the lower triangle is
+    /// stored.
+    pub fn iter_row(&self, from: Node) -> impl Iterator<Item = (Node, Data)> + '_ {
+        // The entries (from, 0), ..., (from, from - 1) are stored contiguously
+        let row_start = get_lower_triangle_matrix_entry_row_bigger(from.0, 0);
+        let lower = self.data[row_start..row_start + from.0]
+            .iter()
+            .enumerate()
+            .map(|(to, &data)| (Node(to), data));
+        // The entries (from, from + 1), ... are stored in the column of 'from'
+        let upper = (from.0 + 1..self.dimension)
+            .map(move |to| (Node(to), self.get_data_from_bigger(Node(to), from)));
+        lower.chain(upper)
+    }
+
+    /// Writes the (logical) row of the given 'from' node into the buffer, such that
+    /// `buffer[to]` contains the data at (from, to), including the diagonal entry.
+    ///
+    /// The buffer is cleared first, so it can be reused across rows to avoid allocations. As only
+    /// the lower triangle is stored, the entries after the diagonal are gathered from the column
+    /// of 'from', which is not contiguous in memory. For algorithms which scan whole rows
+    /// repeatedly (like Prim's algorithm), a full [`Matrix`] with
+    /// [`Matrix::get_adjacency_list`] is faster, if the memory is available.
+    pub fn row_into(&self, from: Node, buffer: &mut Vec<Data>) {
+        buffer.clear();
+        buffer.reserve(self.dimension);
+        // The entries (from, 0), ..., (from, from) are stored contiguously
+        let row_start = get_lower_triangle_matrix_entry_row_bigger(from.0, 0);
+        buffer.extend_from_slice(&self.data[row_start..=row_start + from.0]);
+        buffer.extend(
+            (from.0 + 1..self.dimension).map(|to| self.get_data_from_bigger(Node(to), from)),
+        );
     }
 }
 
@@ -127,8 +322,8 @@ impl<Data: Clone> MatrixSym<Data> {
     }
 }
 
-impl<Data: Display + Ord + Copy> Display for MatrixSym<Data> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<Data: Display + Ord + Copy, Layout: TriangularLayout> Display for MatrixSym<Data, Layout> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let max_value = self
             .data
             .iter()
@@ -137,7 +332,7 @@ impl<Data: Display + Ord + Copy> Display for MatrixSym<Data> {
         let max_len = format!("{}", max_value).len();
         for row in 0..self.dimension {
             for column in 0..=row {
-                let value = self.get_data_from_bigger(Node(row), Node(column));
+                let value = self.get_data(Node(row), Node(column));
                 write!(f, "{:max_len$} ", value)?;
             }
             writeln!(f)?;
@@ -199,27 +394,142 @@ impl<'a, Data: Copy> MatrixSymViewRestricted<'a, Data> {
     }
 }
 
-#[inline(always)]
-/// Computes the index of entry (row, column) in a vec-flattened lower-(left-)triangular matrix.
-pub fn get_lower_triangle_matrix_entry(row: usize, column: usize) -> usize {
-    if row > column {
-        get_lower_triangle_matrix_entry_row_bigger(row, column)
-    } else {
-        get_lower_triangle_matrix_entry_row_bigger(column, row)
+impl<Data: Copy> ReadDistance for MatrixSymViewRestricted<'_, Data> {
+    type Data = Data;
+
+    #[inline(always)]
+    fn get(&self, from: Node, to: Node) -> Data {
+        self.get_data(from, to)
     }
 }
 
-#[inline(always)]
-/// Computes the index of entry (row, column) in a vec-flattened lower-(left-)triangular matrix,
-/// assuming row >= column.
-///
-/// For a function that assumes column >= row, just switch the parameters to this function.
-pub fn get_lower_triangle_matrix_entry_row_bigger(row: usize, column: usize) -> usize {
-    debug_assert!(
-        row >= column,
-        "get_lower_triangle_matrix_entry_row_bigger called with row < column: row {}, column {}",
-        row,
-        column
-    );
-    (row * (row + 1)) / 2 + column
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new_checks_length() {
+        assert!(MatrixSym::try_new(vec![0; 6], 3).is_ok());
+        assert_eq!(
+            MatrixSym::try_new(vec![0; 9], 3).unwrap_err(),
+            MatrixLengthError {
+                expected: 6,
+                found: 9
+            }
+        );
+        assert!(Matrix::try_new(vec![0; 9], 3).is_ok());
+        assert_eq!(
+            Matrix::try_new(vec![0; 6], 3).unwrap_err(),
+            MatrixLengthError {
+                expected: 9,
+                found: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_iter_row_matches_full_matrix() {
+        let dimension = 7;
+        let distance_function = |from: Node, to: Node| from.0 * 10 + to.0 + from.0 * to.0;
+        let symmetric = MatrixSym::new_from_distance_function(dimension, distance_function);
+        let full = Matrix::new_from_distance_function(dimension, |from, to| {
+            distance_function(from.max(to), from.min(to))
+        });
+
+        for from in 0..dimension {
+            let row_symmetric: Vec<_> = symmetric.iter_row(Node(from)).collect();
+            let row_full: Vec<_> = full.iter_row(Node(from)).collect();
+            assert_eq!(row_symmetric.len(), dimension - 1);
+            assert!(row_symmetric.iter().all(|(to, _)| *to != Node(from)));
+            assert_eq!(row_symmetric, row_full);
+        }
+    }
+
+    #[test]
+    fn test_row_into_matches_get_data() {
+        let dimension = 6;
+        let symmetric = MatrixSym::new_from_distance_function(dimension, |from, to| from.0 * to.0);
+
+        let mut buffer = Vec::new();
+        for from in 0..dimension {
+            symmetric.row_into(Node(from), &mut buffer);
+            let expected: Vec<_> = (0..dimension)
+                .map(|to| symmetric.get_data(Node(from), Node(to)))
+                .collect();
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_layout_entries_match_index() {
+        for dimension in 0..8 {
+            for (expected, (row, column)) in UpperTriangular::entries(dimension).enumerate() {
+                assert_eq!(UpperTriangular::index(row, column, dimension), expected);
+            }
+            for (expected, (row, column)) in LowerTriangular::entries(dimension).enumerate() {
+                assert_eq!(LowerTriangular::index(row, column, dimension), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_upper_layout_matches_lower_layout() {
+        let dimension = 7;
+        let distance_function = |from: Node, to: Node| from.0 * 10 + to.0 + from.0 * to.0;
+        let lower = MatrixSym::new_from_distance_function(dimension, distance_function);
+        let mut upper = MatrixSym::<_, UpperTriangular>::new_from_distance_function_with_layout(
+            dimension,
+            distance_function,
+        );
+
+        let mut lower_buffer = Vec::new();
+        let mut upper_buffer = Vec::new();
+        for from in 0..dimension {
+            for to in 0..dimension {
+                assert_eq!(
+                    upper.get_data(Node(from), Node(to)),
+                    lower.get_data(Node(from), Node(to))
+                );
+            }
+            lower.row_into(Node(from), &mut lower_buffer);
+            upper.row_into(Node(from), &mut upper_buffer);
+            assert_eq!(upper_buffer, lower_buffer);
+        }
+
+        upper.set_data(Node(2), Node(5), 0);
+        assert_eq!(upper.get_data(Node(5), Node(2)), 0);
+    }
+
+    #[test]
+    fn test_to_non_symmetric() {
+        let symmetric = MatrixSym::new_from_distance_function(5, |from, to| from.0 * 10 + to.0);
+        let full = symmetric.to_non_symmetric();
+        for from in (0..5).map(Node) {
+            for to in (0..5).map(Node) {
+                assert_eq!(full.get_data(from, to), symmetric.get_data(from, to));
+            }
+        }
+        // Including the diagonal
+        assert_eq!(full.get_data(Node(3), Node(3)), 33);
+
+        let empty = MatrixSym::<usize>::new(Vec::new(), 0);
+        assert_eq!(empty.to_non_symmetric().dimension(), 0);
+    }
+
+    #[test]
+    fn test_symmetric_round_trip_keeps_diagonal() {
+        // Rows of the lower triangle of dimension 3 with distinct non-zero diagonal entries
+        let data = vec![7, 1, 8, 2, 3, 9];
+        let symmetric = MatrixSym::new(data.clone(), 3);
+
+        let full = symmetric.to_non_symmetric();
+        assert!(full.is_symmetric());
+        for (node, diagonal) in [(0, 7), (1, 8), (2, 9)] {
+            assert_eq!(full.get_data(Node(node), Node(node)), diagonal);
+        }
+
+        let round_trip = full.to_symmetric();
+        assert_eq!(round_trip.dimension(), 3);
+        assert_eq!(round_trip.data(), &data);
+    }
 }
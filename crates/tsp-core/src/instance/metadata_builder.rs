@@ -1,5 +1,11 @@
-use thiserror::Error;
-use tsp_core::{
+/*!
+Builder for [`InstanceMetadata`], which allows constructing the metadata field by field, e.g. while
+parsing the specification part of a TSPLIB file.
+*/
+
+use alloc::string::String;
+
+use crate::{
     instance::InstanceMetadata,
     tsp_lib_spec::{
         DisplayDataType, EdgeDataFormat, EdgeWeightFormat, EdgeWeightType, NodeCoordType,
@@ -7,17 +13,23 @@ use tsp_core::{
     },
 };
 
-use crate::metadata::MetaDataParseError;
-
-#[derive(Error, Debug)]
+/// Error returned by [`InstanceMetadataBuilder::build`] if a required field has not been set.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InstanceMetadataBuilderError(&'static str);
 
-impl std::fmt::Display for InstanceMetadataBuilderError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InstanceMetadataBuilderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "InstanceMetadataBuilderError: {}", self.0)
     }
 }
 
+impl core::error::Error for InstanceMetadataBuilderError {}
+
+/// Builder for [`InstanceMetadata`].
+///
+/// The name, problem type, dimension and edge weight type are required, all other fields are
+/// optional. Each setter comes in a consuming variant and a `_mut` variant taking `&mut self`.
+#[derive(Debug, Clone)]
 pub struct InstanceMetadataBuilder {
     name: Option<String>,
     problem_type: Option<ProblemType>,
@@ -38,6 +50,7 @@ impl Default for InstanceMetadataBuilder {
 }
 
 impl InstanceMetadataBuilder {
+    /// Creates a builder with no fields set.
     pub fn new() -> Self {
         Self {
             name: None,
@@ -53,7 +66,18 @@ impl InstanceMetadataBuilder {
         }
     }
 
-    pub fn build(self) -> Result<InstanceMetadata, MetaDataParseError> {
+    /// Builds the metadata, failing if one of the required fields has not been set or the
+    /// dimension is zero.
+    pub fn build(self) -> Result<InstanceMetadata, InstanceMetadataBuilderError> {
+        let dimension = self
+            .dimension
+            .ok_or(InstanceMetadataBuilderError("dimension is required"))?;
+        if dimension == 0 {
+            return Err(InstanceMetadataBuilderError(
+                "dimension has to be at least 1",
+            ));
+        }
+
         Ok(InstanceMetadata {
             name: self
                 .name
@@ -62,9 +86,7 @@ impl InstanceMetadataBuilder {
                 .problem_type
                 .ok_or(InstanceMetadataBuilderError("problem_type is required"))?,
             comment: self.comment,
-            dimension: self
-                .dimension
-                .ok_or(InstanceMetadataBuilderError("dimension is required"))?,
+            dimension,
             capacity: self.capacity,
             edge_weight_type: self
                 .edge_weight_type
@@ -76,101 +98,121 @@ impl InstanceMetadataBuilder {
         })
     }
 
+    /// Sets the name.
     pub fn name(mut self, name: String) -> Self {
         self.name = Some(name);
         self
     }
 
+    /// Sets the name in place.
     pub fn name_mut(&mut self, name: String) -> &mut Self {
         self.name = Some(name);
         self
     }
 
+    /// Sets the problem type.
     pub fn problem_type(mut self, problem_type: ProblemType) -> Self {
         self.problem_type = Some(problem_type);
         self
     }
 
+    /// Sets the problem type in place.
     pub fn problem_type_mut(&mut self, problem_type: ProblemType) -> &mut Self {
         self.problem_type = Some(problem_type);
         self
     }
 
+    /// Sets the comment.
     pub fn comment(mut self, comment: String) -> Self {
         self.comment = Some(comment);
         self
     }
 
+    /// Sets the comment in place.
     pub fn comment_mut(&mut self, comment: String) -> &mut Self {
         self.comment = Some(comment);
         self
     }
 
+    /// Sets the dimension.
     pub fn dimension(mut self, dimension: usize) -> Self {
         self.dimension = Some(dimension);
         self
     }
 
+    /// Sets the dimension in place.
     pub fn dimension_mut(&mut self, dimension: usize) -> &mut Self {
         self.dimension = Some(dimension);
         self
     }
 
+    /// Sets the capacity.
     pub fn capacity(mut self, capacity: usize) -> Self {
         self.capacity = Some(capacity);
         self
     }
 
+    /// Sets the capacity in place.
     pub fn capacity_mut(&mut self, capacity: usize) -> &mut Self {
         self.capacity = Some(capacity);
         self
     }
 
+    /// Sets the edge weight type.
     pub fn edge_weight_type(mut self, edge_weight_type: EdgeWeightType) -> Self {
         self.edge_weight_type = Some(edge_weight_type);
         self
     }
 
+    /// Sets the edge weight type in place.
     pub fn edge_weight_type_mut(&mut self, edge_weight_type: EdgeWeightType) -> &mut Self {
         self.edge_weight_type = Some(edge_weight_type);
         self
     }
 
+    /// Sets the edge weight format.
     pub fn edge_weight_format(mut self, edge_weight_format: EdgeWeightFormat) -> Self {
         self.edge_weight_format = Some(edge_weight_format);
         self
     }
 
+    /// Sets the edge weight format in place.
     pub fn edge_weight_format_mut(&mut self, edge_weight_format: EdgeWeightFormat) -> &mut Self {
         self.edge_weight_format = Some(edge_weight_format);
         self
     }
 
+    /// Sets the edge data format.
     pub fn edge_data_format(mut self, edge_data_format: EdgeDataFormat) -> Self {
         self.edge_data_format = Some(edge_data_format);
         self
     }
 
+    /// Sets the edge data format in place.
     pub fn edge_data_format_mut(&mut self, edge_data_format: EdgeDataFormat) -> &mut Self {
         self.edge_data_format = Some(edge_data_format);
         self
     }
 
+    /// Sets the node coord type.
     pub fn node_coord_type(mut self, node_coord_type: NodeCoordType) -> Self {
         self.node_coord_type = Some(node_coord_type);
         self
     }
 
+    /// Sets the node coord type in place.
     pub fn node_coord_type_mut(&mut self, node_coord_type: NodeCoordType) -> &mut Self {
         self.node_coord_type = Some(node_coord_type);
         self
     }
 
+    /// Sets the display data type.
     pub fn display_data_type(mut self, display_data_type: DisplayDataType) -> Self {
         self.display_data_type = Some(display_data_type);
         self
     }
 
+    /// Sets the display data type in place.
     pub fn display_data_type_mut(&mut self, display_data_type: DisplayDataType) -> &mut Self {
         self.display_data_type = Some(display_data_type);
         self
@@ -1,10 +1,13 @@
-use std::fmt::Display;
+use alloc::{format, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt::Display};
 
 use crate::{
     instance::{
-        distance::Distance,
+        distance::{Distance, DistanceSource},
         edge::UnEdge,
+        edge_set::FixedEdgeSet,
         matrix::{Matrix, MatrixSym},
+        node::Node,
     },
     tsp_lib_spec::{
         DisplayDataType, EdgeDataFormat, EdgeWeightFormat, EdgeWeightType, NodeCoordType,
@@ -14,8 +17,11 @@ use crate::{
 
 pub mod distance;
 pub mod edge;
+pub mod edge_set;
 pub mod matrix;
+pub mod metadata_builder;
 pub mod node;
+pub mod tour_order;
 
 #[derive(Debug, Clone)]
 pub struct TSPSymInstance<DistanceContainer> {
@@ -25,6 +31,18 @@ pub struct TSPSymInstance<DistanceContainer> {
     /// Row major order, i.e. distance from node i to node j is at index (i * num_nodes + j).
     /// Node indexing starts at 0.
     distances: DistanceContainer,
+    /// Edges which are required to be part of any tour (e.g. from a FIXED_EDGES_SECTION).
+    fixed_edges: Vec<UnEdge>,
+    /// Pairs (before, after) of nodes, where before has to be visited before after (SOP only).
+    precedences: Vec<(Node, Node)>,
+    /// Demand of each node (CVRP only).
+    demands: Vec<usize>,
+    /// Depot nodes (CVRP only).
+    depots: Vec<Node>,
+    /// Pairs of distinct nodes whose distance has been rounded to 0 (only collected on request).
+    rounded_zero_distances: Vec<(Node, Node)>,
+    /// 2D coordinates of each node for displaying the instance (e.g. from a DISPLAY_DATA_SECTION).
+    display_coordinates: Vec<(f64, f64)>,
 }
 
 impl<DistanceContainer> TSPSymInstance<DistanceContainer> {
@@ -33,41 +51,222 @@ impl<DistanceContainer> TSPSymInstance<DistanceContainer> {
         Self {
             metadata,
             distances: distance_container,
+            fixed_edges: Vec::new(),
+            precedences: Vec::new(),
+            demands: Vec::new(),
+            depots: Vec::new(),
+            rounded_zero_distances: Vec::new(),
+            display_coordinates: Vec::new(),
         }
     }
 
+    /// Sets the edges which are required to be part of any tour.
+    pub fn with_fixed_edges(mut self, fixed_edges: Vec<UnEdge>) -> Self {
+        self.fixed_edges = fixed_edges;
+        self
+    }
+
+    /// Sets the precedence constraints of a sequential ordering problem (SOP), see
+    /// [`precedences`](Self::precedences).
+    pub fn with_precedences(mut self, precedences: Vec<(Node, Node)>) -> Self {
+        self.precedences = precedences;
+        self
+    }
+
+    /// Sets the demands and depots of a capacitated vehicle routing problem (CVRP), see
+    /// [`demands`](Self::demands) and [`depots`](Self::depots).
+    pub fn with_demands_and_depots(mut self, demands: Vec<usize>, depots: Vec<Node>) -> Self {
+        self.demands = demands;
+        self.depots = depots;
+        self
+    }
+
+    /// Sets the pairs of distinct nodes whose distance has been rounded to 0, see
+    /// [`rounded_zero_distances`](Self::rounded_zero_distances).
+    pub fn with_rounded_zero_distances(
+        mut self,
+        rounded_zero_distances: Vec<(Node, Node)>,
+    ) -> Self {
+        self.rounded_zero_distances = rounded_zero_distances;
+        self
+    }
+
+    /// Sets the coordinates of the nodes for displaying the instance, see
+    /// [`display_coordinates`](Self::display_coordinates).
+    pub fn with_display_coordinates(mut self, display_coordinates: Vec<(f64, f64)>) -> Self {
+        self.display_coordinates = display_coordinates;
+        self
+    }
+
     pub fn metadata(&self) -> &InstanceMetadata {
         &self.metadata
     }
+
+    /// Returns the container holding the distances of the instance.
+    ///
+    /// To access single distances independently of the container, see
+    /// [`distance`](Self::distance). A [`MatrixSym`] can be converted to a full [`Matrix`] with
+    /// [`MatrixSym::to_non_symmetric`].
+    pub fn distances(&self) -> &DistanceContainer {
+        &self.distances
+    }
+
+    /// Returns the edges which are required to be part of any tour.
+    ///
+    /// Empty, if the instance does not specify any fixed edges.
+    pub fn fixed_edges(&self) -> &[UnEdge] {
+        &self.fixed_edges
+    }
+
+    /// Returns the fixed edges as a [`FixedEdgeSet`] for fast membership tests, e.g. to keep them
+    /// during local search.
+    pub fn fixed_edge_set(&self) -> FixedEdgeSet {
+        FixedEdgeSet::from_edges(&self.fixed_edges)
+    }
+
+    /// Returns the precedence constraints of a sequential ordering problem (SOP) as pairs
+    /// `(before, after)`, meaning that `before` has to be visited before `after`.
+    ///
+    /// Empty for all other problem types.
+    pub fn precedences(&self) -> &[(Node, Node)] {
+        &self.precedences
+    }
+
+    /// Returns the demand of each node of a capacitated vehicle routing problem (CVRP). The
+    /// capacity of the vehicles is given by [`InstanceMetadata::capacity`].
+    ///
+    /// Empty for all other problem types.
+    pub fn demands(&self) -> &[usize] {
+        &self.demands
+    }
+
+    /// Returns the depot nodes of a capacitated vehicle routing problem (CVRP).
+    ///
+    /// Empty for all other problem types.
+    pub fn depots(&self) -> &[Node] {
+        &self.depots
+    }
+
+    /// Returns the pairs `(i, j)` with `i < j` of nodes with distinct coordinates whose distance
+    /// has been rounded to 0, e.g. by the nearest integer rounding of EUC_2D distances.
+    ///
+    /// Only collected when parsing in strict mode, empty otherwise.
+    pub fn rounded_zero_distances(&self) -> &[(Node, Node)] {
+        &self.rounded_zero_distances
+    }
+
+    /// Returns the 2D coordinates `(x, y)` of each node for displaying the instance, e.g. to plot
+    /// tours of instances with explicit distances.
+    ///
+    /// Taken from a DISPLAY_DATA_SECTION, or from a NODE_COORD_SECTION following the
+    /// EDGE_WEIGHT_SECTION of an instance with explicit distances. Empty if the instance has
+    /// neither, in particular if the distances are computed from the node coordinates.
+    pub fn display_coordinates(&self) -> &[(f64, f64)] {
+        &self.display_coordinates
+    }
+}
+
+impl<DistanceContainer: DistanceSource> TSPSymInstance<DistanceContainer> {
+    /// Returns the number of nodes of the instance.
+    pub fn dimension(&self) -> usize {
+        self.distances.dimension()
+    }
+
+    /// Returns the distance between the given nodes, regardless of how the distances are stored.
+    pub fn distance(&self, from: Node, to: Node) -> Distance {
+        self.distances.distance(from, to)
+    }
+
+    /// Returns a hash of the dimension and the distances of the instance, e.g. as the key of a
+    /// cache of solutions.
+    ///
+    /// Only the distances are hashed, not the name, comment or any other metadata. Thus, renamed
+    /// but otherwise identical instances intentionally have the same hash, and so do the same
+    /// distances stored in a [`Matrix`] and a [`MatrixSym`]. The hash is computed with 64-bit
+    /// FNV-1a over all `n^2` distances, so unlike the randomized `DefaultHasher` of `std`, it
+    /// is stable across runs and platforms.
+    pub fn content_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+        let hash_bytes = |hash: u64, bytes: &[u8]| {
+            bytes.iter().fold(hash, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+        };
+
+        let dimension = self.dimension();
+        let mut hash = hash_bytes(FNV_OFFSET_BASIS, &(dimension as u64).to_le_bytes());
+        for from in 0..dimension {
+            for to in 0..dimension {
+                let distance = self.distance(Node(from), Node(to));
+                hash = hash_bytes(hash, &distance.0.to_le_bytes());
+            }
+        }
+        hash
+    }
 }
 
 impl TSPSymInstance<MatrixSym<Distance>> {
+    /// Returns the stored lower triangle of the distance matrix in row-major order.
     pub fn raw_distances(&self) -> &[Distance] {
         self.distances.data()
     }
 
+    /// Returns the distance matrix, the same as [`distances`](Self::distances).
     pub fn distance_matrix(&self) -> &MatrixSym<Distance> {
         &self.distances
     }
 }
 
 impl TSPSymInstance<Matrix<Distance>> {
+    /// Returns the full distance matrix in row-major order.
     pub fn raw_distances(&self) -> &[Distance] {
         self.distances.data()
     }
 
+    /// Returns the distance matrix, the same as [`distances`](Self::distances).
     pub fn distance_matrix(&self) -> &Matrix<Distance> {
         &self.distances
     }
 }
 
-impl<DistanceContainer: Display> Display for TSPSymInstance<DistanceContainer> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "TSP Instance: {}", self.metadata.name)?;
+/// Instances up to this dimension are displayed including their distance matrix.
+const DISPLAY_MAX_MATRIX_DIMENSION: usize = 20;
+
+/// Displays a one-line summary of the instance followed by its metadata, e.g.
+/// `berlin52: 52 nodes, 2704 stored distances, EUC_2D, symmetric`.
+///
+/// The distance matrix is only included for non-empty instances with a dimension of at most 20.
+impl<DistanceContainer: Display + DistanceSource> Display for TSPSymInstance<DistanceContainer> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "{}: {} nodes, {} stored distances, {:?}, {}",
+            self.metadata.name,
+            self.distances.dimension(),
+            self.distances.stored_distances(),
+            self.metadata.edge_weight_type,
+            if self.distances.is_symmetric() {
+                "symmetric"
+            } else {
+                "asymmetric"
+            }
+        )?;
         writeln!(f, "Type: {:?}", self.metadata.problem_type)?;
+        if let Some(comment) = &self.metadata.comment {
+            writeln!(f, "Comment: {}", comment)?;
+        }
         writeln!(f, "Dimension: {}", self.metadata.dimension)?;
         writeln!(f, "Edge Weight Type: {:?}", self.metadata.edge_weight_type)?;
-        writeln!(f, "Distance Matrix:\n{}", self.distances)?;
+        if let Some(edge_weight_format) = &self.metadata.edge_weight_format {
+            writeln!(f, "Edge Weight Format: {:?}", edge_weight_format)?;
+        }
+        if !self.fixed_edges.is_empty() {
+            writeln!(f, "Fixed Edges: {}", self.fixed_edges.len())?;
+        }
+        if (1..=DISPLAY_MAX_MATRIX_DIMENSION).contains(&self.distances.dimension()) {
+            writeln!(f, "Distance Matrix:\n{}", self.distances)?;
+        }
         Ok(())
     }
 }
@@ -87,7 +286,29 @@ pub struct InstanceMetadata {
     pub display_data_type: Option<DisplayDataType>,
 }
 
+impl InstanceMetadata {
+    /// Creates the metadata of a symmetric TSP instance with only the required fields set.
+    ///
+    /// Useful when constructing instances programmatically instead of parsing them. For more
+    /// control, use [`InstanceMetadataBuilder`](metadata_builder::InstanceMetadataBuilder).
+    pub fn minimal(name: String, dimension: usize, edge_weight_type: EdgeWeightType) -> Self {
+        Self {
+            name,
+            problem_type: ProblemType::TSP,
+            comment: None,
+            dimension,
+            capacity: None,
+            edge_weight_type,
+            edge_weight_format: None,
+            edge_data_format: None,
+            node_coord_type: NodeCoordType::NO_COORDS,
+            display_data_type: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnTour {
     pub edges: Vec<UnEdge>,
     pub cost: Distance,
@@ -95,17 +316,426 @@ pub struct UnTour {
 
 impl PartialEq for UnTour {
     fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost && self.edges.len() == other.edges.len() && {
-            let mut res = true;
-            for edge in &self.edges {
-                if !other.edges.contains(edge) {
-                    res = false;
-                    break;
-                }
+        self.cost == other.cost && self.same_cycle_as(other)
+    }
+}
+
+impl Eq for UnTour {}
+
+impl UnTour {
+    /// Returns the number of edges of the tour.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns whether the tour has no edges, e.g. the tour of an instance with at most one node.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the number of nodes of the instance the tour belongs to, computed as the largest
+    /// node index plus one.
+    ///
+    /// For a valid tour, this is the same as [`len`](Self::len). Returns 0 for the empty tour.
+    pub fn dimension(&self) -> usize {
+        self.edges
+            .iter()
+            .map(|edge| edge.from.0.max(edge.to.0) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns whether both tours consist of the same edges, regardless of their order and
+    /// orientation. In contrast to `==`, the stored [`cost`](Self::cost) is ignored.
+    ///
+    /// The edges are compared as multisets, so an edge used twice only matches an edge that is
+    /// used twice as well. Takes `O(n log n)` time and `O(n)` additional memory.
+    pub fn same_cycle_as(&self, other: &UnTour) -> bool {
+        self.edges.len() == other.edges.len() && {
+            // The order of undirected edges ignores their orientation
+            let mut edges = self.edges.clone();
+            let mut other_edges = other.edges.clone();
+            edges.sort_unstable();
+            other_edges.sort_unstable();
+            edges == other_edges
+        }
+    }
+
+    /// Returns whether the tour contains the given edge in either direction.
+    ///
+    /// Takes `O(n)`, see [`edge_set`](Self::edge_set) for repeated queries.
+    pub fn contains_edge(&self, edge: &UnEdge) -> bool {
+        self.edges.contains(edge)
+    }
+
+    /// Returns the set of edges of the tour, which answers membership queries in `O(1)` regardless
+    /// of the orientation of the edge.
+    ///
+    /// The set is a bitset over the adjacency matrix and thus takes `O(n^2)` bits, so it only pays
+    /// off for many queries against the same tour.
+    pub fn edge_set(&self) -> FixedEdgeSet {
+        FixedEdgeSet::from_edges(&self.edges)
+    }
+
+    /// Computes the cost of the tour's edges with respect to the given distances, ignoring the
+    /// stored [`cost`](Self::cost).
+    ///
+    /// The cost is accumulated in `i64` and saturates at `i32::MAX`, see
+    /// [`Distance::saturating_sum`], so the cost of a tour on a large instance cannot overflow.
+    pub fn compute_cost(&self, distances: &impl DistanceSource) -> Distance {
+        Distance::saturating_sum(
+            self.edges
+                .iter()
+                .map(|edge| distances.distance(edge.from, edge.to)),
+        )
+    }
+
+    /// Returns the number of edges of the tour incident to the given node.
+    ///
+    /// Each node of a valid tour has degree 2.
+    pub fn degree(&self, node: Node) -> usize {
+        self.edges
+            .iter()
+            .map(|edge| usize::from(edge.from == node) + usize::from(edge.to == node))
+            .sum()
+    }
+
+    /// Returns the relative gap of the tour cost to the given optimal cost as a fraction, that
+    /// is, `(cost - optimum) / optimum`.
+    ///
+    /// For example, a tour that is 5% longer than the optimum has a gap of `0.05`. If the optimum
+    /// is 0, the gap is 0 for a tour of cost 0 and infinite otherwise.
+    pub fn gap_to(&self, optimum: Distance) -> f64 {
+        let difference = f64::from(self.cost.0) - f64::from(optimum.0);
+        if optimum.0 == 0 {
+            if difference == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY
             }
-            res
+        } else {
+            difference / f64::from(optimum.0)
         }
     }
+
+    /// Returns a human-readable line comparing the tour cost to the given optimal cost, e.g.
+    /// `cost 10653, optimum 10628, gap 0.24%`.
+    pub fn report(&self, optimum: Distance) -> String {
+        format!(
+            "cost {}, optimum {}, gap {:.2}%",
+            self.cost.0,
+            optimum.0,
+            self.gap_to(optimum) * 100.0
+        )
+    }
+
+    /// Compares the tours by their stored [`cost`](Self::cost) only, ignoring their edges.
+    ///
+    /// Two different tours of equal cost compare as [`Ordering::Equal`], so unlike `==`, this is
+    /// not a comparison of the tours themselves. See [`CostOrdered`] for a wrapper which orders
+    /// tours this way, e.g. in a [`BinaryHeap`](alloc::collections::BinaryHeap).
+    pub fn cmp_by_cost(&self, other: &UnTour) -> Ordering {
+        self.cost.cmp(&other.cost)
+    }
 }
 
-impl Eq for UnTour {}
+/// A tour which is ordered by its cost, see [`UnTour::cmp_by_cost`].
+///
+/// Useful to keep candidate tours in sorted collections, e.g. in a
+/// [`BinaryHeap`](alloc::collections::BinaryHeap) for beam search or to track the k best tours.
+/// Wrap it in [`Reverse`](core::cmp::Reverse) to pop the cheapest tour first.
+///
+/// The ordering and equality ignore the structure of the tours: two different tours of equal cost
+/// are equal as `CostOrdered`, although they are not equal as [`UnTour`].
+#[derive(Debug, Clone)]
+pub struct CostOrdered(pub UnTour);
+
+impl PartialEq for CostOrdered {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.cost == other.0.cost
+    }
+}
+
+impl Eq for CostOrdered {}
+
+impl PartialOrd for CostOrdered {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CostOrdered {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_by_cost(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BinaryHeap, string::ToString, vec};
+    use core::cmp::Reverse;
+
+    use super::*;
+
+    #[test]
+    fn test_compute_cost_saturates() {
+        let tour = UnTour {
+            edges: vec![
+                UnEdge::new(Node(0), Node(1)),
+                UnEdge::new(Node(1), Node(2)),
+                UnEdge::new(Node(2), Node(0)),
+            ],
+            cost: Distance(0),
+        };
+        let distances = Matrix::new_from_distance_function(3, |_, _| Distance(10));
+        assert_eq!(tour.compute_cost(&distances), Distance(30));
+
+        // The plain sum of these distances would wrap around to a negative value
+        let distances = Matrix::new_from_distance_function(3, |_, _| Distance(i32::MAX / 2));
+        assert_eq!(tour.compute_cost(&distances), Distance(i32::MAX));
+
+        assert_eq!(Distance(i32::MAX).checked_add(Distance(1)), None);
+        assert_eq!(Distance(2).checked_add(Distance(3)), Some(Distance(5)));
+        assert_eq!(
+            Distance(i32::MAX).saturating_add(Distance(1)),
+            Distance(i32::MAX)
+        );
+        assert_eq!(
+            Distance::saturating_sum([Distance(i32::MIN), Distance(-1), Distance(-5)]),
+            Distance(i32::MIN)
+        );
+        // Only the final sum saturates, intermediate sums may exceed i32
+        assert_eq!(
+            Distance::saturating_sum([Distance(i32::MAX), Distance(1), Distance(-10)]),
+            Distance(i32::MAX - 9)
+        );
+    }
+
+    #[test]
+    fn test_content_hash_ignores_metadata() {
+        let distance_function =
+            |from: Node, to: Node| Distance((from.0 as i32 - to.0 as i32).abs());
+        let instance = |name: &str| {
+            TSPSymInstance::new(
+                Matrix::new_from_distance_function(4, distance_function),
+                InstanceMetadata::minimal(name.to_string(), 4, EdgeWeightType::EXPLICIT),
+            )
+        };
+        let symmetric = TSPSymInstance::new(
+            MatrixSym::new_from_distance_function(4, distance_function),
+            InstanceMetadata::minimal("symmetric".to_string(), 4, EdgeWeightType::EXPLICIT),
+        );
+        let hash = instance("a").content_hash();
+        assert_eq!(hash, instance("b").content_hash());
+        assert_eq!(hash, symmetric.content_hash());
+        // Guards against accidental changes of the hash, which would invalidate existing caches
+        assert_eq!(hash, 13622627877826431265);
+
+        let changed = TSPSymInstance::new(
+            Matrix::new_from_distance_function(4, |from, to| {
+                distance_function(from, to) + Distance(i32::from(from != to))
+            }),
+            InstanceMetadata::minimal("a".to_string(), 4, EdgeWeightType::EXPLICIT),
+        );
+        assert_ne!(hash, changed.content_hash());
+        let empty = TSPSymInstance::new(
+            Matrix::new_from_distance_function(0, distance_function),
+            InstanceMetadata::minimal("a".to_string(), 0, EdgeWeightType::EXPLICIT),
+        );
+        assert_ne!(hash, empty.content_hash());
+    }
+
+    #[test]
+    fn test_un_tour_helpers() {
+        let tour = UnTour {
+            edges: vec![
+                UnEdge::new(Node(0), Node(2)),
+                UnEdge::new(Node(2), Node(1)),
+                UnEdge::new(Node(1), Node(3)),
+                UnEdge::new(Node(3), Node(0)),
+            ],
+            cost: Distance(10),
+        };
+        assert_eq!(tour.len(), 4);
+        assert!(!tour.is_empty());
+        assert_eq!(tour.dimension(), 4);
+        assert!(tour.contains_edge(&UnEdge::new(Node(1), Node(2))));
+        assert!(!tour.contains_edge(&UnEdge::new(Node(0), Node(1))));
+        let edge_set = tour.edge_set();
+        assert_eq!(edge_set.len(), 4);
+        for from in 0..4 {
+            for to in 0..4 {
+                let edge = UnEdge::new(Node(from), Node(to));
+                assert_eq!(edge_set.contains(edge), tour.contains_edge(&edge));
+            }
+        }
+        assert!((0..4).all(|node| tour.degree(Node(node)) == 2));
+        assert_eq!(tour.degree(Node(4)), 0);
+    }
+
+    #[test]
+    fn test_un_tour_gap_to() {
+        let tour = UnTour {
+            edges: vec![UnEdge::new(Node(0), Node(1)), UnEdge::new(Node(1), Node(0))],
+            cost: Distance(105),
+        };
+        assert!((tour.gap_to(Distance(100)) - 0.05).abs() < 1e-12);
+        assert_eq!(tour.gap_to(Distance(105)), 0.0);
+        assert_eq!(tour.gap_to(Distance(0)), f64::INFINITY);
+        assert_eq!(
+            tour.report(Distance(100)),
+            "cost 105, optimum 100, gap 5.00%"
+        );
+    }
+
+    #[test]
+    fn test_un_tour_helpers_small_tours() {
+        let empty_tour = UnTour {
+            edges: Vec::new(),
+            cost: Distance(0),
+        };
+        assert!(empty_tour.is_empty());
+        assert_eq!(empty_tour.dimension(), 0);
+
+        // Tours on two nodes use the same edge twice
+        let edge = UnEdge::new(Node(0), Node(1));
+        let two_node_tour = UnTour {
+            edges: vec![edge, UnEdge::new(Node(1), Node(0))],
+            cost: Distance(2),
+        };
+        assert_eq!(two_node_tour.len(), 2);
+        assert_eq!(two_node_tour.dimension(), 2);
+        assert!(two_node_tour.contains_edge(&edge));
+        assert_eq!(two_node_tour.edge_set().len(), 1);
+        assert_eq!(two_node_tour.degree(Node(0)), 2);
+    }
+
+    #[test]
+    fn test_un_tour_eq_large_node_indices() {
+        // Comparing tours must not allocate proportionally to the square of the node indices
+        let tour = |nodes: [usize; 3]| UnTour {
+            edges: vec![
+                UnEdge::new(Node(nodes[0]), Node(nodes[1])),
+                UnEdge::new(Node(nodes[1]), Node(nodes[2])),
+                UnEdge::new(Node(nodes[2]), Node(nodes[0])),
+            ],
+            cost: Distance(3),
+        };
+        let large = usize::MAX / 2;
+        assert_eq!(tour([0, large, large + 1]), tour([large + 1, large, 0]));
+        assert_ne!(tour([0, large, large + 1]), tour([1, large, large + 1]));
+    }
+
+    #[test]
+    fn test_un_tour_same_cycle_as_duplicate_edges() {
+        let a = UnEdge::new(Node(0), Node(1));
+        let b = UnEdge::new(Node(1), Node(2));
+        let duplicated = UnTour {
+            edges: vec![a, a, b],
+            cost: Distance(0),
+        };
+        let other = UnTour {
+            edges: vec![a, b, b],
+            cost: Distance(0),
+        };
+        assert!(!duplicated.same_cycle_as(&other));
+        assert!(!other.same_cycle_as(&duplicated));
+        assert!(duplicated.same_cycle_as(&UnTour {
+            edges: vec![b, UnEdge::new(Node(1), Node(0)), a],
+            cost: Distance(0),
+        }));
+    }
+
+    #[test]
+    fn test_cost_ordered_heap() {
+        let triangle = |nodes: [usize; 3], cost: i32| UnTour {
+            edges: vec![
+                UnEdge::new(Node(nodes[0]), Node(nodes[1])),
+                UnEdge::new(Node(nodes[1]), Node(nodes[2])),
+                UnEdge::new(Node(nodes[2]), Node(nodes[0])),
+            ],
+            cost: Distance(cost),
+        };
+        let a = triangle([0, 1, 2], 30);
+        let b = triangle([3, 4, 5], 30);
+        let c = triangle([0, 1, 3], 10);
+
+        // Different tours of equal cost are equal by cost, but not as tours
+        assert_eq!(a.cmp_by_cost(&b), Ordering::Equal);
+        assert_ne!(a, b);
+        assert_eq!(CostOrdered(a.clone()), CostOrdered(b.clone()));
+        assert_eq!(c.cmp_by_cost(&a), Ordering::Less);
+
+        let mut heap: BinaryHeap<_> = [a, c, b]
+            .into_iter()
+            .map(|tour| Reverse(CostOrdered(tour)))
+            .collect();
+        let costs: Vec<_> = core::iter::from_fn(|| heap.pop())
+            .map(|Reverse(CostOrdered(tour))| tour.cost)
+            .collect();
+        assert_eq!(costs, vec![Distance(10), Distance(30), Distance(30)]);
+    }
+
+    #[test]
+    fn test_display_summary() {
+        let metadata = InstanceMetadata::minimal("small".to_string(), 3, EdgeWeightType::EUC_2D);
+        let instance = TSPSymInstance::new(
+            MatrixSym::new(
+                vec![
+                    Distance(0),
+                    Distance(1),
+                    Distance(0),
+                    Distance(2),
+                    Distance(3),
+                    Distance(0),
+                ],
+                3,
+            ),
+            metadata,
+        );
+        let display = instance.to_string();
+        assert_eq!(
+            display.lines().next(),
+            Some("small: 3 nodes, 6 stored distances, EUC_2D, symmetric")
+        );
+        assert!(display.contains("Distance Matrix:"));
+    }
+
+    #[test]
+    fn test_display_omits_large_matrix() {
+        let dimension = DISPLAY_MAX_MATRIX_DIMENSION + 1;
+        let metadata =
+            InstanceMetadata::minimal("large".to_string(), dimension, EdgeWeightType::EXPLICIT);
+        let mut matrix = Matrix::new_from_dimension_with_value(dimension, Distance(1));
+        matrix.set_data(Node(0), Node(1), Distance(2));
+        let instance = TSPSymInstance::new(matrix, metadata);
+        let display = instance.to_string();
+        assert_eq!(
+            display.lines().next(),
+            Some("large: 21 nodes, 441 stored distances, EXPLICIT, asymmetric")
+        );
+        assert!(!display.contains("Distance Matrix:"));
+    }
+
+    #[test]
+    fn test_distance_accessors_match_containers() {
+        let distance_function = |from: Node, to: Node| Distance((from.0 * to.0) as i32);
+        let full = TSPSymInstance::new(
+            Matrix::new_from_distance_function(5, distance_function),
+            InstanceMetadata::minimal("full".to_string(), 5, EdgeWeightType::EXPLICIT),
+        );
+        let symmetric = TSPSymInstance::new(
+            MatrixSym::new_from_distance_function(5, distance_function),
+            InstanceMetadata::minimal("symmetric".to_string(), 5, EdgeWeightType::EXPLICIT),
+        );
+
+        assert_eq!(full.dimension(), 5);
+        assert_eq!(symmetric.dimension(), 5);
+        for from in (0..5).map(Node) {
+            for to in (0..5).map(Node) {
+                assert_eq!(full.distance(from, to), distance_function(from, to));
+                assert_eq!(symmetric.distance(from, to), distance_function(from, to));
+            }
+        }
+    }
+}
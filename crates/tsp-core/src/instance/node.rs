@@ -1,8 +1,27 @@
-use std::ops::{Add, Sub};
+use core::ops::{Add, Sub};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node(pub usize);
 
+impl Node {
+    /// Returns the node with the given 1-based id, as used for nodes in TSPLIB files, e.g. in the
+    /// node coordinate section or in tour files.
+    ///
+    /// Panics in debug builds if the id is 0, which is not a valid TSPLIB id.
+    #[inline(always)]
+    pub fn from_tsplib(id: usize) -> Node {
+        debug_assert!(id > 0, "TSPLIB node ids start at 1");
+        Node(id - 1)
+    }
+
+    /// Returns the 1-based id of the node as used in TSPLIB files, see [`Node::from_tsplib`].
+    #[inline(always)]
+    pub fn to_tsplib(self) -> usize {
+        self.0 + 1
+    }
+}
+
 impl Add<usize> for Node {
     type Output = Node;
 
@@ -30,3 +49,17 @@ impl From<Node> for usize {
         node.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tsplib_ids_round_trip() {
+        assert_eq!(Node::from_tsplib(1), Node(0));
+        assert_eq!(Node(0).to_tsplib(), 1);
+        for id in 1..10 {
+            assert_eq!(Node::from_tsplib(id).to_tsplib(), id);
+        }
+    }
+}
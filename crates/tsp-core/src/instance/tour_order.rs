@@ -0,0 +1,572 @@
+/*!
+Representation of tours as the order in which the nodes are visited, see [`TourOrder`].
+*/
+
+use alloc::{vec, vec::Vec};
+
+use crate::instance::{
+    UnTour,
+    distance::{Distance, DistanceSource},
+    edge::UnEdge,
+    node::Node,
+};
+
+/// A tour represented by the order in which the nodes are visited.
+///
+/// In contrast to [`UnTour`], which stores the edges of a tour, this representation is well
+/// suited for local search moves, which rearrange (segments of) the tour. The tour implicitly
+/// returns from the last node to the first one.
+///
+/// The nodes are guaranteed to be a permutation of `0..dimension`, i.e. each node is visited
+/// exactly once.
+///
+/// Optionally, the tour tracks its cost (see [`TourOrder::with_cost`]), which is then updated
+/// incrementally by the mutation methods like [`TourOrder::reverse_segment`]. Two tours are equal
+/// if they visit the nodes in the same order, regardless of whether their cost is tracked.
+#[derive(Debug, Clone)]
+pub struct TourOrder {
+    nodes: Vec<Node>,
+    /// The cost of the tour, if it is tracked.
+    cost: Option<Distance>,
+}
+
+impl PartialEq for TourOrder {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+    }
+}
+
+impl Eq for TourOrder {}
+
+impl TourOrder {
+    /// Create a new tour from the order in which the nodes are visited.
+    ///
+    /// Panics if the nodes are not a permutation of `0..nodes.len()`.
+    pub fn new(nodes: Vec<Node>) -> Self {
+        assert!(
+            is_permutation(&nodes),
+            "Nodes of a tour should be a permutation of 0..{}",
+            nodes.len()
+        );
+        Self { nodes, cost: None }
+    }
+
+    /// Create the tour visiting the nodes in the order `0, 1, ..., dimension - 1`.
+    pub fn identity(dimension: usize) -> Self {
+        Self {
+            nodes: (0..dimension).map(Node).collect(),
+            cost: None,
+        }
+    }
+
+    /// Starts tracking the cost of the tour with respect to the given distances.
+    ///
+    /// The cost is computed once here and then updated incrementally by the mutation methods, see
+    /// [`TourOrder::cached_cost`]. The same distances have to be passed to the mutation methods
+    /// afterwards.
+    pub fn with_cost(mut self, distances: &impl DistanceSource) -> Self {
+        self.cost = Some(self.cost(distances));
+        self
+    }
+
+    /// Returns the tracked cost of the tour, or `None` if the cost is not tracked.
+    pub fn cached_cost(&self) -> Option<Distance> {
+        self.cost
+    }
+
+    /// Recomputes the cost of the tour from scratch and returns whether it matches the tracked
+    /// cost. Tours which do not track their cost are always valid.
+    ///
+    /// This is meant to catch drift of the tracked cost, e.g. due to passing different distances
+    /// to the mutation methods.
+    pub fn verify(&self, distances: &impl DistanceSource) -> bool {
+        self.cost.is_none_or(|cost| cost == self.cost(distances))
+    }
+
+    /// Returns the nodes in the order in which they are visited.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the number of nodes in the tour.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns whether the tour contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Iterate over the edges of the tour, including the edge from the last node back to the
+    /// first one.
+    ///
+    /// Tours with less than two nodes have no edges.
+    pub fn edges(&self) -> impl Iterator<Item = UnEdge> + '_ {
+        let number_of_edges = if self.nodes.len() < 2 {
+            0
+        } else {
+            self.nodes.len()
+        };
+        (0..number_of_edges).map(|index| {
+            UnEdge::new(
+                self.nodes[index],
+                self.nodes[(index + 1) % self.nodes.len()],
+            )
+        })
+    }
+
+    /// Computes the cost of the tour with respect to the given distances.
+    ///
+    /// Like [`UnTour::compute_cost`], the cost saturates at `i32::MAX` instead of overflowing.
+    pub fn cost(&self, distances: &impl DistanceSource) -> Distance {
+        Distance::saturating_sum(
+            self.edges()
+                .map(|edge| distances.distance(edge.from, edge.to)),
+        )
+    }
+
+    /// Computes the cost of the path visiting the nodes in order, i.e. the cost of the tour
+    /// without the edge from the last node back to the first one.
+    ///
+    /// This is the cost of open tours, e.g. the ones constructed by
+    /// `construction::nearest_neighbor_with_endpoints` of the solvers. Like [`TourOrder::cost`],
+    /// the cost saturates instead of overflowing.
+    pub fn path_cost(&self, distances: &impl DistanceSource) -> Distance {
+        Distance::saturating_sum(
+            self.nodes
+                .windows(2)
+                .map(|pair| distances.distance(pair[0], pair[1])),
+        )
+    }
+
+    /// Reverses the segment of the tour between the positions `start` and `end` (both inclusive)
+    /// and returns the change in cost, which is negative if the tour got shorter.
+    ///
+    /// This is the 2-opt move replacing the edges entering and leaving the segment. The change
+    /// is computed in constant time, assuming symmetric distances. If the cost is tracked, it is
+    /// updated accordingly.
+    ///
+    /// Panics if `start > end` or `end` is not a position of the tour.
+    pub fn reverse_segment(
+        &mut self,
+        start: usize,
+        end: usize,
+        distances: &impl DistanceSource,
+    ) -> Distance {
+        assert!(
+            start <= end && end < self.nodes.len(),
+            "Invalid segment {start}..={end} for a tour with {} nodes",
+            self.nodes.len()
+        );
+        let length = self.nodes.len();
+
+        // Reversing the whole tour does not change its edges
+        let delta = if end - start + 1 == length {
+            Distance(0)
+        } else {
+            let before = self.nodes[(start + length - 1) % length];
+            let after = self.nodes[(end + 1) % length];
+            let (first, last) = (self.nodes[start], self.nodes[end]);
+            distances.distance(before, last) + distances.distance(first, after)
+                - distances.distance(before, first)
+                - distances.distance(last, after)
+        };
+
+        self.nodes[start..=end].reverse();
+        self.apply_delta(delta)
+    }
+
+    /// Moves the node at position `from` to position `to`, shifting the nodes in between, and
+    /// returns the change in cost, which is negative if the tour got shorter.
+    ///
+    /// That is, the node is removed from the tour and then inserted such that it is at position
+    /// `to` afterwards. The change is computed in constant time, but shifting the nodes takes
+    /// linear time in the distance between the positions. If the cost is tracked, it is updated
+    /// accordingly.
+    ///
+    /// Panics if `from` or `to` is not a position of the tour.
+    pub fn move_node(
+        &mut self,
+        from: usize,
+        to: usize,
+        distances: &impl DistanceSource,
+    ) -> Distance {
+        let length = self.nodes.len();
+        assert!(
+            from < length && to < length,
+            "Invalid positions {from} and {to} for a tour with {length} nodes"
+        );
+
+        // Tours with less than three nodes always have the same edges
+        if length < 3 || from == to {
+            let node = self.nodes.remove(from);
+            self.nodes.insert(to, node);
+            return self.apply_delta(Distance(0));
+        }
+
+        let node = self.nodes[from];
+        let removed_before = self.nodes[(from + length - 1) % length];
+        let removed_after = self.nodes[(from + 1) % length];
+        let mut delta = distances.distance(removed_before, removed_after)
+            - distances.distance(removed_before, node)
+            - distances.distance(node, removed_after);
+
+        self.nodes.remove(from);
+        // Insert between the nodes at positions to - 1 and to of the remaining nodes
+        let inserted_before = self.nodes[(to + length - 2) % (length - 1)];
+        let inserted_after = self.nodes[to % (length - 1)];
+        delta = delta
+            + distances.distance(inserted_before, node)
+            + distances.distance(node, inserted_after)
+            - distances.distance(inserted_before, inserted_after);
+        self.nodes.insert(to, node);
+
+        self.apply_delta(delta)
+    }
+
+    /// Swaps the consecutive segments `first..second` and `second..third` of the tour and returns
+    /// the change in cost, which is negative if the tour got shorter.
+    ///
+    /// That is, the tour `A B C D` with `B = first..second` and `C = second..third` becomes
+    /// `A C B D`, which is the double-bridge move. The change is computed in constant time,
+    /// assuming symmetric distances. If the cost is tracked, it is updated accordingly.
+    ///
+    /// Panics unless `0 < first < second < third < self.len()`, i.e. all four segments are
+    /// non-empty.
+    pub fn swap_segments(
+        &mut self,
+        first: usize,
+        second: usize,
+        third: usize,
+        distances: &impl DistanceSource,
+    ) -> Distance {
+        assert!(
+            0 < first && first < second && second < third && third < self.nodes.len(),
+            "Invalid segments {first}..{second} and {second}..{third} for a tour with {} nodes",
+            self.nodes.len()
+        );
+
+        let before = self.nodes[first - 1];
+        let (first_start, first_end) = (self.nodes[first], self.nodes[second - 1]);
+        let (second_start, second_end) = (self.nodes[second], self.nodes[third - 1]);
+        let after = self.nodes[third];
+        let delta = distances.distance(before, second_start)
+            + distances.distance(second_end, first_start)
+            + distances.distance(first_end, after)
+            - distances.distance(before, first_start)
+            - distances.distance(first_end, second_start)
+            - distances.distance(second_end, after);
+
+        self.nodes[first..third].rotate_left(second - first);
+        self.apply_delta(delta)
+    }
+
+    /// Replaces the order in which the nodes are visited by the given one with the given cost,
+    /// e.g. the result of a local search on a copy of [`TourOrder::nodes`].
+    ///
+    /// If the cost is tracked, the tracked cost is replaced by the given cost, such that the tour
+    /// keeps tracking its cost. Otherwise, the cost is ignored. The number of nodes may change,
+    /// e.g. if a node has been removed from the instance.
+    ///
+    /// Panics if the nodes are not a permutation of `0..nodes.len()`.
+    pub fn set_nodes(&mut self, nodes: Vec<Node>, cost: Distance) {
+        assert!(
+            is_permutation(&nodes),
+            "Nodes of a tour should be a permutation of 0..{}",
+            nodes.len()
+        );
+        self.nodes = nodes;
+        if let Some(tracked_cost) = &mut self.cost {
+            *tracked_cost = cost;
+        }
+    }
+
+    /// Updates the tracked cost (if any) by the given change and returns the change.
+    fn apply_delta(&mut self, delta: Distance) -> Distance {
+        if let Some(cost) = &mut self.cost {
+            *cost += delta;
+        }
+        delta
+    }
+
+    /// Converts the tour into an [`UnTour`] with its cost with respect to the given distances.
+    pub fn to_un_tour(&self, distances: &impl DistanceSource) -> UnTour {
+        UnTour {
+            edges: self.edges().collect(),
+            cost: self.cost(distances),
+        }
+    }
+}
+
+/// Returns whether the nodes are a permutation of `0..nodes.len()`.
+fn is_permutation(nodes: &[Node]) -> bool {
+    let mut seen = vec![false; nodes.len()];
+    nodes
+        .iter()
+        .all(|node| node.0 < nodes.len() && !core::mem::replace(&mut seen[node.0], true))
+}
+
+/// Error returned by [`UnTour::to_order`] if the edges do not form a single tour through all
+/// nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedTourError(&'static str);
+
+impl core::fmt::Display for MalformedTourError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "MalformedTourError: {}", self.0)
+    }
+}
+
+impl core::error::Error for MalformedTourError {}
+
+impl UnTour {
+    /// Converts the tour to the order in which the nodes are visited, starting at node 0 and
+    /// following the first edge of node 0.
+    ///
+    /// Returns an error if the edges do not form a single tour through the nodes
+    /// `0..dimension`, e.g. if a node does not have degree 2 or the edges form several subtours.
+    /// Like [`TourOrder::edges`], tours with less than two nodes are expected to have no edges.
+    pub fn to_order(&self, dimension: usize) -> Result<TourOrder, MalformedTourError> {
+        let expected_edges = if dimension < 2 { 0 } else { dimension };
+        if self.edges.len() != expected_edges {
+            return Err(MalformedTourError(
+                "the number of edges does not match the dimension",
+            ));
+        }
+        if dimension < 2 {
+            return Ok(TourOrder::identity(dimension));
+        }
+
+        let mut neighbors = vec![Vec::with_capacity(2); dimension];
+        for edge in &self.edges {
+            if edge.from.0 >= dimension || edge.to.0 >= dimension {
+                return Err(MalformedTourError("an edge has a node out of bounds"));
+            }
+            if edge.from == edge.to {
+                return Err(MalformedTourError("an edge is a self loop"));
+            }
+            neighbors[edge.from.0].push(edge.to);
+            neighbors[edge.to.0].push(edge.from);
+        }
+        if neighbors.iter().any(|neighbors| neighbors.len() != 2) {
+            return Err(MalformedTourError("a node does not have degree 2"));
+        }
+
+        let mut nodes = Vec::with_capacity(dimension);
+        let mut visited = vec![false; dimension];
+        let (mut previous, mut current) = (None, Node(0));
+        for _ in 0..dimension {
+            if core::mem::replace(&mut visited[current.0], true) {
+                return Err(MalformedTourError("the edges form several subtours"));
+            }
+            nodes.push(current);
+            let next = match neighbors[current.0][..] {
+                [first, second] if Some(first) == previous => second,
+                [first, _] => first,
+                _ => unreachable!("All nodes have degree 2"),
+            };
+            (previous, current) = (Some(current), next);
+        }
+
+        Ok(TourOrder::new(nodes))
+    }
+
+    /// Converts the tour to the order in which the nodes are visited like [`UnTour::to_order`],
+    /// but continues from node 0 with its smaller neighbor. Thus, tours are [the same
+    /// cycle](UnTour::same_cycle_as) if and only if their canonical orders are equal.
+    pub fn to_canonical_order(&self, dimension: usize) -> Result<TourOrder, MalformedTourError> {
+        let mut nodes = self.to_order(dimension)?.nodes;
+        if nodes.len() > 2 && nodes[1] > nodes[nodes.len() - 1] {
+            nodes[1..].reverse();
+        }
+        Ok(TourOrder::new(nodes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instance::matrix::MatrixSym, test_support::line_distances};
+
+    #[test]
+    fn test_reverse_segment_delta() {
+        let distances = line_distances(&[0, 5, 1, 4, 2, 3]);
+        let mut tour = TourOrder::identity(6).with_cost(&distances);
+        assert_eq!(tour.cached_cost(), Some(Distance(18)));
+
+        // 0 5 1 4 2 3 -> 0 1 5 4 2 3
+        assert_eq!(tour.reverse_segment(1, 2, &distances), Distance(-6));
+        assert_eq!(tour.cached_cost(), Some(Distance(12)));
+        assert!(tour.verify(&distances));
+
+        // Reversing the whole tour does not change the cost
+        assert_eq!(tour.reverse_segment(0, 5, &distances), Distance(0));
+        assert!(tour.verify(&distances));
+    }
+
+    #[test]
+    fn test_mutations_keep_cost_in_sync() {
+        let positions = [7, 3, 9, 0, 4, 8, 1, 6, 2, 5];
+        let distances = line_distances(&positions);
+        let length = positions.len();
+        let mut tour = TourOrder::identity(length).with_cost(&distances);
+
+        // Deterministic pseudo-random sequence of moves
+        let mut state = 17usize;
+        let mut next = || {
+            state = (state * 31 + 7) % 1009;
+            state
+        };
+        for _ in 0..200 {
+            let (first, second) = (next() % length, next() % length);
+            let cost_before = tour.cached_cost().unwrap();
+            let delta = match next() % 3 {
+                0 => tour.reverse_segment(first.min(second), first.max(second), &distances),
+                1 => tour.move_node(first, second, &distances),
+                _ => {
+                    let first = 1 + next() % (length - 3);
+                    let second = first + 1 + next() % (length - first - 2);
+                    let third = second + 1 + next() % (length - second - 1);
+                    tour.swap_segments(first, second, third, &distances)
+                }
+            };
+            assert_eq!(tour.cached_cost(), Some(cost_before + delta));
+            assert!(is_permutation(tour.nodes()));
+            assert!(tour.verify(&distances));
+        }
+    }
+
+    #[test]
+    fn test_mutations_on_symmetric_matrix() {
+        let positions = [7, 3, 9, 0, 4, 8];
+        let full = line_distances(&positions);
+        let symmetric = MatrixSym::new_from_distance_function(positions.len(), |from, to| {
+            full.get_data(from, to)
+        });
+        let mut tour = TourOrder::identity(positions.len()).with_cost(&symmetric);
+        let mut expected = TourOrder::identity(positions.len()).with_cost(&full);
+        assert_eq!(tour.cached_cost(), expected.cached_cost());
+
+        assert_eq!(
+            tour.reverse_segment(1, 3, &symmetric),
+            expected.reverse_segment(1, 3, &full)
+        );
+        assert_eq!(
+            tour.move_node(0, 4, &symmetric),
+            expected.move_node(0, 4, &full)
+        );
+        assert_eq!(
+            tour.swap_segments(1, 3, 4, &symmetric),
+            expected.swap_segments(1, 3, 4, &full)
+        );
+        assert_eq!(tour, expected);
+        assert!(tour.verify(&symmetric));
+        assert_eq!(tour.to_un_tour(&symmetric), expected.to_un_tour(&full));
+    }
+
+    #[test]
+    fn test_set_nodes_keeps_tracking_cost() {
+        let distances = line_distances(&[0, 5, 1, 4]);
+        let mut tour = TourOrder::identity(4).with_cost(&distances);
+        tour.set_nodes(vec![Node(0), Node(2), Node(3), Node(1)], Distance(10));
+        assert_eq!(tour.cached_cost(), Some(Distance(10)));
+        assert!(tour.verify(&distances));
+
+        let mut untracked = TourOrder::identity(4);
+        untracked.set_nodes(vec![Node(3), Node(2), Node(1), Node(0)], Distance(16));
+        assert_eq!(untracked.cached_cost(), None);
+    }
+
+    #[test]
+    fn test_verify_detects_drift() {
+        let distances = line_distances(&[0, 1, 2, 3]);
+        let other_distances = line_distances(&[0, 2, 1, 3]);
+        let mut tour = TourOrder::identity(4).with_cost(&distances);
+        tour.move_node(1, 2, &other_distances);
+        assert!(!tour.verify(&distances));
+
+        // Untracked tours are always valid
+        assert!(TourOrder::identity(4).verify(&distances));
+    }
+
+    #[test]
+    fn test_path_cost() {
+        let distances = line_distances(&[0, 5, 1, 4]);
+        let tour = TourOrder::new(vec![Node(0), Node(2), Node(1), Node(3)]);
+        assert_eq!(tour.path_cost(&distances), Distance(6));
+        assert_eq!(tour.cost(&distances), Distance(10));
+        assert_eq!(TourOrder::identity(1).path_cost(&distances), Distance(0));
+    }
+
+    #[test]
+    fn test_equality_ignores_cost() {
+        let distances = line_distances(&[0, 1, 2]);
+        assert_eq!(
+            TourOrder::identity(3).with_cost(&distances),
+            TourOrder::identity(3)
+        );
+    }
+
+    #[test]
+    fn test_to_order_round_trip() {
+        let order = TourOrder::new([3, 0, 4, 1, 2].map(Node).to_vec());
+        let tour = UnTour {
+            edges: order.edges().collect(),
+            cost: Distance(0),
+        };
+        let converted = tour.to_order(5).unwrap();
+        // The conversion starts at node 0, possibly in the other direction
+        let expected = [0, 4, 1, 2, 3].map(Node);
+        let mut reversed = expected;
+        reversed[1..].reverse();
+        assert!(converted.nodes() == expected || converted.nodes() == reversed);
+
+        for dimension in 0..3 {
+            let tour = UnTour {
+                edges: TourOrder::identity(dimension).edges().collect(),
+                cost: Distance(0),
+            };
+            assert_eq!(tour.to_order(dimension), Ok(TourOrder::identity(dimension)));
+        }
+    }
+
+    #[test]
+    fn test_to_canonical_order() {
+        let tour = |nodes: [usize; 5]| UnTour {
+            edges: TourOrder::new(nodes.map(Node).to_vec()).edges().collect(),
+            cost: Distance(0),
+        };
+        // The same cycle in both directions and starting at different nodes
+        let tour_a = tour([3, 0, 4, 1, 2]);
+        let tour_b = tour([1, 4, 0, 3, 2]);
+        assert!(tour_a.same_cycle_as(&tour_b));
+        let expected = [0, 3, 2, 1, 4].map(Node);
+        assert_eq!(tour_a.to_canonical_order(5).unwrap().nodes(), expected);
+        assert_eq!(tour_b.to_canonical_order(5).unwrap().nodes(), expected);
+
+        let other = tour([0, 1, 2, 3, 4]);
+        assert!(!tour_a.same_cycle_as(&other));
+        assert_ne!(other.to_canonical_order(5).unwrap().nodes(), expected);
+    }
+
+    #[test]
+    fn test_to_order_malformed() {
+        let tour = |edges: &[(usize, usize)]| UnTour {
+            edges: edges
+                .iter()
+                .map(|&(from, to)| UnEdge::new(Node(from), Node(to)))
+                .collect(),
+            cost: Distance(0),
+        };
+        // Two subtours
+        let subtours = tour(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+        assert!(subtours.to_order(6).is_err());
+        // Node 0 has degree 3 and node 3 has degree 1
+        let degrees = tour(&[(0, 1), (1, 2), (2, 0), (0, 3)]);
+        assert!(degrees.to_order(4).is_err());
+        // Wrong number of edges and nodes out of bounds
+        assert!(tour(&[(0, 1), (1, 2), (2, 0)]).to_order(4).is_err());
+        assert!(tour(&[(0, 1), (1, 5), (5, 0)]).to_order(3).is_err());
+    }
+}
@@ -1,6 +1,12 @@
 #![warn(missing_debug_implementations, missing_docs)]
+#![no_std]
+
+extern crate alloc;
 
 pub mod instance;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod triangular;
 pub mod tsp_lib_spec;
 
 #[cfg(test)]
@@ -0,0 +1,18 @@
+/*!
+Fixtures for testing code on small hand-made instances, shared by the tests of this crate and of
+the crates building on it. Outside of the tests of this crate, requires the `test-support`
+feature.
+*/
+
+use crate::instance::{distance::Distance, matrix::Matrix};
+
+/// Distances of nodes placed on a line at the given positions, that is, node `i` is at
+/// `positions[i]`.
+///
+/// Any tour visiting the nodes in the order of their positions and returning to the first one is
+/// optimal and costs twice the distance between the outermost positions.
+pub fn line_distances(positions: &[i32]) -> Matrix<Distance> {
+    Matrix::new_from_distance_function(positions.len(), |from, to| {
+        Distance((positions[from.0] - positions[to.0]).abs())
+    })
+}
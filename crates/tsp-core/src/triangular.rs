@@ -0,0 +1,114 @@
+//! Index math for vec-flattened lower-(left-)triangular matrices.
+//!
+//! The entries are stored in row-major order including the diagonal, that is, row `i` consists of
+//! the entries `(i, 0), ..., (i, i)` and starts at index `i * (i + 1) / 2`. Since the stored
+//! matrices are symmetric, the entry (row, column) with row < column is the same as the entry
+//! (column, row).
+
+#[inline(always)]
+/// Computes the index of entry (row, column) in a vec-flattened lower-(left-)triangular matrix.
+pub fn get_lower_triangle_matrix_entry(row: usize, column: usize) -> usize {
+    if row > column {
+        get_lower_triangle_matrix_entry_row_bigger(row, column)
+    } else {
+        get_lower_triangle_matrix_entry_column_bigger(row, column)
+    }
+}
+
+#[inline(always)]
+/// Computes the index of entry (row, column) in a vec-flattened lower-(left-)triangular matrix,
+/// assuming row >= column.
+pub fn get_lower_triangle_matrix_entry_row_bigger(row: usize, column: usize) -> usize {
+    debug_assert!(
+        row >= column,
+        "get_lower_triangle_matrix_entry_row_bigger called with row < column: row {}, column {}",
+        row,
+        column
+    );
+    (row * (row + 1)) / 2 + column
+}
+
+#[inline(always)]
+/// Computes the index of entry (row, column) in a vec-flattened lower-(left-)triangular matrix,
+/// assuming column >= row.
+pub fn get_lower_triangle_matrix_entry_column_bigger(row: usize, column: usize) -> usize {
+    debug_assert!(
+        column >= row,
+        "get_lower_triangle_matrix_entry_column_bigger called with column < row: row {}, column {}",
+        row,
+        column
+    );
+    (column * (column + 1)) / 2 + row
+}
+
+/// Computes the (row, column) of the entry at the given index in a vec-flattened
+/// lower-(left-)triangular matrix. Inverse of [`get_lower_triangle_matrix_entry_row_bigger`].
+pub fn find_row_column_from_lower_triangle_index(index: usize) -> (usize, usize) {
+    let row = ((8 * index + 1).isqrt() - 1) / 2;
+    let column = index - (row * (row + 1)) / 2;
+    (row, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower_triangle_indices() {
+        // Row-major lower triangle of a 4x4 matrix:
+        // 0
+        // 1 2
+        // 3 4 5
+        // 6 7 8 9
+        for (row, column, index) in [
+            (0, 0, 0),
+            (1, 0, 1),
+            (1, 1, 2),
+            (2, 0, 3),
+            (2, 2, 5),
+            (3, 1, 7),
+            (3, 3, 9),
+            (100, 7, 5057),
+        ] {
+            assert_eq!(
+                get_lower_triangle_matrix_entry_row_bigger(row, column),
+                index
+            );
+            assert_eq!(
+                get_lower_triangle_matrix_entry_column_bigger(column, row),
+                index
+            );
+            assert_eq!(get_lower_triangle_matrix_entry(row, column), index);
+            assert_eq!(get_lower_triangle_matrix_entry(column, row), index);
+        }
+    }
+
+    #[test]
+    fn test_lower_triangle_indices_are_consecutive() {
+        let mut expected = 0;
+        for row in 0..20 {
+            for column in 0..=row {
+                assert_eq!(get_lower_triangle_matrix_entry(row, column), expected);
+                assert_eq!(
+                    find_row_column_from_lower_triangle_index(expected),
+                    (row, column)
+                );
+                expected += 1;
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_row_bigger_rejects_column_bigger() {
+        get_lower_triangle_matrix_entry_row_bigger(1, 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_column_bigger_rejects_row_bigger() {
+        get_lower_triangle_matrix_entry_column_bigger(2, 1);
+    }
+}
@@ -1,5 +1,7 @@
 #![allow(non_camel_case_types)]
 
+use alloc::string::String;
+
 /// Enumeration of all possible data section keywords in a .tsp file.
 ///
 /// The Keywords are according to the TSPLIB 95 specification.
@@ -61,6 +63,36 @@ pub enum EdgeWeightType {
     SPECIAL,
 }
 
+impl EdgeWeightType {
+    /// Returns whether the distances are given explicitly in an EDGE_WEIGHT_SECTION.
+    pub fn is_explicit(&self) -> bool {
+        matches!(self, EdgeWeightType::EXPLICIT)
+    }
+
+    /// Returns the number of coordinates per node the distances are computed from, that is, 2 for
+    /// the 2D types including GEO and ATT and 3 for the 3D types including the crystallography
+    /// types XRAY1 and XRAY2.
+    ///
+    /// Returns `None` for explicit distances and for SPECIAL, whose distance function is not
+    /// defined by the TSPLIB.
+    pub fn coordinate_dimensions(&self) -> Option<u8> {
+        match self {
+            EdgeWeightType::EUC_2D
+            | EdgeWeightType::MAX_2D
+            | EdgeWeightType::MAN_2D
+            | EdgeWeightType::CEIL_2D
+            | EdgeWeightType::GEO
+            | EdgeWeightType::ATT => Some(2),
+            EdgeWeightType::EUC_3D
+            | EdgeWeightType::MAX_3D
+            | EdgeWeightType::MAN_3D
+            | EdgeWeightType::XRAY1
+            | EdgeWeightType::XRAY2 => Some(3),
+            EdgeWeightType::EXPLICIT | EdgeWeightType::SPECIAL => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EdgeWeightFormat {
     FUNCTION,
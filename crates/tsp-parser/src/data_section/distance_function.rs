@@ -1,9 +1,45 @@
 use std::f64::consts::PI;
 
-use tsp_core::instance::distance::Distance;
+use tsp_core::{instance::distance::Distance, tsp_lib_spec::EdgeWeightType};
 
 use crate::data_section::{GeoPoint, Point2D, Point3D};
 
+/// Returns the distance function on 2D coordinates for the given edge weight type, or `None` if
+/// the edge weight type is not defined via 2D coordinates.
+///
+/// The functions are the same ones used when parsing instances, so distances computed with them
+/// are consistent with parsed distance matrices. For [`EdgeWeightType::GEO`], the coordinates are
+/// expected in the raw TSPLIB format, see [`geographical_distance_2d`].
+///
+/// When computing many distances, prefer calling the distance function directly, as calls via
+/// function pointers are not inlined.
+pub fn distance_function_for(kind: EdgeWeightType) -> Option<fn(&Point2D, &Point2D) -> Distance> {
+    match kind {
+        EdgeWeightType::EUC_2D => Some(euclidean_distance_2d),
+        EdgeWeightType::MAX_2D => Some(max_distance_2d),
+        EdgeWeightType::MAN_2D => Some(manhattan_distance_2d),
+        EdgeWeightType::CEIL_2D => Some(ceil_distance_2d),
+        EdgeWeightType::ATT => Some(att_distance_2d),
+        EdgeWeightType::GEO => Some(geographical_distance_2d),
+        _ => None,
+    }
+}
+
+/// Returns the distance function on 3D coordinates for the given edge weight type, or `None` if
+/// the edge weight type is not defined via 3D coordinates.
+///
+/// See [`distance_function_for`] for details.
+pub fn distance_function_3d_for(
+    kind: EdgeWeightType,
+) -> Option<fn(&Point3D, &Point3D) -> Distance> {
+    match kind {
+        EdgeWeightType::EUC_3D => Some(euclidean_distance_3d),
+        EdgeWeightType::MAX_3D => Some(max_distance_3d),
+        EdgeWeightType::MAN_3D => Some(manhattan_distance_3d),
+        _ => None,
+    }
+}
+
 /// Computes the 2D Euclidean distance between two points as defined in TSPLIB95.
 #[inline(always)]
 pub fn euclidean_distance_2d(point_a: &Point2D, point_b: &Point2D) -> Distance {
@@ -72,13 +108,26 @@ pub fn ceil_distance_2d(point_a: &Point2D, point_b: &Point2D) -> Distance {
     )
 }
 
+/// Radius of the earth in kilometers as used by the TSPLIB95 GEO distance.
+pub const EARTH_RADIUS: f64 = 6378.388;
+
 /// Computes the geographical distance between two points as defined in TSPLIB95.
 #[inline(always)]
 pub fn geographical_distance(point_a: &GeoPoint, point_b: &GeoPoint) -> Distance {
+    geographical_distance_with_radius(point_a, point_b, EARTH_RADIUS)
+}
+
+/// Computes the geographical distance between two points as defined in TSPLIB95 on a sphere with
+/// the given radius instead of the earth's, e.g. to scale the distances.
+#[inline(always)]
+pub fn geographical_distance_with_radius(
+    point_a: &GeoPoint,
+    point_b: &GeoPoint,
+    rrr: f64,
+) -> Distance {
     if point_a == point_b {
         return Distance(0);
     }
-    let rrr = 6378.388;
     let q1 = (point_a.longitude - point_b.longitude).cos();
     let q2 = (point_a.latitude - point_b.latitude).cos();
     let q3 = (point_a.latitude + point_b.latitude).cos();
@@ -86,6 +135,18 @@ pub fn geographical_distance(point_a: &GeoPoint, point_b: &GeoPoint) -> Distance
     Distance(((rrr * (0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)).acos()) + 1.0) as i32)
 }
 
+/// Computes the geographical distance between two points given in the raw TSPLIB95 format, that
+/// is, as `DDD.MM` degrees and minutes of latitude and longitude.
+///
+/// This converts both points with [`convert_to_geo_coordinates`] first. When computing many
+/// distances, convert the points once and use [`geographical_distance`] instead.
+pub fn geographical_distance_2d(point_a: &Point2D, point_b: &Point2D) -> Distance {
+    geographical_distance(
+        &convert_to_geo_coordinates(point_a),
+        &convert_to_geo_coordinates(point_b),
+    )
+}
+
 /// Converts a 2D point representing geographical coordinates as defined in TSPLIB95.
 pub fn convert_to_geo_coordinates(point: &Point2D) -> GeoPoint {
     let deg_lat = nint(point.x) as f64;
@@ -102,6 +163,25 @@ pub fn convert_to_geo_coordinates(point: &Point2D) -> GeoPoint {
     }
 }
 
+/// Returns whether the degrees of the 2D point are within the range of geographical coordinates,
+/// that is, the latitude `x` has at most 90 and the longitude `y` at most 180 degrees (in absolute
+/// value).
+pub fn geo_degrees_in_range(point: &Point2D) -> bool {
+    point.x.trunc().abs() <= 90.0 && point.y.trunc().abs() <= 180.0
+}
+
+/// Returns whether both coordinates of the 2D point have less than 60 minutes in the TSPLIB
+/// format `DDD.MM`.
+///
+/// Coordinates given in decimal degrees instead usually have 60 or more minutes for some of the
+/// points, in which case [`convert_to_geo_coordinates`] silently computes wrong positions.
+/// However, a few points of the TSPLIB instance ali535 have 60 or more minutes as well.
+pub fn geo_minutes_valid(point: &Point2D) -> bool {
+    // The tolerance accounts for rounding errors, e.g. 12.6 - 12.0 is slightly less than 0.6
+    let minutes = |value: f64| (value - value.trunc()).abs() * 100.0 + 1e-9;
+    minutes(point.x) < 60.0 && minutes(point.y) < 60.0
+}
+
 /// Computes the ATT distance between two points as defined in TSPLIB95.
 #[inline(always)]
 pub fn att_distance_2d(point_a: &Point2D, point_b: &Point2D) -> Distance {
@@ -0,0 +1,346 @@
+//! Parsing of EDGE_WEIGHT_SECTIONs, i.e. explicitly given distance matrices.
+//!
+//! The entries of a section are whitespace separated and may be split across lines arbitrarily.
+//! Their order is given by the EDGE_WEIGHT_FORMAT of the instance. As instances of this crate are
+//! symmetric, the entries are stored as the lower triangle (including the diagonal) in row-major
+//! order, which is the order of the LOWER_DIAG_ROW format. Of a FULL_MATRIX, only the lower
+//! triangle is kept.
+
+use std::str::SplitAsciiWhitespace;
+
+use memchr::memchr;
+use tsp_core::{
+    instance::{InstanceMetadata, distance::Distance, node::Node},
+    triangular::get_lower_triangle_matrix_entry,
+    tsp_lib_spec::{EdgeWeightFormat, ProblemType},
+};
+
+use crate::{ParserError, check_utf8, data_section::DataSectionParseError};
+
+/// Parses an EDGE_WEIGHT_SECTION into the lower triangle (including the diagonal) of the distance
+/// matrix in row-major order.
+///
+/// Entries missing in the format (i.e. the diagonal for formats without it) are set to 0. Entries
+/// above the diagonal of a FULL_MATRIX are ignored, unless `strict` is set, in which case they
+/// have to match their counterparts below the diagonal.
+pub(crate) fn parse_edge_weight_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+    strict: bool,
+) -> Result<Vec<Distance>, ParserError> {
+    let dimension = metadata.dimension;
+    let format = metadata
+        .edge_weight_format
+        .as_ref()
+        .ok_or(DataSectionParseError::MissingEdgeWeightFormat)?;
+    let (mut entries, expected) = entries_in_order(format, dimension)?;
+
+    let mut data = vec![Distance(0); dimension * (dimension + 1) / 2];
+    // Entries above the diagonal, which only occur in the FULL_MATRIX format
+    let mut upper_entries = Vec::new();
+    parse_edge_weights(file_content, index_in_map, expected, |distance| {
+        let (row, column) = entries
+            .next()
+            .expect("The format yields as many entries as expected");
+        if matches!(format, EdgeWeightFormat::FULL_MATRIX) && column > row {
+            if strict {
+                upper_entries.push((row, column, distance));
+            }
+        } else {
+            data[get_lower_triangle_matrix_entry(row, column)] = distance;
+        }
+    })?;
+
+    for (row, column, distance) in upper_entries {
+        let lower_distance = data[get_lower_triangle_matrix_entry(row, column)];
+        if distance != lower_distance {
+            return Err(DataSectionParseError::AsymmetricEdgeWeights {
+                from: Node(row).to_tsplib(),
+                to: Node(column).to_tsplib(),
+                distance: distance.0,
+                reverse_distance: lower_distance.0,
+            }
+            .into());
+        }
+    }
+
+    Ok(data)
+}
+
+/// The full distance matrix and the precedence constraints of a sequential ordering problem.
+type SopEdgeWeights = (Vec<Distance>, Vec<(Node, Node)>);
+
+/// Parses the EDGE_WEIGHT_SECTION of a sequential ordering problem (SOP) into a full distance
+/// matrix in row-major order and the precedence constraints.
+///
+/// In SOP instances, the section starts with the dimension, followed by the full matrix. An entry
+/// of -1 at (row, column) means that the node of the column has to be visited before the node of
+/// the row. These entries are set to 0 in the distance matrix and returned as pairs
+/// `(before, after)` instead.
+pub(crate) fn parse_sop_edge_weight_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+) -> Result<SopEdgeWeights, ParserError> {
+    let dimension = metadata.dimension;
+    match metadata.edge_weight_format {
+        Some(EdgeWeightFormat::FULL_MATRIX) => {}
+        Some(_) => {
+            return Err(ParserError::Unsupported {
+                feature: "EDGE_WEIGHT_FORMAT other than FULL_MATRIX for SOP",
+            });
+        }
+        None => return Err(DataSectionParseError::MissingEdgeWeightFormat.into()),
+    }
+
+    let mut data = vec![Distance(0); dimension * dimension];
+    let mut precedences = Vec::new();
+    // The leading dimension is parsed as the first entry, so the matrix starts at position 1
+    let mut position = 0;
+    let mut leading_dimension = None;
+    parse_edge_weights(
+        file_content,
+        index_in_map,
+        dimension * dimension + 1,
+        |distance| {
+            if position == 0 {
+                leading_dimension = Some(distance.0);
+            } else {
+                let index = position - 1;
+                if distance == Distance(-1) {
+                    precedences.push((Node(index % dimension), Node(index / dimension)));
+                } else {
+                    data[index] = distance;
+                }
+            }
+            position += 1;
+        },
+    )?;
+
+    if leading_dimension != Some(dimension as i32) {
+        return Err(DataSectionParseError::SopDimensionMismatch {
+            expected: dimension,
+            found: leading_dimension.unwrap_or_default(),
+        }
+        .into());
+    }
+
+    Ok((data, precedences))
+}
+
+/// Streams the entries of an EDGE_WEIGHT_SECTION as `(row, column, distance)` while parsing them,
+/// e.g. to build a sparse structure of an instance too large for a distance matrix.
+///
+/// The bytes have to start right after the EDGE_WEIGHT_SECTION keyword, e.g. at the index left by
+/// [`parse_metadata`](crate::metadata::parse_metadata). Unlike [`parse_data_sections`], nothing is
+/// allocated for the distances. The entries are yielded in the order of the EDGE_WEIGHT_FORMAT,
+/// such that both `(i, j)` and `(j, i)` are yielded for a FULL_MATRIX, and the diagonal only for
+/// formats including it.
+///
+/// The stream ends after the expected number of entries. A malformed entry or a missing entry
+/// (the section ending early at `EOF` or another data keyword) is yielded as an error, after which
+/// the stream ends as well. Returns an error right away if the format is missing or not supported,
+/// including the EDGE_WEIGHT_SECTION of sequential ordering problems (SOP), or if the bytes are not
+/// valid UTF-8.
+///
+/// [`parse_data_sections`]: crate::data_section::parse_data_sections
+pub fn stream_edge_weights<'a>(
+    bytes: &'a [u8],
+    metadata: &InstanceMetadata,
+) -> Result<
+    impl Iterator<Item = Result<(Node, Node, Distance), DataSectionParseError>> + use<'a>,
+    ParserError,
+> {
+    if matches!(metadata.problem_type, ProblemType::SOP) {
+        return Err(ParserError::Unsupported {
+            feature: "Streaming the edge weights of SOP instances",
+        });
+    }
+    let format = metadata
+        .edge_weight_format
+        .as_ref()
+        .ok_or(DataSectionParseError::MissingEdgeWeightFormat)?;
+    let (mut entries, expected) = entries_in_order(format, metadata.dimension)?;
+    check_utf8(bytes)?;
+
+    Ok(EdgeWeightStream::new(bytes, expected).map(move |distance| {
+        distance.map(|distance| {
+            let (row, column) = entries
+                .next()
+                .expect("The format yields as many entries as expected");
+            (Node(row), Node(column), distance)
+        })
+    }))
+}
+
+/// Iterator over the expected number of whitespace separated edge weights at the start of the
+/// bytes. The entries may be split across lines arbitrarily.
+///
+/// Ends after the expected number of entries. A malformed entry, an entry too many on the line of
+/// the last one or a missing entry (the section ending early at `EOF` or another data keyword) is
+/// yielded as an error, after which the iterator ends as well.
+struct EdgeWeightStream<'a> {
+    /// The bytes after the line of the current tokens.
+    rest: &'a [u8],
+    /// The remaining tokens of the current line.
+    tokens: SplitAsciiWhitespace<'a>,
+    expected: usize,
+    found: usize,
+    finished: bool,
+}
+
+impl<'a> EdgeWeightStream<'a> {
+    fn new(bytes: &'a [u8], expected: usize) -> Self {
+        Self {
+            rest: bytes,
+            tokens: "".split_ascii_whitespace(),
+            expected,
+            found: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the error for a section with the given number of entries.
+    fn count_error(&self, found: usize) -> DataSectionParseError {
+        DataSectionParseError::EdgeWeightCount {
+            expected: self.expected,
+            found,
+        }
+    }
+}
+
+impl Iterator for EdgeWeightStream<'_> {
+    type Item = Result<Distance, DataSectionParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            if self.found == self.expected {
+                self.finished = true;
+                // Further entries on the line of the last one belong to the section as well
+                return self
+                    .tokens
+                    .next()
+                    .map(|_| Err(self.count_error(self.expected + 1)));
+            }
+
+            if let Some(token) = self.tokens.next() {
+                self.found += 1;
+                return Some(match token.parse::<i32>() {
+                    Ok(distance) => Ok(Distance(distance)),
+                    Err(_) => {
+                        self.finished = true;
+                        Err(DataSectionParseError::InvalidEdgeWeight(token.to_string()))
+                    }
+                });
+            }
+
+            let (line, line_length) = match memchr(b'\n', self.rest) {
+                Some(index_newline) => (&self.rest[..index_newline], index_newline + 1),
+                None => (self.rest, self.rest.len()),
+            };
+            // SAFETY: The bytes of stream_edge_weights are checked to be valid UTF-8, and the TSP
+            // instance file is expected to be valid UTF-8
+            let line_str = unsafe { std::str::from_utf8_unchecked(line) }.trim();
+            if self.rest.is_empty()
+                || line_str == "EOF"
+                || line_str.starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                self.finished = true;
+                return Some(Err(self.count_error(self.found)));
+            }
+            self.rest = &self.rest[line_length..];
+            self.tokens = line_str.split_ascii_whitespace();
+        }
+        None
+    }
+}
+
+/// Parses the expected number of whitespace separated edge weights, calling `insert` for each of
+/// them in order.
+///
+/// Moves the index to the line after the one containing the last entry. The entries may be split
+/// across lines arbitrarily, see [`EdgeWeightStream`].
+fn parse_edge_weights(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    expected: usize,
+    mut insert: impl FnMut(Distance),
+) -> Result<(), DataSectionParseError> {
+    let bytes = &file_content[*index_in_map..];
+    let mut stream = EdgeWeightStream::new(bytes, expected);
+    for distance in stream.by_ref() {
+        insert(distance?);
+    }
+    *index_in_map += bytes.len() - stream.rest.len();
+    Ok(())
+}
+
+/// The (row, column) pairs of the entries of an EDGE_WEIGHT_SECTION and the number of entries.
+type EntryOrder = (Box<dyn Iterator<Item = (usize, usize)>>, usize);
+
+/// Returns the (row, column) pairs of the entries of an EDGE_WEIGHT_SECTION in the order given by
+/// the format, together with the number of entries.
+///
+/// Returns [`ParserError::Unsupported`] for formats which are not implemented yet.
+fn entries_in_order(
+    format: &EdgeWeightFormat,
+    dimension: usize,
+) -> Result<EntryOrder, ParserError> {
+    let with_diagonal = dimension * (dimension + 1) / 2;
+    let without_diagonal = dimension * dimension.saturating_sub(1) / 2;
+    let entries: EntryOrder = match format {
+        EdgeWeightFormat::LOWER_DIAG_ROW => (
+            Box::new((0..dimension).flat_map(|row| (0..=row).map(move |column| (row, column)))),
+            with_diagonal,
+        ),
+        EdgeWeightFormat::LOWER_ROW => (
+            Box::new((0..dimension).flat_map(|row| (0..row).map(move |column| (row, column)))),
+            without_diagonal,
+        ),
+        EdgeWeightFormat::UPPER_DIAG_ROW => (
+            Box::new(
+                (0..dimension)
+                    .flat_map(move |row| (row..dimension).map(move |column| (row, column))),
+            ),
+            with_diagonal,
+        ),
+        EdgeWeightFormat::UPPER_ROW => (
+            Box::new(
+                (0..dimension)
+                    .flat_map(move |row| (row + 1..dimension).map(move |column| (row, column))),
+            ),
+            without_diagonal,
+        ),
+        EdgeWeightFormat::FUNCTION => {
+            return Err(ParserError::Unsupported {
+                feature: "EDGE_WEIGHT_FORMAT: FUNCTION",
+            });
+        }
+        EdgeWeightFormat::FULL_MATRIX => (
+            Box::new(
+                (0..dimension).flat_map(move |row| (0..dimension).map(move |column| (row, column))),
+            ),
+            dimension * dimension,
+        ),
+        // Listing a triangle column by column is the same as listing the transposed triangle,
+        // i.e. the opposite one, row by row
+        EdgeWeightFormat::UPPER_COL
+        | EdgeWeightFormat::LOWER_COL
+        | EdgeWeightFormat::UPPER_DIAG_COL
+        | EdgeWeightFormat::LOWER_DIAG_COL => {
+            let transposed_format = match format {
+                EdgeWeightFormat::UPPER_COL => EdgeWeightFormat::LOWER_ROW,
+                EdgeWeightFormat::LOWER_COL => EdgeWeightFormat::UPPER_ROW,
+                EdgeWeightFormat::UPPER_DIAG_COL => EdgeWeightFormat::LOWER_DIAG_ROW,
+                _ => EdgeWeightFormat::UPPER_DIAG_ROW,
+            };
+            let (entries, expected) = entries_in_order(&transposed_format, dimension)?;
+            (
+                Box::new(entries.map(|(row, column)| (column, row))),
+                expected,
+            )
+        }
+    };
+    Ok(entries)
+}
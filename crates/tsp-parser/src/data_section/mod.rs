@@ -5,30 +5,96 @@
 ///
 /// Distance values are required to be non-negative integers. Computations are expected to be
 /// carried out in double precision arithmetic, i.e. `f64` in Rust.
+use std::{sync::OnceLock, time::Instant};
+
+use log::warn;
 use memchr::memchr;
 use memmap2::Mmap;
+use thiserror::Error;
 use tsp_core::{
-    instance::InstanceMetadata,
-    tsp_lib_spec::{EdgeWeightType, TSPDataKeyword},
+    instance::{InstanceMetadata, distance::Distance, edge::UnEdge, node::Node},
+    tsp_lib_spec::{EdgeWeightType, ProblemType, TSPDataKeyword},
 };
 
-pub(crate) mod distance_function;
+pub mod distance_function;
+mod explicit;
+
+pub use explicit::stream_edge_weights;
 
 use crate::{
-    FileContent,
-    data_section::distance_function::{euclidean_distance_2d, geographical_distance},
+    ParseDiagnostics, ParseOptions, ParserError,
+    data_section::{
+        distance_function::{
+            EARTH_RADIUS, euclidean_distance_2d, geographical_distance_with_radius,
+        },
+        explicit::{parse_edge_weight_section, parse_sop_edge_weight_section},
+    },
     distance_container::ParseFromTSPLib,
+    last_line_is_eof,
+    metadata::parse_data_keyword,
 };
 
+#[derive(Error, Debug)]
+pub enum DataSectionParseError {
+    #[error("Invalid node index in this line: {0}")]
+    InvalidNodeIndex(String),
+    #[error("Node index {index} is out of range for dimension {dimension}")]
+    NodeIndexOutOfRange { index: usize, dimension: usize },
+    #[error("Node index {0} appears more than once")]
+    DuplicateNodeIndex(usize),
+    #[error("Node index {0} is missing")]
+    MissingNodeIndex(usize),
+    #[error("Missing or invalid coordinate in this line: {0}")]
+    InvalidCoordinate(String),
+    #[error(
+        "Node {node} has the coordinates ({x}, {y}), whose degrees are out of range for \
+         geographical coordinates"
+    )]
+    InvalidGeoCoordinate { node: usize, x: f64, y: f64 },
+    #[error("Invalid edge in this line: {0}")]
+    InvalidEdge(String),
+    #[error("Expected another data section after {0:?}")]
+    MissingDataSection(TSPDataKeyword),
+    #[error("Expected EDGE_WEIGHT_SECTION for explicit edge weights, found {0:?}")]
+    UnexpectedDataSection(TSPDataKeyword),
+    #[error("Explicit edge weights require an EDGE_WEIGHT_FORMAT")]
+    MissingEdgeWeightFormat,
+    #[error("Invalid edge weight: {0}")]
+    InvalidEdgeWeight(String),
+    #[error("Expected {expected} edge weights, found {found}")]
+    EdgeWeightCount { expected: usize, found: usize },
+    #[error(
+        "Expected the dimension {expected} at the start of the SOP EDGE_WEIGHT_SECTION, found \
+         {found}"
+    )]
+    SopDimensionMismatch { expected: usize, found: i32 },
+    #[error(
+        "The edge weight {distance} from node {from} to node {to} differs from the reverse edge \
+         weight {reverse_distance} in a symmetric instance"
+    )]
+    AsymmetricEdgeWeights {
+        from: usize,
+        to: usize,
+        distance: i32,
+        reverse_distance: i32,
+    },
+    #[error("Invalid demand in this line: {0}")]
+    InvalidDemand(String),
+    #[error("Data section {0:?} is not supported for this problem type")]
+    UnsupportedDataSection(TSPDataKeyword),
+    #[error("The distance {distance} scaled by {scale} does not fit into a distance")]
+    ScaledDistanceOverflow { distance: i32, scale: u32 },
+}
+
 /// A point in 2D space.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Point2D {
     pub x: f64,
     pub y: f64,
 }
 
 /// A point in 3D space.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Point3D {
     pub x: f64,
     pub y: f64,
@@ -43,84 +109,269 @@ pub struct GeoPoint {
 }
 
 pub fn parse_data_sections<DistanceContainer: ParseFromTSPLib>(
-    file_content: &FileContent,
+    file_content: &[u8],
     index_in_map: &mut usize,
     data_keyword: TSPDataKeyword,
     metadata: &InstanceMetadata,
-) -> DistanceContainer {
-    match metadata.edge_weight_type {
+    options: &ParseOptions,
+) -> Result<DistanceContainer, ParserError> {
+    parse_distances(
+        file_content,
+        index_in_map,
+        data_keyword,
+        metadata,
+        options,
+        &mut None,
+    )
+    .map(|(distance_container, _)| distance_container)
+}
+
+/// Parses the data sections determining the distances like [`parse_data_sections`].
+///
+/// Additionally returns the pairs of distinct nodes whose distance has been rounded to 0 if
+/// [`ParseOptions::strict`] is set, see [`rounded_zero_distances`]. If diagnostics are given, the
+/// number of points, the time spent on computing the distances and the number of threads used
+/// are recorded in them.
+pub(crate) fn parse_distances<DistanceContainer: ParseFromTSPLib>(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    data_keyword: TSPDataKeyword,
+    metadata: &InstanceMetadata,
+    options: &ParseOptions,
+    diagnostics: &mut Option<ParseDiagnostics>,
+) -> Result<(DistanceContainer, Vec<(Node, Node)>), ParserError> {
+    let scale = options.scale as f64;
+    let distances = match metadata.edge_weight_type {
         // The distance function is not chosen via a match statement here because the compiler
         // does not seem to inline the distance function properly in that case
         // (leading to a big performance hit)
         EdgeWeightType::EUC_2D => {
             let distance_function = euclidean_distance_2d;
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_2d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_2d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::MAX_2D => {
             let distance_function = distance_function::max_distance_2d;
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_2d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_2d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::MAN_2D => {
             let distance_function = distance_function::manhattan_distance_2d;
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_2d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_2d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::CEIL_2D => {
             let distance_function = distance_function::ceil_distance_2d;
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_2d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_2d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::ATT => {
             let distance_function = distance_function::att_distance_2d;
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_2d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_2d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::GEO => {
-            let distance_function = geographical_distance;
+            // Scaling the coordinates would change the positions on the globe, so the radius is
+            // scaled instead
+            let distance_function = move |point_a: &GeoPoint, point_b: &GeoPoint| {
+                geographical_distance_with_radius(point_a, point_b, scale * EARTH_RADIUS)
+            };
+            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata, 1.0)?;
+            if options.strict {
+                check_geo_coordinates(&node_data)?;
+            }
             // TODO(perf): Possibly parallelize the conversion to geo coordinates
-            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata)
+            let node_data = node_data
                 .into_iter()
                 .map(|point| distance_function::convert_to_geo_coordinates(&point))
                 .collect::<Vec<GeoPoint>>();
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                distance_function::geographical_distance,
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::EUC_3D => {
             let distance_function = distance_function::euclidean_distance_3d;
-            let node_data = parse_3d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_3d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_3d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::MAX_3D => {
             let distance_function = distance_function::max_distance_3d;
-            let node_data = parse_3d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_3d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_3d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::MAN_3D => {
             let distance_function = distance_function::manhattan_distance_3d;
-            let node_data = parse_3d_node_coord_section(file_content, index_in_map, metadata);
-            DistanceContainer::from_node_coord_section(&node_data, metadata, distance_function)
+            let node_data =
+                parse_3d_node_coord_section(file_content, index_in_map, metadata, scale)?;
+            from_scaled_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                unscaled_3d(distance_function, scale),
+                options,
+                diagnostics,
+            )?
         }
         EdgeWeightType::EXPLICIT => {
-            // TODO: Implement explicit distance matrix parsing
-            todo!("Explicit distance matrix parsing is not supported yet");
+            if !matches!(data_keyword, TSPDataKeyword::EDGE_WEIGHT_SECTION) {
+                return Err(DataSectionParseError::UnexpectedDataSection(data_keyword).into());
+            }
+            let mut lower_triangle =
+                parse_edge_weight_section(file_content, index_in_map, metadata, options.strict)?;
+            scale_distances(&mut lower_triangle, options.scale)?;
+            let start = Instant::now();
+            let distance_container =
+                DistanceContainer::from_lower_triangle(lower_triangle, metadata);
+            if let Some(diagnostics) = diagnostics {
+                diagnostics.distance_compute_ns = start.elapsed().as_nanos();
+                diagnostics.threads_used = 1;
+            }
+            // Explicit distances are not rounded
+            (distance_container, Vec::new())
         }
-        _ => unimplemented!(
-            "Node coordinate type {:?} is not yet implemented",
-            metadata.node_coord_type
-        ),
-    }
+        EdgeWeightType::XRAY1 | EdgeWeightType::XRAY2 => {
+            return Err(ParserError::Unsupported {
+                feature: "EDGE_WEIGHT_TYPE: XRAY1/XRAY2",
+            });
+        }
+        EdgeWeightType::SPECIAL => {
+            let special_distance_function = options
+                .special_distance_function
+                .ok_or(ParserError::SpecialFunctionRequired)?;
+            let scale = options.scale;
+            // The distance function cannot fail, so the first overflow is recorded and returned
+            // once all distances are computed
+            let overflow = OnceLock::new();
+            let overflow_ref = &overflow;
+            let distance_function = move |point_a: &Point2D, point_b: &Point2D| {
+                scale_distance(special_distance_function(point_a, point_b), scale).unwrap_or_else(
+                    |error| {
+                        let _ = overflow_ref.set(error);
+                        Distance::MAX
+                    },
+                )
+            };
+            let node_data = parse_2d_node_coord_section(file_content, index_in_map, metadata, 1.0)?;
+            let distances = from_node_coords(
+                &node_data,
+                metadata,
+                distance_function,
+                options.strict,
+                diagnostics,
+            );
+            if let Some(error) = overflow.into_inner() {
+                return Err(error.into());
+            }
+            distances
+        }
+    };
+
+    Ok(distances)
 }
 
-fn parse_2d_node_coord_section(
-    file_content: &FileContent,
+/// Parses a FIXED_EDGES_SECTION preceding the data section of the distances, see
+/// [`parse_fixed_edges`].
+///
+/// Returns the fixed edges (with 0-based node indices) and the keyword of the data section
+/// following the fixed edges.
+pub(crate) fn parse_fixed_edges_section(
+    file_content: &[u8],
     index_in_map: &mut usize,
     metadata: &InstanceMetadata,
-) -> Vec<Point2D> {
-    let mut point_data: Vec<Point2D> = Vec::with_capacity(metadata.dimension);
+) -> Result<(Vec<UnEdge>, TSPDataKeyword), ParserError> {
+    let fixed_edges = parse_fixed_edges(file_content, index_in_map, metadata)?;
+
+    // The fixed edges do not determine the distances, so there has to be another data section
+    let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) else {
+        return Err(
+            DataSectionParseError::MissingDataSection(TSPDataKeyword::FIXED_EDGES_SECTION).into(),
+        );
+    };
+    let line = &file_content[*index_in_map..*index_in_map + index_newline];
+    // SAFETY: The TSP instance file is expected to be valid UTF-8
+    let line_str = unsafe { std::str::from_utf8_unchecked(line) }.trim();
+    *index_in_map += index_newline + 1;
+    if line_str == "EOF" {
+        return Err(
+            DataSectionParseError::MissingDataSection(TSPDataKeyword::FIXED_EDGES_SECTION).into(),
+        );
+    }
+    let data_keyword = parse_data_keyword(line_str)?;
 
-    // Read a line to test if the point data is floating point or integer
-    let is_float_data = is_float_data(file_content, index_in_map);
+    Ok((fixed_edges, data_keyword))
+}
+
+/// Parses a FIXED_EDGES_SECTION, that is, a list of edges (pairs of 1-based node indices) which
+/// are required to be part of the tour, terminated by `-1`.
+///
+/// Returns the fixed edges with 0-based node indices.
+fn parse_fixed_edges(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+) -> Result<Vec<UnEdge>, DataSectionParseError> {
+    let mut fixed_edges = Vec::new();
 
     while let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) {
         let line = &file_content[*index_in_map..*index_in_map + index_newline];
@@ -131,141 +382,641 @@ fn parse_2d_node_coord_section(
         // Move the index to the start of the next line (+1 for the newline character)
         *index_in_map += index_newline + 1;
 
-        // Check if end of file is reached
-        if line_str == "EOF" || line_str.is_empty() {
+        if line_str == "-1" {
             break;
         }
+        if line_str == "EOF" {
+            return Err(DataSectionParseError::MissingDataSection(
+                TSPDataKeyword::FIXED_EDGES_SECTION,
+            ));
+        }
+
+        let mut parts = line_str.split_ascii_whitespace();
+        let (Some(from), Some(to), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(DataSectionParseError::InvalidEdge(line_str.to_string()));
+        };
+        let parse_node = |node_str: &str| match node_str.parse::<usize>() {
+            Ok(index) if (1..=metadata.dimension).contains(&index) => Ok(Node::from_tsplib(index)),
+            Ok(index) => Err(DataSectionParseError::NodeIndexOutOfRange {
+                index,
+                dimension: metadata.dimension,
+            }),
+            Err(_) => Err(DataSectionParseError::InvalidEdge(line_str.to_string())),
+        };
+        fixed_edges.push(UnEdge::new(parse_node(from)?, parse_node(to)?));
+    }
 
-        let point = parse_line_to_2d_point(line_str, is_float_data);
+    Ok(fixed_edges)
+}
 
-        point_data.push(point);
+/// Parses the data section of a sequential ordering problem (SOP), that is, an EDGE_WEIGHT_SECTION
+/// containing the dimension followed by the full distance matrix.
+///
+/// Returns the distances and the precedence constraints as pairs `(before, after)`, which are
+/// given by entries of -1 in the matrix. Those entries are set to 0 in the distances. Like in
+/// [`parse_distances`], the creation of the distance container is recorded in the diagnostics.
+pub(crate) fn parse_sop_data_section<DistanceContainer: ParseFromTSPLib>(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    data_keyword: TSPDataKeyword,
+    metadata: &InstanceMetadata,
+    options: &ParseOptions,
+    diagnostics: &mut Option<ParseDiagnostics>,
+) -> Result<(DistanceContainer, Vec<(Node, Node)>), ParserError> {
+    if !matches!(data_keyword, TSPDataKeyword::EDGE_WEIGHT_SECTION) {
+        return Err(DataSectionParseError::UnexpectedDataSection(data_keyword).into());
     }
+    let (mut full_matrix, precedences) =
+        parse_sop_edge_weight_section(file_content, index_in_map, metadata)?;
+    scale_distances(&mut full_matrix, options.scale)?;
+
+    let start = Instant::now();
+    let distance_container = DistanceContainer::from_full_matrix(full_matrix, metadata);
+    if let Some(diagnostics) = diagnostics {
+        diagnostics.distance_compute_ns = start.elapsed().as_nanos();
+        diagnostics.threads_used = 1;
+    }
+    Ok((distance_container, precedences))
+}
 
-    point_data
+/// The data sections following the data section of the distances.
+#[derive(Debug, Default)]
+pub(crate) struct SubsequentDataSections {
+    /// Edges of FIXED_EDGES_SECTIONs, in addition to those preceding the distances.
+    pub(crate) fixed_edges: Vec<UnEdge>,
+    /// Demand of each node (0 for nodes without a given demand), empty unless the instance is a
+    /// capacitated vehicle routing problem (CVRP).
+    pub(crate) demands: Vec<usize>,
+    /// Depot nodes of a CVRP.
+    pub(crate) depots: Vec<Node>,
+    /// Coordinates of a DISPLAY_DATA_SECTION, or of a NODE_COORD_SECTION of explicit instances.
+    pub(crate) display_coordinates: Vec<Point2D>,
 }
 
-fn parse_3d_node_coord_section(
-    file_content: &FileContent,
+/// Parses the data sections following the data section of the distances until `EOF`, e.g. the
+/// DEMAND_SECTION and DEPOT_SECTION of a capacitated vehicle routing problem (CVRP) or the
+/// DISPLAY_DATA_SECTION of an instance with explicit distances.
+///
+/// As the distances are already known, a NODE_COORD_SECTION is only accepted for explicit
+/// instances, whose node coordinates are used for display only. Parsing stops at the first line
+/// which is not a data keyword, leaving it to the caller to skip to the `EOF` line.
+pub(crate) fn parse_subsequent_data_sections(
+    file_content: &[u8],
     index_in_map: &mut usize,
     metadata: &InstanceMetadata,
-) -> Vec<Point3D> {
-    let mut point_data: Vec<Point3D> = Vec::with_capacity(metadata.dimension);
+) -> Result<SubsequentDataSections, ParserError> {
+    let is_cvrp = matches!(metadata.problem_type, ProblemType::CVRP);
+    let mut sections = SubsequentDataSections {
+        demands: if is_cvrp {
+            vec![0; metadata.dimension]
+        } else {
+            Vec::new()
+        },
+        ..SubsequentDataSections::default()
+    };
 
-    // Read a line to test if the point data is floating point or integer
-    let is_float_data = is_float_data(file_content, index_in_map);
+    // Sections may end with the EOF line of the instance, after which nothing belongs to it
+    while !last_line_is_eof(file_content, *index_in_map)
+        && let Some((line_str, line_length)) = next_line(file_content, *index_in_map)
+    {
+        let line_str = line_str.trim();
+        if line_str.is_empty() {
+            *index_in_map += line_length;
+            continue;
+        }
+        let Ok(data_keyword) = parse_data_keyword(line_str) else {
+            break;
+        };
+        *index_in_map += line_length;
+
+        match data_keyword {
+            TSPDataKeyword::FIXED_EDGES_SECTION => sections.fixed_edges.extend(parse_fixed_edges(
+                file_content,
+                index_in_map,
+                metadata,
+            )?),
+            TSPDataKeyword::DEMAND_SECTION if is_cvrp => {
+                parse_demand_section(file_content, index_in_map, &mut sections.demands)?
+            }
+            TSPDataKeyword::DEPOT_SECTION if is_cvrp => {
+                sections.depots = parse_depot_section(file_content, index_in_map, metadata)?
+            }
+            TSPDataKeyword::DISPLAY_DATA_SECTION => {
+                sections.display_coordinates =
+                    parse_2d_node_coord_section(file_content, index_in_map, metadata, 1.0)?
+            }
+            TSPDataKeyword::NODE_COORD_SECTION
+                if matches!(metadata.edge_weight_type, EdgeWeightType::EXPLICIT) =>
+            {
+                sections.display_coordinates =
+                    parse_2d_node_coord_section(file_content, index_in_map, metadata, 1.0)?
+            }
+            data_keyword => {
+                return Err(DataSectionParseError::UnsupportedDataSection(data_keyword).into());
+            }
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Parses a DEMAND_SECTION, that is, lines of (1-based) node indices and their demands, into the
+/// given demands.
+fn parse_demand_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    demands: &mut [usize],
+) -> Result<(), DataSectionParseError> {
+    let mut node_seen = vec![false; demands.len()];
 
     while let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) {
         let line = &file_content[*index_in_map..*index_in_map + index_newline];
         // SAFETY: The TSP instance file is expected to be valid UTF-8
         let line_str = unsafe { std::str::from_utf8_unchecked(line) };
+        let line_str = line_str.trim();
 
         // Move the index to the start of the next line (+1 for the newline character)
         *index_in_map += index_newline + 1;
 
-        // Check if end of file is reached
-        if line_str == "EOF" {
+        if line_str == "EOF" || line_str.is_empty() || starts_data_section(line_str) {
+            // Leave the keyword to the caller
+            *index_in_map -= index_newline + 1;
+            break;
+        }
+
+        let mut parts = line_str.split_ascii_whitespace();
+        let node_index = parse_node_index(parts.next(), line_str, &mut node_seen)?;
+        let (Some(demand), None) = (parts.next(), parts.next()) else {
+            return Err(DataSectionParseError::InvalidDemand(line_str.to_string()));
+        };
+        demands[node_index] = demand
+            .parse()
+            .map_err(|_| DataSectionParseError::InvalidDemand(line_str.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Parses a DEPOT_SECTION, that is, a list of (1-based) node indices terminated by `-1`.
+///
+/// Returns the depots with 0-based node indices.
+fn parse_depot_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+) -> Result<Vec<Node>, DataSectionParseError> {
+    let mut depots = Vec::new();
+
+    while let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) {
+        let line = &file_content[*index_in_map..*index_in_map + index_newline];
+        // SAFETY: The TSP instance file is expected to be valid UTF-8
+        let line_str = unsafe { std::str::from_utf8_unchecked(line) };
+        let line_str = line_str.trim();
+
+        // Move the index to the start of the next line (+1 for the newline character)
+        *index_in_map += index_newline + 1;
+
+        if line_str == "-1" || line_str == "EOF" {
             break;
         }
 
-        let point = parse_line_to_3d_point(line_str, is_float_data);
+        match line_str.parse::<usize>() {
+            Ok(index) if (1..=metadata.dimension).contains(&index) => {
+                depots.push(Node::from_tsplib(index))
+            }
+            Ok(index) => {
+                return Err(DataSectionParseError::NodeIndexOutOfRange {
+                    index,
+                    dimension: metadata.dimension,
+                });
+            }
+            Err(_) => {
+                return Err(DataSectionParseError::InvalidNodeIndex(
+                    line_str.to_string(),
+                ));
+            }
+        }
+    }
 
-        point_data.push(point);
+    Ok(depots)
+}
+
+/// Checks that the coordinates of a GEO instance are geographical coordinates in the TSPLIB format
+/// `DDD.MM`. Degrees out of range are an error, while minutes of 60 or more only cause a warning,
+/// as they occur in TSPLIB instances as well.
+fn check_geo_coordinates(node_data: &[Point2D]) -> Result<(), DataSectionParseError> {
+    if let Some(node) = node_data
+        .iter()
+        .position(|point| !distance_function::geo_degrees_in_range(point))
+    {
+        return Err(DataSectionParseError::InvalidGeoCoordinate {
+            node: Node(node).to_tsplib(),
+            x: node_data[node].x,
+            y: node_data[node].y,
+        });
     }
 
-    point_data
+    let invalid_minutes = node_data
+        .iter()
+        .filter(|point| !distance_function::geo_minutes_valid(point))
+        .count();
+    if invalid_minutes > 0 {
+        warn!(
+            "{invalid_minutes} of {} GEO coordinates have 60 or more minutes, the coordinates \
+             might not be in the DDD.MM format",
+            node_data.len()
+        );
+    }
+    Ok(())
 }
 
+/// Creates the distance container from the node coordinates. If `strict` is set, also returns the
+/// pairs of distinct nodes whose distance has been rounded to 0, see [`rounded_zero_distances`].
+/// If diagnostics are given, the computation of the distances is recorded in them.
 #[inline(always)]
-fn parse_line_to_2d_point(line_str: &str, is_float_data: bool) -> Point2D {
-    // We assume the input to be split by ascii whitespace
-    let mut parts = line_str.split_ascii_whitespace();
-    let _node_index = parts.next();
-
-    let x_str = parts
-        .next()
-        .expect(format!("Missing x coordinate: {}", line_str).as_str());
-    let y_str = parts
-        .next()
-        .expect(format!("Missing y coordinate: {}", line_str).as_str());
-    let (x, y) = if is_float_data {
-        (
-            x_str
-                .parse::<f64>()
-                .expect("x coordinate should always be a valid f64 floating point number"),
-            y_str
-                .parse::<f64>()
-                .expect("y coordinate should always be a valid f64 floating point number"),
-        )
+fn from_node_coords<DistanceContainer: ParseFromTSPLib, PointType: PartialEq + Sync + Send>(
+    node_data: &Vec<PointType>,
+    metadata: &InstanceMetadata,
+    distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    strict: bool,
+    diagnostics: &mut Option<ParseDiagnostics>,
+) -> (DistanceContainer, Vec<(Node, Node)>) {
+    let start = Instant::now();
+    let (distance_container, threads_used) =
+        DistanceContainer::from_node_coord_section_with_threads(
+            node_data,
+            metadata,
+            distance_function,
+        );
+    if let Some(diagnostics) = diagnostics {
+        diagnostics.distance_compute_ns = start.elapsed().as_nanos();
+        diagnostics.points_parsed = node_data.len();
+        diagnostics.threads_used = threads_used;
+    }
+    let zero_distances = if strict {
+        rounded_zero_distances(node_data, distance_function)
     } else {
-        (
-            x_str
-                .parse::<i32>()
-                .expect("x coordinate should be a valid i32 integer by sampling first line")
-                as f64,
-            y_str
-                .parse::<i32>()
-                .expect("y coordinate should be a valid i32 integer by sampling first line")
-                as f64,
-        )
+        Vec::new()
     };
+    (distance_container, zero_distances)
+}
+
+/// Computes the distances from node coordinates which have been multiplied by the scale of the
+/// [`ParseOptions`] like [`from_node_coords`], but fails if a scaled distance does not fit into an
+/// `i32`.
+///
+/// The distance functions convert to `i32` with saturation, so overflowing distances show up as
+/// `i32::MAX`. The distance without scale, which is reported in the error, is computed by
+/// `unscaled_distance_function`.
+fn from_scaled_node_coords<
+    DistanceContainer: ParseFromTSPLib,
+    PointType: PartialEq + Sync + Send,
+>(
+    node_data: &Vec<PointType>,
+    metadata: &InstanceMetadata,
+    distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    unscaled_distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    options: &ParseOptions,
+    diagnostics: &mut Option<ParseDiagnostics>,
+) -> Result<(DistanceContainer, Vec<(Node, Node)>), DataSectionParseError> {
+    let scale = options.scale;
+    // The distance function cannot fail, so the first overflow is recorded and returned once all
+    // distances are computed
+    let overflow = OnceLock::new();
+    let overflow_ref = &overflow;
+    let checked_distance_function = move |point_a: &PointType, point_b: &PointType| {
+        let distance = distance_function(point_a, point_b);
+        if distance.0 == i32::MAX && scale != 1 {
+            let _ = overflow_ref.set(DataSectionParseError::ScaledDistanceOverflow {
+                distance: unscaled_distance_function(point_a, point_b).0,
+                scale,
+            });
+        }
+        distance
+    };
+    let distances = from_node_coords(
+        node_data,
+        metadata,
+        checked_distance_function,
+        options.strict,
+        diagnostics,
+    );
+    match overflow.into_inner() {
+        Some(error) => Err(error),
+        None => Ok(distances),
+    }
+}
+
+/// Returns the distance function on 2D coordinates before they have been multiplied by `scale`.
+fn unscaled_2d(
+    distance_function: impl Fn(&Point2D, &Point2D) -> Distance + Sync + Send + Copy,
+    scale: f64,
+) -> impl Fn(&Point2D, &Point2D) -> Distance + Sync + Send + Copy {
+    move |point_a: &Point2D, point_b: &Point2D| {
+        let unscale = |point: &Point2D| Point2D {
+            x: point.x / scale,
+            y: point.y / scale,
+        };
+        distance_function(&unscale(point_a), &unscale(point_b))
+    }
+}
 
-    Point2D { x, y }
+/// Returns the distance function on 3D coordinates before they have been multiplied by `scale`.
+fn unscaled_3d(
+    distance_function: impl Fn(&Point3D, &Point3D) -> Distance + Sync + Send + Copy,
+    scale: f64,
+) -> impl Fn(&Point3D, &Point3D) -> Distance + Sync + Send + Copy {
+    move |point_a: &Point3D, point_b: &Point3D| {
+        let unscale = |point: &Point3D| Point3D {
+            x: point.x / scale,
+            y: point.y / scale,
+            z: point.z / scale,
+        };
+        distance_function(&unscale(point_a), &unscale(point_b))
+    }
 }
 
+/// Returns the pairs `(i, j)` with `i < j` of nodes with distinct coordinates, whose distance is
+/// 0 nonetheless, as the exact distance has been rounded to 0.
+///
+/// Such spurious zero-weight edges may lead to degenerate tours and ties, e.g. in spanning trees.
+/// Takes `O(n^2)`, as all distances are computed again.
+pub fn rounded_zero_distances<PointType: PartialEq>(
+    node_data: &[PointType],
+    distance_function: impl Fn(&PointType, &PointType) -> Distance,
+) -> Vec<(Node, Node)> {
+    let mut zero_distances = Vec::new();
+    for (to, point_to) in node_data.iter().enumerate() {
+        for (from, point_from) in node_data.iter().enumerate().take(to) {
+            if distance_function(point_from, point_to) == Distance(0) && point_from != point_to {
+                zero_distances.push((Node(from), Node(to)));
+            }
+        }
+    }
+    zero_distances
+}
+
+/// Multiplies explicitly given distances by the scale of the [`ParseOptions`].
+fn scale_distances(distances: &mut [Distance], scale: u32) -> Result<(), DataSectionParseError> {
+    if scale != 1 {
+        for distance in distances {
+            *distance = scale_distance(*distance, scale)?;
+        }
+    }
+    Ok(())
+}
+
+/// Multiplies the distance by the scale, failing if the result does not fit into an `i32`.
+fn scale_distance(distance: Distance, scale: u32) -> Result<Distance, DataSectionParseError> {
+    i32::try_from(scale)
+        .ok()
+        .and_then(|factor| distance.0.checked_mul(factor))
+        .map(Distance)
+        .ok_or(DataSectionParseError::ScaledDistanceOverflow {
+            distance: distance.0,
+            scale,
+        })
+}
+
+/// Returns whether the line starts a new data section, i.e. is a data keyword rather than data.
 #[inline(always)]
-fn parse_line_to_3d_point(line_str: &str, is_float_data: bool) -> Point3D {
-    // We assume the input to be split by ascii whitespace
+fn starts_data_section(line_str: &str) -> bool {
+    line_str.starts_with(|c: char| c.is_ascii_alphabetic()) && line_str != "EOF"
+}
+
+/// Parses a 2D NODE_COORD_SECTION, multiplying all coordinates by the given scale.
+fn parse_2d_node_coord_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+    scale: f64,
+) -> Result<Vec<Point2D>, DataSectionParseError> {
+    let mut point_data = vec![Point2D { x: 0.0, y: 0.0 }; metadata.dimension];
+    let mut node_seen = vec![false; metadata.dimension];
+
+    // Read a line to test if the point data is floating point or integer
+    let is_float_data = is_float_data(file_content, index_in_map);
+
+    while let Some((line_str, line_length)) = next_line(file_content, *index_in_map) {
+        let line_str = line_str.trim();
+
+        // Move the index to the start of the next line
+        *index_in_map += line_length;
+
+        // Check if end of file is reached
+        if line_str == "EOF" || line_str.is_empty() {
+            break;
+        }
+        // Check if another data section follows, which is left to the caller
+        if starts_data_section(line_str) {
+            *index_in_map -= line_length;
+            break;
+        }
+
+        let (node_index, point) = parse_line_to_2d_point(line_str, is_float_data, &mut node_seen)?;
+
+        point_data[node_index] = point;
+    }
+
+    check_all_nodes_seen(&node_seen)?;
+
+    if scale != 1.0 {
+        for point in &mut point_data {
+            point.x *= scale;
+            point.y *= scale;
+        }
+    }
+
+    Ok(point_data)
+}
+
+/// Parses a 3D NODE_COORD_SECTION, multiplying all coordinates by the given scale.
+fn parse_3d_node_coord_section(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    metadata: &InstanceMetadata,
+    scale: f64,
+) -> Result<Vec<Point3D>, DataSectionParseError> {
+    let mut point_data = vec![
+        Point3D {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0
+        };
+        metadata.dimension
+    ];
+    let mut node_seen = vec![false; metadata.dimension];
+
+    // Read a line to test if the point data is floating point or integer
+    let is_float_data = is_float_data(file_content, index_in_map);
+
+    while let Some((line_str, line_length)) = next_line(file_content, *index_in_map) {
+        let line_str = line_str.trim();
+
+        // Move the index to the start of the next line
+        *index_in_map += line_length;
+
+        // Check if end of file is reached
+        if line_str == "EOF" || line_str.is_empty() {
+            break;
+        }
+        // Check if another data section follows, which is left to the caller
+        if starts_data_section(line_str) {
+            *index_in_map -= line_length;
+            break;
+        }
+
+        let (node_index, point) = parse_line_to_3d_point(line_str, is_float_data, &mut node_seen)?;
+
+        point_data[node_index] = point;
+    }
+
+    check_all_nodes_seen(&node_seen)?;
+
+    if scale != 1.0 {
+        for point in &mut point_data {
+            point.x *= scale;
+            point.y *= scale;
+            point.z *= scale;
+        }
+    }
+
+    Ok(point_data)
+}
+
+/// Parses the (1-based) node index at the start of a line of a node coordinate section and
+/// returns it as a 0-based index.
+///
+/// Marks the node as seen, failing if it is out of range or has already been seen before.
+#[inline(always)]
+fn parse_node_index(
+    node_index_str: Option<&str>,
+    line_str: &str,
+    node_seen: &mut [bool],
+) -> Result<usize, DataSectionParseError> {
+    let node_index = node_index_str
+        .and_then(|node_index_str| node_index_str.parse::<usize>().ok())
+        .ok_or_else(|| DataSectionParseError::InvalidNodeIndex(line_str.to_string()))?;
+
+    if node_index == 0 || node_index > node_seen.len() {
+        return Err(DataSectionParseError::NodeIndexOutOfRange {
+            index: node_index,
+            dimension: node_seen.len(),
+        });
+    }
+    let node = Node::from_tsplib(node_index);
+    if std::mem::replace(&mut node_seen[node.0], true) {
+        return Err(DataSectionParseError::DuplicateNodeIndex(node_index));
+    }
+
+    Ok(node.0)
+}
+
+/// Checks that each node appeared in the node coordinate section.
+fn check_all_nodes_seen(node_seen: &[bool]) -> Result<(), DataSectionParseError> {
+    match node_seen.iter().position(|&seen| !seen) {
+        Some(missing) => Err(DataSectionParseError::MissingNodeIndex(
+            Node(missing).to_tsplib(),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[inline(always)]
+fn parse_line_to_2d_point(
+    line_str: &str,
+    is_float_data: bool,
+    node_seen: &mut [bool],
+) -> Result<(usize, Point2D), DataSectionParseError> {
+    // Columns may be separated by any number of spaces and tabs
     let mut parts = line_str.split_ascii_whitespace();
-    let _node_index = parts.next();
-
-    let x_str = parts.next().expect("Missing x coordinate");
-    let y_str = parts.next().expect("Missing y coordinate");
-    let z_str = parts.next().expect("Missing z coordinate");
-    let (x, y, z) = if is_float_data {
-        (
-            x_str
-                .parse::<f64>()
-                .expect("x coordinate should always be a valid f64 floating point number"),
-            y_str
-                .parse::<f64>()
-                .expect("y coordinate should always be a valid f64 floating point number"),
-            z_str
-                .parse::<f64>()
-                .expect("z coordinate should always be a valid f64 floating point number"),
-        )
-    } else {
-        (
-            x_str
-                .parse::<i32>()
-                .expect("x coordinate should be a valid i32 integer by sampling first line")
-                as f64,
-            y_str
-                .parse::<i32>()
-                .expect("y coordinate should be a valid i32 integer by sampling first line")
-                as f64,
-            z_str
-                .parse::<i32>()
-                .expect("z coordinate should be a valid i32 integer by sampling first line")
-                as f64,
-        )
-    };
+    let node_index = parse_node_index(parts.next(), line_str, node_seen)?;
 
-    Point3D { x, y, z }
+    let mut next_coordinate = || parse_coordinate(parts.next(), line_str, is_float_data);
+    let x = next_coordinate()?;
+    let y = next_coordinate()?;
+
+    Ok((node_index, Point2D { x, y }))
 }
 
 #[inline(always)]
-fn is_float_data(file_content: &FileContent, index_in_map: &usize) -> bool {
-    let index_newline = memchr(b'\n', &file_content[*index_in_map..])
-        .expect("The data section should not be empty");
-    let line = &file_content[*index_in_map..*index_in_map + index_newline];
+fn parse_line_to_3d_point(
+    line_str: &str,
+    is_float_data: bool,
+    node_seen: &mut [bool],
+) -> Result<(usize, Point3D), DataSectionParseError> {
+    // Columns may be separated by any number of spaces and tabs
+    let mut parts = line_str.split_ascii_whitespace();
+    let node_index = parse_node_index(parts.next(), line_str, node_seen)?;
+
+    let mut next_coordinate = || parse_coordinate(parts.next(), line_str, is_float_data);
+    let x = next_coordinate()?;
+    let y = next_coordinate()?;
+    let z = next_coordinate()?;
+
+    Ok((node_index, Point3D { x, y, z }))
+}
+
+/// Parses a single coordinate of the given line, failing if the token is missing or not a number.
+///
+/// If the data has been sampled to be integer data, tokens consisting only of digits (with an
+/// optional leading minus sign) are parsed as `i64`, which is faster. All other tokens are parsed
+/// as `f64`, which also covers scientific notation like `3.5e2`.
+#[inline(always)]
+fn parse_coordinate(
+    token: Option<&str>,
+    line_str: &str,
+    is_float_data: bool,
+) -> Result<f64, DataSectionParseError> {
+    let invalid_coordinate = || DataSectionParseError::InvalidCoordinate(line_str.to_string());
+    let token = token.ok_or_else(invalid_coordinate)?;
+    if !is_float_data
+        && token
+            .strip_prefix('-')
+            .unwrap_or(token)
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+        && let Ok(value) = token.parse::<i64>()
+    {
+        return Ok(value as f64);
+    }
+    token.parse::<f64>().map_err(|_| invalid_coordinate())
+}
+
+/// Returns the line starting at the given index together with its length in bytes, including the
+/// newline character if there is one.
+///
+/// The last line of the file does not need to end with a newline. Returns `None` at the end of the
+/// file.
+#[inline(always)]
+fn next_line(file_content: &[u8], index_in_map: usize) -> Option<(&str, usize)> {
+    let rest = &file_content[index_in_map..];
+    if rest.is_empty() {
+        return None;
+    }
+    let (line, line_length) = match memchr(b'\n', rest) {
+        Some(index_newline) => (&rest[..index_newline], index_newline + 1),
+        None => (rest, rest.len()),
+    };
 
     // SAFETY: The TSP instance file is expected to be valid UTF-8
-    let line_str = unsafe { std::str::from_utf8_unchecked(line) };
+    Some((unsafe { std::str::from_utf8_unchecked(line) }, line_length))
+}
 
-    // We assume the input to be split by ascii whitespace
-    let mut parts = line_str.split_ascii_whitespace();
-    let _node_index = parts.next();
+/// Samples the first line of the node coordinate section to check whether the coordinates are
+/// floating point numbers (decimal point or exponent) or integers.
+///
+/// If the section is empty or its first line has no x coordinate, the data is treated as integer
+/// data and the error is left to the parsing of the line itself.
+#[inline(always)]
+fn is_float_data(file_content: &[u8], index_in_map: &usize) -> bool {
+    let Some((line_str, _)) = next_line(file_content, *index_in_map) else {
+        return false;
+    };
 
-    let x_str = parts.next().expect("Missing x coordinate");
-    x_str.contains('.')
+    // Columns may be separated by any number of spaces and tabs, skip the node index
+    line_str
+        .split_ascii_whitespace()
+        .nth(1)
+        .is_some_and(|x_str| x_str.contains(['.', 'e', 'E']))
 }
@@ -0,0 +1,19 @@
+/// Counts and timings collected while parsing an instance, see
+/// [`parse_tsp_instance_with_diagnostics`](crate::parse_tsp_instance_with_diagnostics).
+///
+/// Only collected if [`ParseOptions::diagnostics`](crate::ParseOptions::diagnostics) is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseDiagnostics {
+    /// Number of lines of the instance which have been read, including the specification part.
+    pub lines_read: usize,
+    /// Number of node coordinates parsed, 0 if the distances are given explicitly.
+    pub points_parsed: usize,
+    /// Time spent on creating the distance container from the node coordinates or the explicit
+    /// edge weights, in nanoseconds. This does not include the checks of
+    /// [`ParseOptions::strict`](crate::ParseOptions::strict).
+    pub distance_compute_ns: u128,
+    /// Number of threads used to compute the distances from the node coordinates, which is more
+    /// than one once the instance exceeds the parallelism bound of the distance container.
+    /// Explicit edge weights are always converted by a single thread.
+    pub threads_used: usize,
+}
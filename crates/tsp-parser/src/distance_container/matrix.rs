@@ -1,21 +1,52 @@
 use std::cell::UnsafeCell;
 
 use log::trace;
-use tsp_core::instance::{InstanceMetadata, distance::Distance, matrix::Matrix};
+use tsp_core::{
+    instance::{InstanceMetadata, distance::Distance, matrix::Matrix, node::Node},
+    triangular::{find_row_column_from_lower_triangle_index, get_lower_triangle_matrix_entry},
+};
 
-use super::ParseFromTSPLib;
-use crate::distance_container::find_row_column_from_lower_triangle_index;
+use super::{ParseFromTSPLib, WriteToTSPLib, available_threads};
 
 // TODO: Add more fine grained benchmarks to determine optimal parallelism bound
 const PARALLELISM_BOUND: usize = 100_000;
 
+/// Number of rows and columns of the tiles in which [`mirror_lower_triangle`] copies the entries.
+/// A tile of 64 x 64 distances takes 16 KiB, so a tile below the diagonal and its counterpart
+/// above it fit into the L1 cache together.
+const MIRROR_TILE_SIZE: usize = 64;
+
 impl ParseFromTSPLib for Matrix<Distance> {
     fn from_node_coord_section<PointType: Sync + Send>(
         node_data: &Vec<PointType>,
         metadata: &InstanceMetadata,
         distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
     ) -> Self {
-        compute_dists_from_node_coords(&node_data, metadata.dimension, distance_function)
+        Self::from_node_coord_section_with_threads(node_data, metadata, distance_function).0
+    }
+
+    fn from_node_coord_section_with_threads<PointType: Sync + Send>(
+        node_data: &Vec<PointType>,
+        metadata: &InstanceMetadata,
+        distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    ) -> (Self, usize) {
+        compute_dists_from_node_coords(node_data, metadata.dimension, distance_function)
+    }
+
+    fn from_lower_triangle(lower_triangle: Vec<Distance>, metadata: &InstanceMetadata) -> Self {
+        Matrix::new_from_distance_function(metadata.dimension, |from, to| {
+            lower_triangle[get_lower_triangle_matrix_entry(from.0, to.0)]
+        })
+    }
+
+    fn from_full_matrix(full_matrix: Vec<Distance>, metadata: &InstanceMetadata) -> Self {
+        Matrix::new(full_matrix, metadata.dimension)
+    }
+}
+
+impl WriteToTSPLib for Matrix<Distance> {
+    fn distance(&self, from: Node, to: Node) -> Distance {
+        self.get_data(from, to)
     }
 }
 
@@ -24,13 +55,38 @@ fn compute_dists_from_node_coords<PointType: Send + Sync>(
     point_data: &[PointType],
     dimension: usize,
     distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
-) -> Matrix<Distance> {
+) -> (Matrix<Distance>, usize) {
+    let nthreads = if dimension * dimension < PARALLELISM_BOUND {
+        1
+    } else {
+        available_threads()
+    };
+    compute_dists_with_threads(point_data, dimension, nthreads, distance_function)
+}
+
+/// Computes the distances like [`compute_dists_from_node_coords`], splitting the lower triangle
+/// into chunks with the same number of entries for the given number of threads.
+///
+/// With a single thread (or 0), the distances are computed on the current thread without
+/// spawning any. Returns the distances and the number of threads used, which is less than the
+/// given number if there are fewer entries than threads.
+fn compute_dists_with_threads<PointType: Send + Sync>(
+    point_data: &[PointType],
+    dimension: usize,
+    nthreads: usize,
+    distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+) -> (Matrix<Distance>, usize) {
     let total_size = dimension * dimension;
     let number_of_entries = (dimension * (dimension + 1)) / 2;
 
     let mut distance_data = vec![Distance(0); total_size];
+    // Without pairs of distinct nodes, there is nothing to compute (or split into chunks)
+    if dimension <= 1 {
+        return (Matrix::new(distance_data, dimension), 1);
+    }
 
-    if total_size < PARALLELISM_BOUND {
+    let mut threads_used = 1;
+    if nthreads <= 1 {
         compute_dists_from_node_coords_chunk(
             &mut distance_data,
             point_data,
@@ -41,8 +97,8 @@ fn compute_dists_from_node_coords<PointType: Send + Sync>(
             dimension,
         );
     } else {
-        let nthreads = std::thread::available_parallelism().unwrap();
-        let entries_per_chunk = number_of_entries.div_ceil(nthreads.get());
+        let entries_per_chunk = number_of_entries.div_ceil(nthreads);
+        threads_used = number_of_entries.div_ceil(entries_per_chunk);
 
         std::thread::scope(|scope| {
             // We keep track of two main indices:
@@ -102,14 +158,29 @@ fn compute_dists_from_node_coords<PointType: Send + Sync>(
         });
     }
 
-    for row in 0..dimension {
-        for column in 0..row {
-            let value = distance_data[row * dimension + column];
-            distance_data[column * dimension + row] = value;
+    mirror_lower_triangle(&mut distance_data, dimension);
+
+    (Matrix::new(distance_data, dimension), threads_used)
+}
+
+/// Copies the entries below the diagonal to their counterparts above it.
+///
+/// Reading a row below the diagonal means writing a column above it, which touches a different
+/// cache line for every entry. Thus, the matrix is processed in tiles of
+/// [`MIRROR_TILE_SIZE`] x [`MIRROR_TILE_SIZE`] entries, which stay in cache while they are copied.
+/// For `d18512.tsp`, this almost halves the time spent mirroring.
+fn mirror_lower_triangle(distance_data: &mut [Distance], dimension: usize) {
+    for row_tile in (0..dimension).step_by(MIRROR_TILE_SIZE) {
+        let row_tile_end = (row_tile + MIRROR_TILE_SIZE).min(dimension);
+        for column_tile in (0..=row_tile).step_by(MIRROR_TILE_SIZE) {
+            for row in row_tile..row_tile_end {
+                for column in column_tile..(column_tile + MIRROR_TILE_SIZE).min(row) {
+                    distance_data[column * dimension + row] =
+                        distance_data[row * dimension + column];
+                }
+            }
         }
     }
-
-    Matrix::new(distance_data, dimension)
 }
 
 #[inline(always)]
@@ -130,32 +201,18 @@ fn compute_dists_from_node_coords_chunk<PointType>(
         "Start row: {}, start column: {}, end row: {}, end column: {}",
         start_row, start_column, end_row, end_column
     );
-    let start_row_point_data = &point_data[start_row];
-    // We take() to only iterate up to start_row - 1, that is, we can omit the column = start_row
-    // case, as it is always zero distance
-    // We also skip() to start from start_column
-    for (column, column_point_data) in point_data
-        .iter()
-        .enumerate()
-        .take(start_row)
-        .skip(start_column)
-    {
-        compute_and_set_distance(
-            chunk,
-            start_row,
-            column,
-            chunk_start_index,
-            start_row_point_data,
-            column_point_data,
-            distance_function,
-            dimension,
-        );
-    }
-
-    for row in (start_row + 1)..end_row {
+    for row in start_row..=end_row {
         let row_point_data = &point_data[row];
-        // We can omit the column = start_row case, as it is always zero distance
-        for (column, column_point_data) in point_data.iter().enumerate().take(row) {
+        // The first and last row of the chunk may be partial. We can omit the column = row case,
+        // as it is always zero distance
+        let first_column = if row == start_row { start_column } else { 0 };
+        let end = if row == end_row { end_column + 1 } else { row };
+        for (column, column_point_data) in point_data
+            .iter()
+            .enumerate()
+            .take(end.min(row))
+            .skip(first_column)
+        {
             compute_and_set_distance(
                 chunk,
                 row,
@@ -168,21 +225,6 @@ fn compute_dists_from_node_coords_chunk<PointType>(
             );
         }
     }
-
-    let end_row_point_data = &point_data[end_row];
-    // We take() to only iterate up to end_column, since it's one indexed, we add 1
-    for (column, column_point_data) in point_data.iter().enumerate().take(end_column + 1) {
-        compute_and_set_distance(
-            chunk,
-            end_row,
-            column,
-            chunk_start_index,
-            end_row_point_data,
-            column_point_data,
-            distance_function,
-            dimension,
-        );
-    }
 }
 
 #[inline(always)]
@@ -223,3 +265,84 @@ fn set_distance(
     // Safety: Index is computed to be within bounds of distance_data
     unsafe { *chunk.get_unchecked_mut(index_in_chunk) = distance };
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::SmallRng};
+
+    use super::*;
+    use crate::distance_container::test_utils::pair_distance;
+
+    #[test]
+    fn test_mirror_lower_triangle_across_tiles() {
+        for dimension in [
+            MIRROR_TILE_SIZE - 1,
+            MIRROR_TILE_SIZE,
+            2 * MIRROR_TILE_SIZE + 1,
+        ] {
+            let mut distance_data: Vec<_> = (0..dimension * dimension)
+                .map(|index| {
+                    let (row, column) = (index / dimension, index % dimension);
+                    if column < row {
+                        pair_distance(&row, &column)
+                    } else {
+                        Distance(0)
+                    }
+                })
+                .collect();
+            mirror_lower_triangle(&mut distance_data, dimension);
+            for row in 0..dimension {
+                for column in 0..dimension {
+                    assert_eq!(
+                        distance_data[row * dimension + column],
+                        pair_distance(&row, &column),
+                        "Wrong distance between {row} and {column} for dimension {dimension}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_degenerate_dimensions() {
+        for dimension in 0..=1 {
+            let point_data: Vec<usize> = (0..dimension).collect();
+            for nthreads in 0..=4 {
+                let (distances, threads_used) =
+                    compute_dists_with_threads(&point_data, dimension, nthreads, pair_distance);
+                assert_eq!(threads_used, 1);
+                assert_eq!(distances.dimension(), dimension);
+                assert!(
+                    distances
+                        .data()
+                        .iter()
+                        .all(|&distance| distance == Distance(0))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_chunks_match_serial() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let dimension = rng.random_range(1..80);
+            let nthreads = rng.random_range(0..=32);
+            let point_data: Vec<usize> = (0..dimension).collect();
+
+            let (distances, threads_used) =
+                compute_dists_with_threads(&point_data, dimension, nthreads, pair_distance);
+            assert!((1..=nthreads.max(1)).contains(&threads_used));
+            for row in 0..dimension {
+                for column in 0..dimension {
+                    assert_eq!(
+                        distances.get_data(Node(row), Node(column)),
+                        pair_distance(&row, &column),
+                        "Wrong distance between {row} and {column} for dimension {dimension} and \
+                         {nthreads} threads"
+                    );
+                }
+            }
+        }
+    }
+}
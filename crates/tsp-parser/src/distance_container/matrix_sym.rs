@@ -1,11 +1,11 @@
-use tsp_core::instance::{
-    InstanceMetadata,
-    distance::Distance,
-    matrix::{MatrixSym, get_lower_triangle_matrix_entry_row_bigger},
+use tsp_core::{
+    instance::{InstanceMetadata, distance::Distance, matrix::MatrixSym, node::Node},
+    triangular::{
+        find_row_column_from_lower_triangle_index, get_lower_triangle_matrix_entry_row_bigger,
+    },
 };
 
-use super::ParseFromTSPLib;
-use crate::distance_container::find_row_column_from_lower_triangle_index;
+use super::{ParseFromTSPLib, WriteToTSPLib, available_threads};
 
 // TODO: Add more fine grained benchmarks to determine optimal parallelism bound
 const PARALLELISM_BOUND: usize = 300_000;
@@ -16,7 +16,44 @@ impl ParseFromTSPLib for MatrixSym<Distance> {
         metadata: &InstanceMetadata,
         distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
     ) -> Self {
-        compute_dists_from_node_coords(&node_data, metadata.dimension, distance_function)
+        Self::from_node_coord_section_with_threads(node_data, metadata, distance_function).0
+    }
+
+    fn from_node_coord_section_with_threads<PointType: Sync + Send>(
+        node_data: &Vec<PointType>,
+        metadata: &InstanceMetadata,
+        distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    ) -> (Self, usize) {
+        compute_dists_from_node_coords(node_data, metadata.dimension, distance_function)
+    }
+
+    fn from_lower_triangle(lower_triangle: Vec<Distance>, metadata: &InstanceMetadata) -> Self {
+        MatrixSym::new(lower_triangle, metadata.dimension)
+    }
+
+    fn from_full_matrix(full_matrix: Vec<Distance>, metadata: &InstanceMetadata) -> Self {
+        let dimension = metadata.dimension;
+        let lower_triangle = (0..dimension)
+            .flat_map(|row| (0..=row).map(move |column| row * dimension + column))
+            .map(|index| full_matrix[index])
+            .collect();
+        MatrixSym::new(lower_triangle, dimension)
+    }
+
+    fn memory_estimate(dimension: usize) -> usize {
+        // The lower triangle including the diagonal
+        let entries = if dimension.is_multiple_of(2) {
+            (dimension / 2).saturating_mul(dimension + 1)
+        } else {
+            dimension.saturating_mul(dimension / 2 + 1)
+        };
+        entries.saturating_mul(size_of::<Distance>())
+    }
+}
+
+impl WriteToTSPLib for MatrixSym<Distance> {
+    fn distance(&self, from: Node, to: Node) -> Distance {
+        self.get_data(from, to)
     }
 }
 
@@ -24,16 +61,42 @@ fn compute_dists_from_node_coords<PointType: Send + Sync>(
     point_data: &[PointType],
     dimension: usize,
     distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
-) -> MatrixSym<Distance> {
+) -> (MatrixSym<Distance>, usize) {
+    let total_size = dimension * (dimension + 1) / 2;
+    let nthreads = if total_size < PARALLELISM_BOUND {
+        1
+    } else {
+        available_threads()
+    };
+    compute_dists_with_threads(point_data, dimension, nthreads, distance_function)
+}
+
+/// Computes the distances like [`compute_dists_from_node_coords`], splitting the lower triangle
+/// into chunks of equal size for the given number of threads.
+///
+/// With a single thread (or 0), the distances are computed on the current thread without
+/// spawning any. Returns the distances and the number of threads used, which is less than the
+/// given number if there are fewer entries than threads.
+fn compute_dists_with_threads<PointType: Send + Sync>(
+    point_data: &[PointType],
+    dimension: usize,
+    nthreads: usize,
+    distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+) -> (MatrixSym<Distance>, usize) {
     let total_size = dimension * (dimension + 1) / 2;
 
     let mut distance_data = vec![Distance(0); total_size];
+    // Without pairs of distinct nodes, there is nothing to compute (or split into chunks)
+    if dimension <= 1 {
+        return (MatrixSym::new(distance_data, dimension), 1);
+    }
 
-    if total_size < PARALLELISM_BOUND {
+    let mut threads_used = 1;
+    if nthreads <= 1 {
         compute_dists_from_node_coords_chunk(&mut distance_data, point_data, 0, distance_function);
     } else {
-        let nthreads = std::thread::available_parallelism().unwrap();
-        let chunk_size = total_size.div_ceil(nthreads.get());
+        let chunk_size = total_size.div_ceil(nthreads);
+        threads_used = total_size.div_ceil(chunk_size);
 
         std::thread::scope(|scope| {
             let mut current_chunk_start = 0;
@@ -53,7 +116,7 @@ fn compute_dists_from_node_coords<PointType: Send + Sync>(
         });
     }
 
-    MatrixSym::new(distance_data, dimension)
+    (MatrixSym::new(distance_data, dimension), threads_used)
 }
 
 #[inline(always)]
@@ -68,29 +131,18 @@ fn compute_dists_from_node_coords_chunk<PointType>(
     let (end_row, end_column) =
         find_row_column_from_lower_triangle_index(chunk_start_index + chunk.len() - 1);
 
-    let start_row_point_data = &point_data[start_row];
-    // We can omit the column = start_row case, as it is always zero distance
-    for (column, column_point_data) in point_data
-        .iter()
-        .enumerate()
-        .take(start_row)
-        .skip(start_column)
-    {
-        compute_and_set_distance(
-            chunk,
-            start_row,
-            column,
-            chunk_start_index,
-            start_row_point_data,
-            column_point_data,
-            distance_function,
-        );
-    }
-
-    for row in (start_row + 1)..end_row {
+    for row in start_row..=end_row {
         let row_point_data = &point_data[row];
-        // We can omit the column = start_row case, as it is always zero distance
-        for (column, column_point_data) in point_data.iter().enumerate().take(row) {
+        // The first and last row of the chunk may be partial. We can omit the column = row case,
+        // as it is always zero distance
+        let first_column = if row == start_row { start_column } else { 0 };
+        let end = if row == end_row { end_column + 1 } else { row };
+        for (column, column_point_data) in point_data
+            .iter()
+            .enumerate()
+            .take(end.min(row))
+            .skip(first_column)
+        {
             compute_and_set_distance(
                 chunk,
                 row,
@@ -102,20 +154,6 @@ fn compute_dists_from_node_coords_chunk<PointType>(
             );
         }
     }
-
-    let end_row_point_data = &point_data[end_row];
-    // We can omit the column = start_row case, as it is always zero distance
-    for (column, column_point_data) in point_data.iter().enumerate().take(end_column) {
-        compute_and_set_distance(
-            chunk,
-            end_row,
-            column,
-            chunk_start_index,
-            end_row_point_data,
-            column_point_data,
-            distance_function,
-        );
-    }
 }
 
 #[inline(always)]
@@ -155,3 +193,54 @@ fn set_distance(
     // Safety: Index is computed to be within bounds of distance_data
     unsafe { *chunk.get_unchecked_mut(index_in_chunk) = distance };
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngExt, SeedableRng, rngs::SmallRng};
+
+    use super::*;
+    use crate::distance_container::test_utils::pair_distance;
+
+    #[test]
+    fn test_degenerate_dimensions() {
+        for dimension in 0..=1 {
+            let point_data: Vec<usize> = (0..dimension).collect();
+            for nthreads in 0..=4 {
+                let (distances, threads_used) =
+                    compute_dists_with_threads(&point_data, dimension, nthreads, pair_distance);
+                assert_eq!(threads_used, 1);
+                assert_eq!(distances.dimension(), dimension);
+                assert!(
+                    distances
+                        .data()
+                        .iter()
+                        .all(|&distance| distance == Distance(0))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_chunks_match_serial() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let dimension = rng.random_range(1..80);
+            let nthreads = rng.random_range(0..=32);
+            let point_data: Vec<usize> = (0..dimension).collect();
+
+            let (distances, threads_used) =
+                compute_dists_with_threads(&point_data, dimension, nthreads, pair_distance);
+            assert!((1..=nthreads.max(1)).contains(&threads_used));
+            for row in 0..dimension {
+                for column in 0..dimension {
+                    assert_eq!(
+                        distances.get_data(Node(row), Node(column)),
+                        pair_distance(&row, &column),
+                        "Wrong distance between {row} and {column} for dimension {dimension} and \
+                         {nthreads} threads"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use tsp_core::instance::{InstanceMetadata, distance::Distance};
+use std::num::NonZeroUsize;
+
+use tsp_core::instance::{InstanceMetadata, distance::Distance, node::Node};
 
 mod matrix;
 mod matrix_sym;
+#[cfg(test)]
+mod test_utils;
 
 pub trait ParseFromTSPLib {
     fn from_node_coord_section<PointType: Sync + Send>(
@@ -9,10 +13,78 @@ pub trait ParseFromTSPLib {
         metadata: &InstanceMetadata,
         distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
     ) -> Self;
+
+    /// Creates the container like [`from_node_coord_section`](Self::from_node_coord_section) and
+    /// additionally returns the number of threads used to compute the distances.
+    ///
+    /// The default implementation reports a single thread.
+    fn from_node_coord_section_with_threads<PointType: Sync + Send>(
+        node_data: &Vec<PointType>,
+        metadata: &InstanceMetadata,
+        distance_function: impl Fn(&PointType, &PointType) -> Distance + Sync + Send + Copy,
+    ) -> (Self, usize)
+    where
+        Self: Sized,
+    {
+        (
+            Self::from_node_coord_section(node_data, metadata, distance_function),
+            1,
+        )
+    }
+
+    /// Creates the container from the lower triangle (including the diagonal) of a symmetric
+    /// distance matrix in row-major order, e.g. from an EDGE_WEIGHT_SECTION.
+    fn from_lower_triangle(lower_triangle: Vec<Distance>, metadata: &InstanceMetadata) -> Self;
+
+    /// Creates the container from a full (possibly asymmetric) distance matrix in row-major
+    /// order, e.g. from an EDGE_WEIGHT_SECTION in the FULL_MATRIX format.
+    ///
+    /// Containers for symmetric distances only keep the lower triangle, that is, the entries
+    /// (row, column) with row >= column.
+    fn from_full_matrix(full_matrix: Vec<Distance>, metadata: &InstanceMetadata) -> Self;
+
+    /// Returns the number of bytes the distances of an instance with the given dimension take in
+    /// this container, see [`matrix_memory_estimate`].
+    ///
+    /// The default implementation assumes a full matrix of `dimension * dimension` distances.
+    fn memory_estimate(dimension: usize) -> usize {
+        dimension
+            .saturating_mul(dimension)
+            .saturating_mul(size_of::<Distance>())
+    }
+}
+
+/// Returns the number of bytes the distances of an instance with the given dimension take in the
+/// given container, saturating at `usize::MAX`.
+///
+/// Only the distances of the final container are counted. While parsing, explicit formats may
+/// temporarily need memory for the entries in the order of the file as well.
+pub fn matrix_memory_estimate<DistanceContainer: ParseFromTSPLib>(dimension: usize) -> usize {
+    DistanceContainer::memory_estimate(dimension)
+}
+
+/// Returns whether the distances of an instance with the given dimension fit into the given
+/// number of bytes in the given container, see [`matrix_memory_estimate`].
+///
+/// The dimension is known after [`parse_metadata`](crate::metadata::parse_metadata), so tooling
+/// can check this before parsing the data sections of huge instances like `pla85900.tsp`, whose
+/// full matrix takes about 30 GB.
+pub fn can_build_matrix<DistanceContainer: ParseFromTSPLib>(
+    dimension: usize,
+    limit: usize,
+) -> bool {
+    matrix_memory_estimate::<DistanceContainer>(dimension) <= limit
+}
+
+/// Returns the number of threads to compute the distances of large instances with, falling back
+/// to a single thread if the available parallelism cannot be determined.
+fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
 }
 
-fn find_row_column_from_lower_triangle_index(index: usize) -> (usize, usize) {
-    let row = (-0.5 + ((0.25 + 2.0 * index as f64).sqrt())).floor() as usize;
-    let column = index - (row * (row + 1)) / 2;
-    (row, column)
+/// Distance containers which can be written to TSPLIB files, see
+/// [`write_instance`](crate::writer::write_instance).
+pub trait WriteToTSPLib {
+    /// Returns the distance between the given nodes.
+    fn distance(&self, from: Node, to: Node) -> Distance;
 }
@@ -0,0 +1,12 @@
+//! Fixtures shared by the tests of the distance containers.
+
+use tsp_core::instance::distance::Distance;
+
+/// Distinct distance for each pair of distinct nodes given by their index, such that entries
+/// computed for the wrong pair of nodes are detected.
+pub(super) fn pair_distance(from: &usize, to: &usize) -> Distance {
+    if from == to {
+        return Distance(0);
+    }
+    Distance((from.max(to) * 1000 + from.min(to)) as i32)
+}
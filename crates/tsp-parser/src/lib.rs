@@ -1,18 +1,36 @@
-use std::{fs::File, ops::Deref, path::Path};
+use std::{fs::File, io::Read, ops::Deref, path::Path};
 
+use memchr::{memchr, memchr_iter, memrchr};
 use memmap2::{Advice, Mmap};
 use thiserror::Error;
-use tsp_core::instance::TSPSymInstance;
+use tsp_core::{
+    instance::TSPSymInstance,
+    tsp_lib_spec::{ProblemType, TSPDataKeyword},
+};
 
 use crate::{
-    data_section::parse_data_sections,
+    data_section::{
+        DataSectionParseError, parse_distances, parse_fixed_edges_section, parse_sop_data_section,
+        parse_subsequent_data_sections,
+    },
     distance_container::ParseFromTSPLib,
     metadata::{MetaDataParseError, parse_metadata},
 };
 
 pub mod data_section;
+mod diagnostics;
 pub mod distance_container;
 pub mod metadata;
+mod options;
+#[cfg(feature = "plotters")]
+pub mod plot;
+pub mod statistics;
+mod tour;
+pub mod writer;
+
+pub use diagnostics::ParseDiagnostics;
+pub use options::ParseOptions;
+pub use tour::{parse_tour, parse_tsp_tour};
 
 #[derive(Error, Debug)]
 pub enum ParserError {
@@ -20,6 +38,21 @@ pub enum ParserError {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     MetaDataParsing(#[from] MetaDataParseError),
+    #[error(transparent)]
+    DataSectionParsing(#[from] DataSectionParseError),
+    #[error("Invalid UTF-8 in line {line}")]
+    InvalidUtf8 { line: usize },
+    /// The instance uses a feature of the TSPLIB format which is not implemented yet.
+    #[error("Unsupported feature: {feature}")]
+    Unsupported { feature: &'static str },
+    /// The instance has the EDGE_WEIGHT_TYPE SPECIAL, but no
+    /// [`ParseOptions::special_distance_function`] is given.
+    #[error("EDGE_WEIGHT_TYPE SPECIAL requires a distance function in the parse options")]
+    SpecialFunctionRequired,
+    /// The file is a tour file with the TYPE TOUR, which has no distances and has to be parsed
+    /// with [`parse_tsp_tour`] instead.
+    #[error("The file has the TYPE TOUR, use parse_tsp_tour to parse tour files")]
+    TourFile,
 }
 
 pub struct FileContent {
@@ -33,19 +66,202 @@ pub struct FileContent {
 pub fn parse_tsp_instance<DistanceContainer: ParseFromTSPLib>(
     instance_path: impl AsRef<Path>,
 ) -> Result<TSPSymInstance<DistanceContainer>, ParserError> {
+    parse_tsp_instance_with_options(instance_path, &ParseOptions::default())
+}
+
+/// Parses a TSPLIB instance like [`parse_tsp_instance`] with the given options, e.g. to scale the
+/// distances.
+pub fn parse_tsp_instance_with_options<DistanceContainer: ParseFromTSPLib>(
+    instance_path: impl AsRef<Path>,
+    options: &ParseOptions,
+) -> Result<TSPSymInstance<DistanceContainer>, ParserError> {
+    let file_content = FileContent::new(instance_path)?;
+    parse_instance(&file_content, &mut 0, options, &mut None)
+}
+
+/// Parses a TSPLIB instance like [`parse_tsp_instance_with_options`] and additionally returns
+/// [`ParseDiagnostics`], e.g. to tune the parallel computation of the distances.
+///
+/// The diagnostics are `None` unless [`ParseOptions::diagnostics`] is set, such that the default
+/// options do not pay for collecting them.
+pub fn parse_tsp_instance_with_diagnostics<DistanceContainer: ParseFromTSPLib>(
+    instance_path: impl AsRef<Path>,
+    options: &ParseOptions,
+) -> Result<(TSPSymInstance<DistanceContainer>, Option<ParseDiagnostics>), ParserError> {
     let file_content = FileContent::new(instance_path)?;
+    let mut diagnostics = options.diagnostics.then(ParseDiagnostics::default);
     let mut index_in_map = 0;
+    let instance = parse_instance(&file_content, &mut index_in_map, options, &mut diagnostics)?;
+    if let Some(diagnostics) = &mut diagnostics {
+        diagnostics.lines_read = memchr_iter(b'\n', &file_content[..index_in_map]).count();
+    }
+    Ok((instance, diagnostics))
+}
+
+/// Parses a TSPLIB instance from the given reader, e.g. from stdin for use in shell pipelines.
+///
+/// In contrast to [`parse_tsp_instance`], the file can not be memory mapped, so the whole stream is
+/// read into a buffer first. Returns [`ParserError::InvalidUtf8`] if the stream is not valid
+/// UTF-8.
+pub fn parse_tsp_reader<DistanceContainer: ParseFromTSPLib>(
+    reader: impl Read,
+) -> Result<TSPSymInstance<DistanceContainer>, ParserError> {
+    parse_tsp_reader_with_options(reader, &ParseOptions::default())
+}
 
-    let (metadata, data_keyword) = parse_metadata(&file_content, &mut index_in_map)?;
+/// Parses a TSPLIB instance from the given reader like [`parse_tsp_reader`] with the given options.
+pub fn parse_tsp_reader_with_options<DistanceContainer: ParseFromTSPLib>(
+    mut reader: impl Read,
+    options: &ParseOptions,
+) -> Result<TSPSymInstance<DistanceContainer>, ParserError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    check_utf8(&bytes)?;
+    parse_instance(&bytes, &mut 0, options, &mut None)
+}
 
-    let data = parse_data_sections::<DistanceContainer>(
-        &file_content,
-        &mut index_in_map,
-        data_keyword,
-        &metadata,
-    );
+/// Parses all instances of the given TSPLIB content, which may consist of several concatenated
+/// instances, each terminated by an `EOF` line.
+///
+/// Blank lines between the instances are skipped. Anything between the end of the data sections
+/// of an instance and its `EOF` line is ignored, like in [`parse_tsp_instance`]. Returns
+/// [`ParserError::InvalidUtf8`] if the content is not valid UTF-8.
+pub fn parse_all_instances<DistanceContainer: ParseFromTSPLib>(
+    bytes: &[u8],
+) -> Result<Vec<TSPSymInstance<DistanceContainer>>, ParserError> {
+    parse_all_instances_with_options(bytes, &ParseOptions::default())
+}
+
+/// Parses all instances of the given TSPLIB content like [`parse_all_instances`] with the given
+/// options.
+pub fn parse_all_instances_with_options<DistanceContainer: ParseFromTSPLib>(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<Vec<TSPSymInstance<DistanceContainer>>, ParserError> {
+    check_utf8(bytes)?;
+    let mut instances = Vec::new();
+    let mut index_in_map = 0;
+
+    while bytes[index_in_map..]
+        .iter()
+        .any(|byte| !byte.is_ascii_whitespace())
+    {
+        skip_blank_lines(bytes, &mut index_in_map);
+        instances.push(parse_instance(
+            bytes,
+            &mut index_in_map,
+            options,
+            &mut None,
+        )?);
+        skip_to_after_eof(bytes, &mut index_in_map);
+    }
+
+    Ok(instances)
+}
+
+/// Parses a single instance starting at the given index, which is moved behind the data sections
+/// of the instance. If diagnostics are given, the parsing of the distances is recorded in them.
+fn parse_instance<DistanceContainer: ParseFromTSPLib>(
+    file_content: &[u8],
+    index_in_map: &mut usize,
+    options: &ParseOptions,
+    diagnostics: &mut Option<ParseDiagnostics>,
+) -> Result<TSPSymInstance<DistanceContainer>, ParserError> {
+    let (metadata, mut data_keyword) = parse_metadata(file_content, index_in_map)?;
+
+    let mut fixed_edges = Vec::new();
+    if let TSPDataKeyword::FIXED_EDGES_SECTION = data_keyword {
+        (fixed_edges, data_keyword) =
+            parse_fixed_edges_section(file_content, index_in_map, &metadata)?;
+    }
+
+    let (data, precedences, zero_distances) = match metadata.problem_type {
+        ProblemType::SOP => {
+            let (data, precedences) = parse_sop_data_section::<DistanceContainer>(
+                file_content,
+                index_in_map,
+                data_keyword,
+                &metadata,
+                options,
+                diagnostics,
+            )?;
+            (data, precedences, Vec::new())
+        }
+        _ => {
+            let (data, zero_distances) = parse_distances::<DistanceContainer>(
+                file_content,
+                index_in_map,
+                data_keyword,
+                &metadata,
+                options,
+                diagnostics,
+            )?;
+            (data, Vec::new(), zero_distances)
+        }
+    };
+    let subsequent = parse_subsequent_data_sections(file_content, index_in_map, &metadata)?;
+    fixed_edges.extend(subsequent.fixed_edges);
+    let display_coordinates = subsequent
+        .display_coordinates
+        .into_iter()
+        .map(|point| (point.x, point.y))
+        .collect();
+
+    Ok(TSPSymInstance::new(data, metadata)
+        .with_fixed_edges(fixed_edges)
+        .with_precedences(precedences)
+        .with_demands_and_depots(subsequent.demands, subsequent.depots)
+        .with_rounded_zero_distances(zero_distances)
+        .with_display_coordinates(display_coordinates))
+}
+
+/// Moves the index to the start of the first line which is not blank.
+fn skip_blank_lines(file_content: &[u8], index_in_map: &mut usize) {
+    while let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..])
+        && file_content[*index_in_map..*index_in_map + index_newline]
+            .iter()
+            .all(|byte| byte.is_ascii_whitespace())
+    {
+        *index_in_map += index_newline + 1;
+    }
+}
+
+/// Moves the index behind the `EOF` line terminating the current instance, unless the line just
+/// parsed already was the `EOF` line. Moves the index to the end if there is no `EOF` line.
+fn skip_to_after_eof(file_content: &[u8], index_in_map: &mut usize) {
+    if last_line_is_eof(file_content, *index_in_map) {
+        return;
+    }
+
+    while let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) {
+        let line = &file_content[*index_in_map..*index_in_map + index_newline];
+        *index_in_map += index_newline + 1;
+        if line.trim_ascii() == b"EOF" {
+            return;
+        }
+    }
+    *index_in_map = file_content.len();
+}
+
+/// Checks that the given bytes are valid UTF-8, which the parsing of the data sections relies on.
+///
+/// Only needed for bytes given by the caller, instance files are expected to be valid UTF-8.
+/// Returns [`ParserError::InvalidUtf8`] with the line of the first invalid byte otherwise.
+pub(crate) fn check_utf8(bytes: &[u8]) -> Result<(), ParserError> {
+    std::str::from_utf8(bytes)
+        .map(|_| ())
+        .map_err(|error| ParserError::InvalidUtf8 {
+            line: memchr_iter(b'\n', &bytes[..error.valid_up_to()]).count() + 1,
+        })
+}
 
-    Ok(TSPSymInstance::new(data, metadata))
+/// Returns whether the line ending right before the given index is the `EOF` line.
+pub(crate) fn last_line_is_eof(file_content: &[u8], index_in_map: usize) -> bool {
+    let parsed = &file_content[..index_in_map];
+    let last_line = parsed
+        .strip_suffix(b"\n")
+        .map(|parsed| &parsed[memrchr(b'\n', parsed).map_or(0, |index| index + 1)..]);
+    last_line.is_some_and(|line| line.trim_ascii() == b"EOF")
 }
 
 impl FileContent {
@@ -1,29 +1,32 @@
 use memchr::memchr;
 use memmap2::Mmap;
 use thiserror::Error;
+pub use tsp_core::instance::metadata_builder;
 use tsp_core::{
-    instance::InstanceMetadata,
+    instance::{
+        InstanceMetadata,
+        metadata_builder::{InstanceMetadataBuilder, InstanceMetadataBuilderError},
+    },
     tsp_lib_spec::{
         DisplayDataType, EdgeDataFormat, EdgeWeightFormat, EdgeWeightType, NodeCoordType,
         ProblemType, TSPDataKeyword,
     },
 };
 
-use crate::{
-    FileContent, ParserError,
-    metadata::metadata_builder::{InstanceMetadataBuilder, InstanceMetadataBuilderError},
-};
-
-pub mod metadata_builder;
+use crate::ParserError;
 
 #[derive(Error, Debug)]
 pub enum MetaDataParseError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("Reached EOF before any data section")]
+    EofBeforeDataSection,
     #[error("Invalid keyword in this line: {0}")]
     InvalidKeyword(String),
     #[error("Invalid (problem) TYPE value: {0}")]
     InvalidProblemType(String),
+    #[error("Missing (problem) TYPE specification")]
+    MissingProblemType,
     #[error("Invalid DIMENSION value: {0}")]
     InvalidDimension(String),
     #[error("Invalid CAPACITY value: {0}")]
@@ -48,23 +51,24 @@ pub enum MetaDataParseError {
 /// `TSPDataKeyword`, and a reference to the remaining lines iterator starting from the data section
 /// (the line after the first data keyword).
 pub fn parse_metadata(
-    file_content: &FileContent,
+    file_content: &[u8],
     index_in_map: &mut usize,
 ) -> Result<(InstanceMetadata, TSPDataKeyword), ParserError> {
     let mut metadata_builder = InstanceMetadataBuilder::new();
+    // Line numbers are 1-based, as they are reported to the user in errors
+    let mut line_number = 0;
     let data_keyword = loop {
         let Some(index_newline) = memchr(b'\n', &file_content[*index_in_map..]) else {
             return Err(
                 MetaDataParseError::InvalidInput("Unexpected end of file".to_string()).into(),
             );
         };
+        line_number += 1;
 
-        let line = unsafe {
-            std::str::from_utf8_unchecked(
-                &file_content[*index_in_map..*index_in_map + index_newline],
-            )
-        };
-        // println!("Parsing line: {}", line);
+        // The metadata section may contain arbitrary text (e.g. in the COMMENT), so we check the
+        // encoding here instead of assuming valid UTF-8 like in the data sections.
+        let line = std::str::from_utf8(&file_content[*index_in_map..*index_in_map + index_newline])
+            .map_err(|_| ParserError::InvalidUtf8 { line: line_number })?;
 
         // Move the index to the start of the next line (+1 for the newline character)
         *index_in_map += index_newline + 1;
@@ -80,7 +84,7 @@ pub fn parse_metadata(
             }
         }
     };
-    let metadata = metadata_builder.build()?;
+    let metadata = metadata_builder.build().map_err(MetaDataParseError::from)?;
 
     Ok((metadata, data_keyword))
 }
@@ -97,6 +101,11 @@ fn parse_specification_or_data_keyword(
             Ok(None)
         }
         // Cold path(s)
+        // EOF terminates the file, so anything after it is ignored. There has to be a data
+        // section before it though, as the metadata alone does not determine the distances.
+        (Some(k), None) if k.trim() == "EOF" => {
+            Err(MetaDataParseError::EofBeforeDataSection.into())
+        }
         (Some(k), None) => Ok(Some(parse_data_keyword(k.trim())?)),
         _ => Err(MetaDataParseError::InvalidKeyword(line.to_string()).into()),
     }
@@ -112,10 +121,14 @@ fn parse_specification(
             metadata_builder.name_mut(value.to_string());
             Ok(())
         }
-        "TYPE" => {
-            metadata_builder.problem_type_mut(parse_problem_type(value)?);
-            Ok(())
-        }
+        "TYPE" => match parse_problem_type(value)? {
+            // Tour files have no distances, so they are parsed separately
+            ProblemType::TOUR => Err(ParserError::TourFile),
+            problem_type => {
+                metadata_builder.problem_type_mut(problem_type);
+                Ok(())
+            }
+        },
         "COMMENT" => {
             metadata_builder.comment_mut(value.to_string());
             Ok(())
@@ -160,13 +173,13 @@ fn parse_specification(
     }
 }
 
-fn parse_data_keyword(input: &str) -> Result<TSPDataKeyword, ParserError> {
+pub(crate) fn parse_data_keyword(input: &str) -> Result<TSPDataKeyword, ParserError> {
     match input {
         "NODE_COORD_SECTION" => Ok(TSPDataKeyword::NODE_COORD_SECTION),
         "DEPOT_SECTION" => Ok(TSPDataKeyword::DEPOT_SECTION),
         "DEMAND_SECTION" => Ok(TSPDataKeyword::DEMAND_SECTION),
         "EDGE_DATA_SECTION" => Ok(TSPDataKeyword::EDGE_DATA_SECTION),
-        "FIXED_EDGES_SECTION" => todo!("Fixed edges sections are not supported yet"),
+        "FIXED_EDGES_SECTION" => Ok(TSPDataKeyword::FIXED_EDGES_SECTION),
         "DISPLAY_DATA_SECTION" => Ok(TSPDataKeyword::DISPLAY_DATA_SECTION),
         "TOUR_SECTION" => Ok(TSPDataKeyword::TOUR_SECTION),
         "EDGE_WEIGHT_SECTION" => Ok(TSPDataKeyword::EDGE_WEIGHT_SECTION),
@@ -174,12 +187,14 @@ fn parse_data_keyword(input: &str) -> Result<TSPDataKeyword, ParserError> {
     }
 }
 
-fn parse_problem_type(input: &str) -> Result<ProblemType, ParserError> {
-    match input {
+pub(crate) fn parse_problem_type(input: &str) -> Result<ProblemType, ParserError> {
+    // Some TSPLIB instances annotate the type, e.g. `TYPE: TSP (M.~Hofmeister)` in si175.tsp
+    match input.split_whitespace().next().unwrap_or_default() {
         "TSP" => Ok(ProblemType::TSP),
         "ATSP" => Ok(ProblemType::ATSP),
         "SOP" => Ok(ProblemType::SOP),
         "HCP" => Ok(ProblemType::HCP),
+        "CVRP" => Ok(ProblemType::CVRP),
         "TOUR" => Ok(ProblemType::TOUR),
         _ => Err(MetaDataParseError::InvalidProblemType(input.to_string()).into()),
     }
@@ -0,0 +1,65 @@
+use tsp_core::instance::distance::Distance;
+
+use crate::data_section::Point2D;
+
+/// Options for parsing TSPLIB instances, see
+/// [`parse_tsp_instance_with_options`](crate::parse_tsp_instance_with_options).
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Factor all distances are multiplied by before they are rounded to integers, defaults to 1.
+    ///
+    /// Scaling preserves sub-unit precision of coordinate based distances, e.g. with a scale of
+    /// 100, an EUC_2D distance of 1.414 is stored as 141 instead of 1. For edge weight types
+    /// defined via coordinates, the coordinates are scaled (for GEO, the radius of the earth),
+    /// which is the same as scaling the exact distance before rounding. Explicit edge weights are
+    /// simply multiplied by the factor.
+    ///
+    /// The scaled distances have to stay below [`Distance::MAX`], which is considerably smaller
+    /// than `i32::MAX` because of the fixed point arithmetic of the solvers. Parsing fails with
+    /// [`DataSectionParseError::ScaledDistanceOverflow`] if a scaled distance does not fit into
+    /// an `i32`, both for explicit edge weights and distances computed from coordinates. Scaled
+    /// distances between [`Distance::MAX`] and `i32::MAX` are not detected by the parser. Thus,
+    /// the scale should be chosen such that the largest distance of the instance times the scale
+    /// is below [`Distance::MAX`].
+    ///
+    /// [`Distance::MAX`]: tsp_core::instance::distance::Distance::MAX
+    /// [`DataSectionParseError::ScaledDistanceOverflow`]: crate::data_section::DataSectionParseError::ScaledDistanceOverflow
+    pub scale: u32,
+    /// Whether to check the computed distances for suspicious values, defaults to false.
+    ///
+    /// Currently, this collects the pairs of nodes with distinct coordinates whose distance has
+    /// been rounded to 0, see
+    /// [`TSPSymInstance::rounded_zero_distances`](tsp_core::instance::TSPSymInstance::rounded_zero_distances).
+    /// This requires computing all distances a second time. Additionally, the entries above the
+    /// diagonal of a FULL_MATRIX EDGE_WEIGHT_SECTION have to match the ones below the diagonal,
+    /// which are otherwise ignored. For GEO instances, the degrees of the coordinates have to be
+    /// in range, and a warning is logged if minutes of 60 or more indicate that the coordinates
+    /// are not in the TSPLIB format `DDD.MM`, see
+    /// [`geo_minutes_valid`](crate::data_section::distance_function::geo_minutes_valid).
+    pub strict: bool,
+    /// Whether to collect [`ParseDiagnostics`](crate::ParseDiagnostics) like the time spent on
+    /// computing the distances, defaults to false.
+    ///
+    /// The diagnostics are only returned by
+    /// [`parse_tsp_instance_with_diagnostics`](crate::parse_tsp_instance_with_diagnostics).
+    pub diagnostics: bool,
+    /// Distance function for instances with EDGE_WEIGHT_TYPE SPECIAL, defaults to `None`.
+    ///
+    /// The distances of such instances are not defined by the file, but by a function of the
+    /// coordinates of the 2D NODE_COORD_SECTION. Parsing them fails with
+    /// [`ParserError::SpecialFunctionRequired`](crate::ParserError::SpecialFunctionRequired)
+    /// unless the function is given. Closures which do not capture anything can be given as well.
+    /// The distances returned by the function are multiplied by [`scale`](Self::scale).
+    pub special_distance_function: Option<fn(&Point2D, &Point2D) -> Distance>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            strict: false,
+            diagnostics: false,
+            special_distance_function: None,
+        }
+    }
+}
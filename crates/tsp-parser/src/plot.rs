@@ -0,0 +1,80 @@
+//! Plotting tours to PNG files, e.g. for presentations. Only available with the `plotters`
+//! feature.
+
+use std::path::Path;
+
+use plotters::prelude::*;
+use thiserror::Error;
+use tsp_core::instance::{UnTour, tour_order::MalformedTourError};
+
+use crate::{data_section::Point2D, statistics::coordinate_stats};
+
+/// Width and height of the plots in pixels.
+const PLOT_SIZE: (u32, u32) = (1024, 1024);
+
+/// Errors that can occur when plotting a tour with [`plot_tour_png`].
+#[derive(Error, Debug)]
+pub enum PlotError {
+    /// The edges of the tour do not form a tour through the nodes with the given coordinates.
+    #[error(transparent)]
+    MalformedTour(#[from] MalformedTourError),
+    /// The plot could not be drawn or written to the file.
+    #[error("Failed to draw the plot: {0}")]
+    Drawing(String),
+}
+
+/// Plots the tour to a PNG file at the given path, overwriting existing files.
+///
+/// Node `i` is drawn at `coordinates[i]`, and the axes are scaled to the bounding box of the
+/// coordinates. The cost of the tour is shown in the title. Returns an error if the edges do not
+/// form a tour through all nodes, see [`UnTour::to_order`].
+pub fn plot_tour_png(
+    coordinates: &[Point2D],
+    tour: &UnTour,
+    path: impl AsRef<Path>,
+) -> Result<(), PlotError> {
+    let order = tour.to_order(coordinates.len())?;
+    let drawing_error = |error: &dyn std::error::Error| PlotError::Drawing(error.to_string());
+
+    let (x_range, y_range) = match coordinate_stats(coordinates) {
+        Some(stats) => (
+            padded_range(stats.min.x, stats.max.x),
+            padded_range(stats.min.y, stats.max.y),
+        ),
+        None => (0.0..1.0, 0.0..1.0),
+    };
+
+    let root = BitMapBackend::new(path.as_ref(), PLOT_SIZE).into_drawing_area();
+    root.fill(&WHITE).map_err(|error| drawing_error(&error))?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(format!("Tour cost {}", tour.cost.0), ("sans-serif", 30))
+        .margin(20)
+        .build_cartesian_2d(x_range, y_range)
+        .map_err(|error| drawing_error(&error))?;
+
+    // Return to the first node to close the tour
+    let tour_points = order
+        .nodes()
+        .iter()
+        .chain(order.nodes().first())
+        .map(|node| (coordinates[node.0].x, coordinates[node.0].y));
+    chart
+        .draw_series(LineSeries::new(tour_points, &BLUE))
+        .map_err(|error| drawing_error(&error))?;
+    chart
+        .draw_series(
+            coordinates
+                .iter()
+                .map(|point| Circle::new((point.x, point.y), 3, BLACK.filled())),
+        )
+        .map_err(|error| drawing_error(&error))?;
+
+    root.present().map_err(|error| drawing_error(&error))
+}
+
+/// Returns the range from min to max, padded by 5% on each side such that the nodes on the
+/// boundary are not cut off. Degenerate ranges, e.g. of a single node, are padded by 1.
+fn padded_range(min: f64, max: f64) -> std::ops::Range<f64> {
+    let padding = if max > min { (max - min) * 0.05 } else { 1.0 };
+    min - padding..max + padding
+}
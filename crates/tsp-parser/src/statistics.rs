@@ -0,0 +1,117 @@
+//! Statistics characterizing the geometry of coordinate instances, e.g. whether the nodes are
+//! spread uniformly or clustered, which helps choosing a heuristic.
+
+use crate::data_section::{GeoPoint, Point2D};
+
+/// Statistics of a set of 2D points, see [`coordinate_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateStats {
+    /// The corner of the bounding box with the smallest coordinates.
+    pub min: Point2D,
+    /// The corner of the bounding box with the largest coordinates.
+    pub max: Point2D,
+    /// The mean of the points.
+    pub centroid: Point2D,
+    /// The mean over all points of the Euclidean distance to the closest other point, or 0 for a
+    /// single point.
+    ///
+    /// Clustered instances have a small average nearest neighbor distance compared to uniformly
+    /// distributed points in the same bounding box.
+    pub average_nearest_neighbor_distance: f64,
+}
+
+/// Statistics of a set of geographical points, see [`geo_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoStats {
+    /// Difference between the largest and smallest latitude in radians.
+    pub latitude_span: f64,
+    /// Difference between the largest and smallest longitude in radians.
+    ///
+    /// Points on both sides of the antimeridian are not treated specially, so their span is
+    /// close to `2 * PI`.
+    pub longitude_span: f64,
+}
+
+/// Computes the bounding box, the centroid and the average nearest neighbor distance of the
+/// points. Returns `None` if there are no points.
+///
+/// The nearest neighbors are found by sweeping over the points sorted by their x coordinate,
+/// which stops once the difference in x exceeds the closest distance found so far. This is fast
+/// for the point sets of typical instances, but takes `O(n^2)` in the worst case.
+pub fn coordinate_stats(points: &[Point2D]) -> Option<CoordinateStats> {
+    let first = *points.first()?;
+    let (mut min, mut max) = (first, first);
+    let (mut sum_x, mut sum_y) = (0.0, 0.0);
+    for point in points {
+        min.x = min.x.min(point.x);
+        min.y = min.y.min(point.y);
+        max.x = max.x.max(point.x);
+        max.y = max.y.max(point.y);
+        sum_x += point.x;
+        sum_y += point.y;
+    }
+    let number_of_points = points.len() as f64;
+    let centroid = Point2D {
+        x: sum_x / number_of_points,
+        y: sum_y / number_of_points,
+    };
+
+    let average_nearest_neighbor_distance = if points.len() < 2 {
+        0.0
+    } else {
+        nearest_neighbor_distances(points).iter().sum::<f64>() / number_of_points
+    };
+
+    Some(CoordinateStats {
+        min,
+        max,
+        centroid,
+        average_nearest_neighbor_distance,
+    })
+}
+
+/// Computes the latitude and longitude spans of the points. Returns `None` if there are no
+/// points.
+pub fn geo_stats(points: &[GeoPoint]) -> Option<GeoStats> {
+    let first = *points.first()?;
+    let (mut min, mut max) = (first, first);
+    for point in points {
+        min.latitude = min.latitude.min(point.latitude);
+        min.longitude = min.longitude.min(point.longitude);
+        max.latitude = max.latitude.max(point.latitude);
+        max.longitude = max.longitude.max(point.longitude);
+    }
+
+    Some(GeoStats {
+        latitude_span: max.latitude - min.latitude,
+        longitude_span: max.longitude - min.longitude,
+    })
+}
+
+/// Returns the Euclidean distance of each point to its closest other point, in the order of the
+/// points sorted by x coordinate. Requires at least two points.
+fn nearest_neighbor_distances(points: &[Point2D]) -> Vec<f64> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|point_a, point_b| point_a.x.total_cmp(&point_b.x));
+
+    (0..sorted.len())
+        .map(|index| {
+            let point = sorted[index];
+            let mut closest = f64::INFINITY;
+            // Points further away in x than the closest point so far cannot be closer
+            for other in &sorted[index + 1..] {
+                if other.x - point.x >= closest {
+                    break;
+                }
+                closest = closest.min((point.x - other.x).hypot(point.y - other.y));
+            }
+            for other in sorted[..index].iter().rev() {
+                if point.x - other.x >= closest {
+                    break;
+                }
+                closest = closest.min((point.x - other.x).hypot(point.y - other.y));
+            }
+            closest
+        })
+        .collect()
+}
@@ -0,0 +1,120 @@
+//! Parsing of TSPLIB tour files (`TYPE: TOUR`), e.g. the `.opt.tour` files containing the optimal
+//! tours of instances.
+
+use std::path::Path;
+
+use tsp_core::{
+    instance::node::Node,
+    tsp_lib_spec::{ProblemType, TSPDataKeyword},
+};
+
+use crate::{
+    FileContent, ParserError,
+    data_section::DataSectionParseError,
+    metadata::{MetaDataParseError, parse_data_keyword, parse_problem_type},
+};
+
+/// Parses the tour file at the given path and returns the nodes of its `TOUR_SECTION` in the
+/// order they are visited, see [`parse_tour`].
+pub fn parse_tsp_tour(tour_path: impl AsRef<Path>) -> Result<Vec<Node>, ParserError> {
+    let file_content = FileContent::new(tour_path)?;
+    parse_tour(&file_content)
+}
+
+/// Parses the content of a tour file and returns the nodes of its `TOUR_SECTION` in the order
+/// they are visited, converting the 1-based ids to nodes.
+///
+/// Tour files have no `EDGE_WEIGHT_TYPE`, so they cannot be parsed with
+/// [`parse_tsp_instance`](crate::parse_tsp_instance), which returns [`ParserError::TourFile`] for
+/// them instead. The `TYPE` has to be given before the section and has to be `TOUR`. The section
+/// ends with `-1`, `EOF` or the end of the file. The tour has to visit each node from 1 to the
+/// `DIMENSION` exactly once, or each node from 1 to the number of ids if there is no
+/// `DIMENSION`.
+pub fn parse_tour(file_content: &[u8]) -> Result<Vec<Node>, ParserError> {
+    let mut lines = file_content.split(|&byte| byte == b'\n').enumerate();
+    let mut dimension = None;
+    let mut has_problem_type = false;
+
+    // Parse the specification part up to the TOUR_SECTION keyword
+    loop {
+        let Some((line_index, line)) = lines.next() else {
+            return Err(MetaDataParseError::EofBeforeDataSection.into());
+        };
+        let line = std::str::from_utf8(line).map_err(|_| ParserError::InvalidUtf8 {
+            line: line_index + 1,
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, ':');
+        match (parts.next().map(str::trim), parts.next().map(str::trim)) {
+            (Some("TYPE"), Some(value)) => {
+                if !matches!(parse_problem_type(value)?, ProblemType::TOUR) {
+                    return Err(MetaDataParseError::InvalidProblemType(value.to_string()).into());
+                }
+                has_problem_type = true;
+            }
+            (Some("DIMENSION"), Some(value)) => {
+                dimension = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| MetaDataParseError::InvalidDimension(value.to_string()))?,
+                );
+            }
+            // The remaining specifications like NAME and COMMENT do not affect the tour
+            (Some(_), Some(_)) => {}
+            (Some("EOF"), None) => return Err(MetaDataParseError::EofBeforeDataSection.into()),
+            (Some(keyword), None) => match parse_data_keyword(keyword)? {
+                TSPDataKeyword::TOUR_SECTION if has_problem_type => break,
+                TSPDataKeyword::TOUR_SECTION => {
+                    return Err(MetaDataParseError::MissingProblemType.into());
+                }
+                data_keyword => {
+                    return Err(DataSectionParseError::UnsupportedDataSection(data_keyword).into());
+                }
+            },
+            _ => return Err(MetaDataParseError::InvalidKeyword(line.to_string()).into()),
+        }
+    }
+
+    let mut ids = Vec::with_capacity(dimension.unwrap_or_default());
+    'section: for (line_index, line) in lines {
+        let line = std::str::from_utf8(line).map_err(|_| ParserError::InvalidUtf8 {
+            line: line_index + 1,
+        })?;
+        for id in line.split_ascii_whitespace() {
+            if id == "-1" || id == "EOF" {
+                break 'section;
+            }
+            ids.push(
+                id.parse::<usize>()
+                    .map_err(|_| DataSectionParseError::InvalidNodeIndex(line.to_string()))?,
+            );
+        }
+    }
+
+    let dimension = dimension.unwrap_or(ids.len());
+    let mut node_seen = vec![false; dimension];
+    let tour = ids
+        .into_iter()
+        .map(|id| {
+            if id == 0 || id > dimension {
+                return Err(DataSectionParseError::NodeIndexOutOfRange {
+                    index: id,
+                    dimension,
+                });
+            }
+            let node = Node::from_tsplib(id);
+            if std::mem::replace(&mut node_seen[node.0], true) {
+                return Err(DataSectionParseError::DuplicateNodeIndex(id));
+            }
+            Ok(node)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(missing) = node_seen.iter().position(|&seen| !seen) {
+        return Err(DataSectionParseError::MissingNodeIndex(Node(missing).to_tsplib()).into());
+    }
+
+    Ok(tour)
+}
@@ -0,0 +1,77 @@
+//! Writing instances to TSPLIB files and tours to CSV files.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use tsp_core::instance::{TSPSymInstance, UnTour, node::Node};
+
+use crate::distance_container::WriteToTSPLib;
+
+/// Writes the instance to a TSPLIB file at the given path, overwriting existing files.
+///
+/// The distances are written explicitly (EDGE_WEIGHT_TYPE EXPLICIT) in the LOWER_DIAG_ROW format,
+/// regardless of how the instance was created. That is, parsing the written file yields the same
+/// distance matrix. Besides the name, type, comment and dimension of the instance, its fixed
+/// edges are written as well. Other metadata (e.g. the original EDGE_WEIGHT_TYPE) is not
+/// preserved.
+pub fn write_instance<DistanceContainer: WriteToTSPLib>(
+    instance: &TSPSymInstance<DistanceContainer>,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let metadata = instance.metadata();
+
+    writeln!(writer, "NAME: {}", metadata.name)?;
+    writeln!(writer, "TYPE: {:?}", metadata.problem_type)?;
+    if let Some(comment) = &metadata.comment {
+        writeln!(writer, "COMMENT: {comment}")?;
+    }
+    writeln!(writer, "DIMENSION: {}", metadata.dimension)?;
+    writeln!(writer, "EDGE_WEIGHT_TYPE: EXPLICIT")?;
+    writeln!(writer, "EDGE_WEIGHT_FORMAT: LOWER_DIAG_ROW")?;
+
+    if !instance.fixed_edges().is_empty() {
+        writeln!(writer, "FIXED_EDGES_SECTION")?;
+        for edge in instance.fixed_edges() {
+            writeln!(writer, "{} {}", edge.from.to_tsplib(), edge.to.to_tsplib())?;
+        }
+        writeln!(writer, "-1")?;
+    }
+
+    writeln!(writer, "EDGE_WEIGHT_SECTION")?;
+    let distances = instance.distances();
+    for row in 0..metadata.dimension {
+        for column in 0..=row {
+            if column > 0 {
+                write!(writer, " ")?;
+            }
+            write!(writer, "{}", distances.distance(Node(row), Node(column)).0)?;
+        }
+        writeln!(writer)?;
+    }
+    writeln!(writer, "EOF")?;
+
+    writer.flush()
+}
+
+/// Writes the tour to a CSV file at the given path, overwriting existing files.
+///
+/// The file contains the 0-indexed ids of the nodes in the order they are visited, one per line,
+/// starting at node 0 (see [`UnTour::to_order`]). This is meant for easy ingestion by external
+/// tools like plotting scripts. Returns an error of kind [`io::ErrorKind::InvalidInput`] if the
+/// edges do not form a tour through the nodes `0..dimension`.
+pub fn write_tour_csv(tour: &UnTour, dimension: usize, path: impl AsRef<Path>) -> io::Result<()> {
+    let order = tour
+        .to_order(dimension)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    for node in order.nodes() {
+        writeln!(writer, "{}", node.0)?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,93 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::MatrixSym, node::Node};
+use tsp_parser::parse_tsp_instance;
+
+#[test]
+fn test_scientific_notation_coordinates_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/scientific_notation.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Nodes are at (0, 0), (350, 0) and (0, 100)
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(350));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(100));
+    assert_eq!(distances.get_data(Node(1), Node(2)), Distance(364));
+}
+
+#[test]
+fn test_unordered_node_indices_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/unordered_node_indices.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Nodes are at (0, 0), (300, 0), (0, 100) and (300, 100), but listed out of order
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(100));
+    assert_eq!(distances.get_data(Node(1), Node(3)), Distance(100));
+    assert_eq!(distances.get_data(Node(2), Node(3)), Distance(300));
+}
+
+#[test]
+fn test_tab_separated_coordinates_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/tab_separated.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Nodes are at (0, 0), (300, 0), (0, 100) and (300, 100), with columns separated by tabs and
+    // runs of spaces, and with leading and trailing whitespace
+    assert_eq!(instance.metadata().dimension, 4);
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(100));
+    assert_eq!(distances.get_data(Node(1), Node(3)), Distance(100));
+    assert_eq!(distances.get_data(Node(2), Node(3)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(3)), Distance(316));
+}
+
+#[test]
+fn test_negative_integer_coordinates_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/negative_coordinates.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Nodes are at (0, 0), (-300, 0), (0, -100) and (-300, -100). The first line is sampled as
+    // integer data, so the negative coordinates have to be parsed as integers as well
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(100));
+    assert_eq!(distances.get_data(Node(1), Node(3)), Distance(100));
+    assert_eq!(distances.get_data(Node(2), Node(3)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(3)), Distance(316));
+}
+
+#[test]
+fn test_single_node_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/single_node.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    assert_eq!(distances.data(), &vec![Distance(0)]);
+}
+
+#[test]
+fn test_3d_coordinates_with_whitespace_after_eof_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/euc_3d.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Nodes are at (0, 0, 0), (3, 0, 0), (3, 4, 0) and (3, 4, 12)
+    assert_eq!(instance.metadata().dimension, 4);
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(3));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(5));
+    assert_eq!(distances.get_data(Node(0), Node(3)), Distance(13));
+    assert_eq!(distances.get_data(Node(1), Node(3)), Distance(13));
+    assert_eq!(distances.get_data(Node(2), Node(3)), Distance(12));
+}
+
+#[test]
+fn test_3d_coordinates_without_trailing_newline_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/euc_3d_no_newline.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // The last node is at (3, 4, 0), but its line is not terminated by a newline
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(5));
+    assert_eq!(distances.get_data(Node(1), Node(2)), Distance(4));
+}
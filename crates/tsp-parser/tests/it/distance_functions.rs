@@ -0,0 +1,131 @@
+use tsp_core::{
+    instance::{TSPSymInstance, distance::Distance, matrix::MatrixSym, node::Node},
+    tsp_lib_spec::EdgeWeightType,
+};
+use tsp_parser::{
+    data_section::{
+        Point2D, Point3D,
+        distance_function::{distance_function_3d_for, distance_function_for},
+    },
+    parse_tsp_instance,
+};
+
+/// Reads the coordinates of the NODE_COORD_SECTION of a (well-formed) 2D instance.
+fn read_2d_coordinates(path: &str) -> Vec<Point2D> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .skip_while(|line| line.trim() != "NODE_COORD_SECTION")
+        .skip(1)
+        .take_while(|line| line.trim() != "EOF")
+        .map(|line| {
+            let parts: Vec<f64> = line
+                .split_whitespace()
+                .map(|part| part.parse().unwrap())
+                .collect();
+            Point2D {
+                x: parts[1],
+                y: parts[2],
+            }
+        })
+        .collect()
+}
+
+fn check_consistent_with_parsed_matrix(path: &str, kind: EdgeWeightType) {
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+    let distances = instance.distance_matrix();
+    let distance_function = distance_function_for(kind).unwrap();
+
+    let points = read_2d_coordinates(path);
+    for (from, point_from) in points.iter().enumerate() {
+        for (to, point_to) in points.iter().enumerate().take(from) {
+            assert_eq!(
+                distance_function(point_from, point_to),
+                distances.get_data(Node(from), Node(to)),
+                "Distances between nodes {from} and {to} differ"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_distance_function_for_euc_2d_short() {
+    check_consistent_with_parsed_matrix(
+        "../../instances/tsplib_symmetric/eil51.tsp",
+        EdgeWeightType::EUC_2D,
+    );
+}
+
+#[test]
+fn test_distance_function_for_att_short() {
+    check_consistent_with_parsed_matrix(
+        "../../instances/tsplib_symmetric/att48.tsp",
+        EdgeWeightType::ATT,
+    );
+}
+
+#[test]
+fn test_distance_function_for_geo_short() {
+    check_consistent_with_parsed_matrix(
+        "../../instances/tsplib_symmetric/burma14.tsp",
+        EdgeWeightType::GEO,
+    );
+}
+
+#[test]
+fn test_distance_function_3d_for_short() {
+    let origin = Point3D {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let point = Point3D {
+        x: 1.0,
+        y: 2.0,
+        z: 2.0,
+    };
+    let euclidean = distance_function_3d_for(EdgeWeightType::EUC_3D).unwrap();
+    let max = distance_function_3d_for(EdgeWeightType::MAX_3D).unwrap();
+    let manhattan = distance_function_3d_for(EdgeWeightType::MAN_3D).unwrap();
+    assert_eq!(euclidean(&origin, &point), Distance(3));
+    assert_eq!(max(&origin, &point), Distance(2));
+    assert_eq!(manhattan(&origin, &point), Distance(5));
+}
+
+#[test]
+fn test_distance_function_for_other_types_short() {
+    assert!(distance_function_for(EdgeWeightType::EXPLICIT).is_none());
+    assert!(distance_function_for(EdgeWeightType::EUC_3D).is_none());
+    assert!(distance_function_3d_for(EdgeWeightType::EUC_2D).is_none());
+    assert!(distance_function_3d_for(EdgeWeightType::GEO).is_none());
+}
+
+#[test]
+fn test_coordinate_dimensions_match_distance_functions_short() {
+    for kind in [
+        EdgeWeightType::EXPLICIT,
+        EdgeWeightType::EUC_2D,
+        EdgeWeightType::EUC_3D,
+        EdgeWeightType::MAX_2D,
+        EdgeWeightType::MAX_3D,
+        EdgeWeightType::MAN_2D,
+        EdgeWeightType::MAN_3D,
+        EdgeWeightType::CEIL_2D,
+        EdgeWeightType::GEO,
+        EdgeWeightType::ATT,
+    ] {
+        let expected = if distance_function_for(kind.clone()).is_some() {
+            Some(2)
+        } else if distance_function_3d_for(kind.clone()).is_some() {
+            Some(3)
+        } else {
+            None
+        };
+        assert_eq!(kind.coordinate_dimensions(), expected, "{kind:?}");
+        assert_eq!(kind.is_explicit(), expected.is_none(), "{kind:?}");
+    }
+
+    assert_eq!(EdgeWeightType::XRAY1.coordinate_dimensions(), Some(3));
+    assert_eq!(EdgeWeightType::SPECIAL.coordinate_dimensions(), None);
+    assert!(!EdgeWeightType::SPECIAL.is_explicit());
+}
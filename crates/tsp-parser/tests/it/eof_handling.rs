@@ -0,0 +1,66 @@
+use tsp_core::{
+    instance::{TSPSymInstance, distance::Distance, matrix::MatrixSym, node::Node},
+    tsp_lib_spec::TSPDataKeyword,
+};
+use tsp_parser::{
+    ParserError, data_section::DataSectionParseError, metadata::MetaDataParseError,
+    parse_tsp_instance,
+};
+
+#[test]
+fn test_junk_after_eof_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/junk_after_eof.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    // Only the three nodes before EOF belong to the instance
+    assert_eq!(instance.metadata().dimension, 3);
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(300));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(400));
+    assert_eq!(distances.get_data(Node(1), Node(2)), Distance(500));
+}
+
+#[test]
+fn test_junk_after_eof_explicit_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/junk_after_eof_explicit.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(3));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(5));
+    assert_eq!(distances.get_data(Node(1), Node(2)), Distance(4));
+}
+
+#[test]
+fn test_eof_before_data_section_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/eof_before_data_section.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::MetaDataParsing(
+                MetaDataParseError::EofBeforeDataSection
+            ))
+        ),
+        "Expected EOF before any data section, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_eof_after_fixed_edges_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/fixed_edges_eof.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::MissingDataSection(TSPDataKeyword::FIXED_EDGES_SECTION)
+            ))
+        ),
+        "Expected missing data section after the fixed edges, got {:?}",
+        result.map(|_| ())
+    );
+}
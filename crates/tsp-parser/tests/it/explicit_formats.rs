@@ -0,0 +1,323 @@
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    edge::UnEdge,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_parser::{
+    ParseOptions, ParserError,
+    data_section::{DataSectionParseError, stream_edge_weights},
+    metadata::parse_metadata,
+    parse_tsp_instance, parse_tsp_instance_with_options, parse_tsp_reader,
+};
+
+/// The distance matrix encoded by all explicit_*.tsp test instances.
+#[rustfmt::skip]
+const EXPECTED_DISTANCES: [[i32; 4]; 4] = [
+    [0, 3, 5, 9],
+    [3, 0, 4, 7],
+    [5, 4, 0, 2],
+    [9, 7, 2, 0],
+];
+
+fn check_explicit_instance(format: &str) {
+    let path = format!("tests/test_assets/instances/explicit_{format}.tsp");
+    let symmetric: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(&path).unwrap();
+    let full: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(&path).unwrap();
+
+    for (from, row) in EXPECTED_DISTANCES.iter().enumerate() {
+        for (to, &distance) in row.iter().enumerate() {
+            let (from, to) = (Node(from), Node(to));
+            assert_eq!(
+                symmetric.distance_matrix().get_data(from, to),
+                Distance(distance),
+                "{format}: wrong distance between {from:?} and {to:?} in symmetric matrix"
+            );
+            assert_eq!(
+                full.distance_matrix().get_data(from, to),
+                Distance(distance),
+                "{format}: wrong distance between {from:?} and {to:?} in full matrix"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_explicit_lower_diag_row_short() {
+    check_explicit_instance("lower_diag_row");
+}
+
+#[test]
+fn test_explicit_lower_row_short() {
+    check_explicit_instance("lower_row");
+}
+
+#[test]
+fn test_explicit_upper_row_short() {
+    check_explicit_instance("upper_row");
+}
+
+#[test]
+fn test_explicit_upper_diag_row_short() {
+    check_explicit_instance("upper_diag_row");
+}
+
+#[test]
+fn test_explicit_full_matrix_short() {
+    check_explicit_instance("full_matrix");
+
+    let full_matrix: TSPSymInstance<Matrix<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/explicit_full_matrix.tsp").unwrap();
+    let lower_diag_row: TSPSymInstance<Matrix<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/explicit_lower_diag_row.tsp").unwrap();
+    assert_eq!(
+        full_matrix.distance_matrix().data(),
+        lower_diag_row.distance_matrix().data()
+    );
+}
+
+#[test]
+fn test_explicit_full_matrix_asymmetric_short() {
+    let path = "tests/test_assets/instances/explicit_full_matrix_asymmetric.tsp";
+
+    // Only the lower triangle is used by default
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+    assert_eq!(
+        instance.distance_matrix().get_data(Node(1), Node(2)),
+        Distance(4)
+    );
+
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::AsymmetricEdgeWeights {
+                    from: 2,
+                    to: 3,
+                    distance: 8,
+                    reverse_distance: 4,
+                }
+            ))
+        ),
+        "Expected asymmetric edge weights, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_explicit_missing_edge_weights_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/explicit_missing_edge_weights.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::EdgeWeightCount {
+                    expected: 6,
+                    found: 5
+                }
+            ))
+        ),
+        "Expected a missing edge weight, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_explicit_upper_col_short() {
+    check_explicit_instance("upper_col");
+}
+
+#[test]
+fn test_explicit_lower_col_short() {
+    check_explicit_instance("lower_col");
+}
+
+#[test]
+fn test_explicit_upper_diag_col_short() {
+    check_explicit_instance("upper_diag_col");
+}
+
+#[test]
+fn test_explicit_lower_diag_col_short() {
+    check_explicit_instance("lower_diag_col");
+}
+
+#[test]
+fn test_explicit_col_same_as_row_short() {
+    for (col_format, row_format) in [
+        ("upper_col", "upper_row"),
+        ("lower_col", "lower_row"),
+        ("upper_diag_col", "upper_diag_row"),
+        ("lower_diag_col", "lower_diag_row"),
+    ] {
+        let col: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(format!(
+            "tests/test_assets/instances/explicit_{col_format}.tsp"
+        ))
+        .unwrap();
+        let row: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(format!(
+            "tests/test_assets/instances/explicit_{row_format}.tsp"
+        ))
+        .unwrap();
+        assert_eq!(col.raw_distances(), row.raw_distances(), "{col_format}");
+    }
+}
+
+#[test]
+fn test_explicit_unsupported_format_short() {
+    let bytes = b"NAME : explicit_function\nTYPE : TSP\nDIMENSION : 2\n\
+        EDGE_WEIGHT_TYPE : EXPLICIT\nEDGE_WEIGHT_FORMAT : FUNCTION\nEDGE_WEIGHT_SECTION\n1\nEOF\n";
+    let result = parse_tsp_reader::<MatrixSym<Distance>>(&bytes[..]);
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::Unsupported {
+                feature: "EDGE_WEIGHT_FORMAT: FUNCTION"
+            })
+        ),
+        "Expected an unsupported format, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_unsupported_edge_weight_type_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>("tests/test_assets/instances/xray.tsp");
+    assert!(
+        matches!(result, Err(ParserError::Unsupported { .. })),
+        "Expected an unsupported edge weight type, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_explicit_display_data_short() {
+    check_explicit_instance("display_data");
+
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/explicit_display_data.tsp").unwrap();
+    assert_eq!(
+        instance.display_coordinates(),
+        &[(0.0, 0.0), (3.0, 0.0), (3.5, 4.0), (5.0, 5.5)]
+    );
+}
+
+#[test]
+fn test_explicit_node_coords_after_edge_weights_short() {
+    check_explicit_instance("node_coords");
+
+    // The sections following the node coordinates are parsed as well
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/explicit_node_coords.tsp").unwrap();
+    assert_eq!(
+        instance.display_coordinates(),
+        &[(0.0, 0.0), (3.0, 0.0), (4.0, 3.0), (6.0, 4.0)]
+    );
+    assert_eq!(instance.fixed_edges(), &[UnEdge::new(Node(0), Node(1))]);
+}
+
+#[test]
+fn test_tsplib_display_data() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/bayg29.tsp").unwrap();
+    let display_coordinates = instance.display_coordinates();
+    assert_eq!(display_coordinates.len(), 29);
+    assert_eq!(display_coordinates[0], (1150.0, 1760.0));
+    assert_eq!(display_coordinates[28], (360.0, 1980.0));
+
+    // The distances of instances with node coordinates are not affected by display coordinates
+    let berlin52: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/berlin52.tsp").unwrap();
+    assert!(berlin52.display_coordinates().is_empty());
+}
+
+/// Streams the edge weights of the instance at the given path.
+fn stream_instance(path: &str) -> Vec<Result<(Node, Node, Distance), DataSectionParseError>> {
+    let bytes = std::fs::read(path).unwrap();
+    let mut index_in_map = 0;
+    let (metadata, _) = parse_metadata(&bytes, &mut index_in_map).unwrap();
+    stream_edge_weights(&bytes[index_in_map..], &metadata)
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn test_stream_edge_weights_short() {
+    for format in [
+        "lower_diag_row",
+        "lower_row",
+        "upper_row",
+        "upper_diag_row",
+        "full_matrix",
+        "display_data",
+    ] {
+        let entries = stream_instance(&format!(
+            "tests/test_assets/instances/explicit_{format}.tsp"
+        ));
+        assert!(!entries.is_empty());
+        for entry in entries {
+            let (from, to, distance) = entry.unwrap();
+            assert_eq!(
+                distance,
+                Distance(EXPECTED_DISTANCES[from.0][to.0]),
+                "{format}: wrong distance between {from:?} and {to:?}"
+            );
+        }
+    }
+
+    // The entries are yielded in the order of the format
+    let entries = stream_instance("tests/test_assets/instances/explicit_upper_row.tsp");
+    let edges: Vec<(usize, usize)> = entries
+        .into_iter()
+        .map(|entry| entry.map(|(from, to, _)| (from.0, to.0)).unwrap())
+        .collect();
+    assert_eq!(edges, [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+}
+
+#[test]
+fn test_stream_edge_weights_missing_entry_short() {
+    let entries = stream_instance("tests/test_assets/instances/explicit_missing_edge_weights.tsp");
+    assert_eq!(entries.len(), 6);
+    assert!(entries[..5].iter().all(Result::is_ok));
+    assert!(matches!(
+        entries[5],
+        Err(DataSectionParseError::EdgeWeightCount {
+            expected: 6,
+            found: 5
+        })
+    ));
+}
+
+#[test]
+fn test_stream_edge_weights_matches_parser() {
+    let path = "../../instances/tsplib_symmetric/bayg29.tsp";
+    let instance: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(path).unwrap();
+    let entries = stream_instance(path);
+    assert_eq!(entries.len(), 29 * 28 / 2);
+    for entry in entries {
+        let (from, to, distance) = entry.unwrap();
+        assert_eq!(instance.distance_matrix().get_data(from, to), distance);
+    }
+}
+
+#[test]
+fn test_stream_edge_weights_invalid_utf8_short() {
+    let bytes = std::fs::read("tests/test_assets/instances/explicit_lower_diag_row.tsp").unwrap();
+    let mut index_in_map = 0;
+    let (metadata, _) = parse_metadata(&bytes, &mut index_in_map).unwrap();
+    let mut section = bytes[index_in_map..].to_vec();
+    section.splice(0..0, *b"0 \xff\n");
+    assert!(matches!(
+        stream_edge_weights(&section, &metadata).err(),
+        Some(ParserError::InvalidUtf8 { line: 1 })
+    ));
+}
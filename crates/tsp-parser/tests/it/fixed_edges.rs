@@ -0,0 +1,21 @@
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge::UnEdge, matrix::MatrixSym, node::Node,
+};
+use tsp_parser::parse_tsp_instance;
+
+#[test]
+fn test_fixed_edges_linhp318() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/linhp318.tsp").unwrap();
+
+    // The file contains the fixed edge "1 214" (1-based)
+    assert_eq!(instance.fixed_edges(), &[UnEdge::new(Node(0), Node(213))]);
+    assert_eq!(instance.metadata().dimension, 318);
+}
+
+#[test]
+fn test_no_fixed_edges_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    assert!(instance.fixed_edges().is_empty());
+}
@@ -0,0 +1,103 @@
+use tsp_core::instance::{distance::Distance, matrix::MatrixSym};
+use tsp_parser::{
+    ParserError, data_section::DataSectionParseError, metadata::MetaDataParseError,
+    parse_tsp_instance,
+};
+
+#[test]
+fn test_invalid_utf8_in_comment_short() {
+    let result =
+        parse_tsp_instance::<MatrixSym<Distance>>("tests/test_assets/instances/latin1_comment.tsp");
+    assert!(
+        matches!(result, Err(ParserError::InvalidUtf8 { line: 3 })),
+        "Expected invalid UTF-8 error in line 3, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_duplicate_node_index_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/duplicate_node_index.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::DuplicateNodeIndex(2)
+            ))
+        ),
+        "Expected duplicate node index 2, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_node_index_out_of_range_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/node_index_out_of_range.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::NodeIndexOutOfRange {
+                    index: 0,
+                    dimension: 4
+                }
+            ))
+        ),
+        "Expected node index 0 to be out of range, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_missing_node_index_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/missing_node_index.tsp",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::MissingNodeIndex(3)
+            ))
+        ),
+        "Expected node index 3 to be missing, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_missing_coordinate_short() {
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/missing_coordinate.tsp",
+    );
+    assert!(
+        matches!(
+            &result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::InvalidCoordinate(line)
+            )) if line == "2 300"
+        ),
+        "Expected a missing coordinate in the line of node 2, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_zero_dimension_short() {
+    let result =
+        parse_tsp_instance::<MatrixSym<Distance>>("tests/test_assets/instances/zero_dimension.tsp");
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::MetaDataParsing(
+                MetaDataParseError::InstanceMetadataBuilderError(_)
+            ))
+        ),
+        "Expected dimension 0 to be rejected, got {:?}",
+        result.map(|_| ())
+    );
+}
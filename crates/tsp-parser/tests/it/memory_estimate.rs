@@ -0,0 +1,63 @@
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{Matrix, MatrixSym},
+};
+use tsp_parser::{
+    distance_container::{can_build_matrix, matrix_memory_estimate},
+    metadata::parse_metadata,
+    parse_tsp_instance,
+};
+
+const GIGABYTE: usize = 1 << 30;
+
+#[test]
+fn test_memory_estimate_matches_parsed_instance() {
+    let path = "../../instances/tsplib_symmetric/berlin52.tsp";
+    let full: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(path).unwrap();
+    let symmetric: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+
+    assert_eq!(
+        matrix_memory_estimate::<Matrix<Distance>>(52),
+        std::mem::size_of_val(full.distances().data())
+    );
+    assert_eq!(
+        matrix_memory_estimate::<MatrixSym<Distance>>(52),
+        std::mem::size_of_val(symmetric.distances().data().as_slice())
+    );
+    for dimension in [0, 1, 2, 7] {
+        assert_eq!(
+            matrix_memory_estimate::<MatrixSym<Distance>>(dimension),
+            dimension * (dimension + 1) / 2 * size_of::<Distance>()
+        );
+    }
+}
+
+#[test]
+fn test_can_build_matrix_before_parsing_data() {
+    let bytes = std::fs::read("../../instances/tsplib_symmetric/pla85900.tsp").unwrap();
+    let (metadata, _) = parse_metadata(&bytes, &mut 0).unwrap();
+
+    assert!(!can_build_matrix::<Matrix<Distance>>(
+        metadata.dimension,
+        16 * GIGABYTE
+    ));
+    assert!(can_build_matrix::<Matrix<Distance>>(
+        metadata.dimension,
+        32 * GIGABYTE
+    ));
+    assert!(can_build_matrix::<MatrixSym<Distance>>(
+        metadata.dimension,
+        16 * GIGABYTE
+    ));
+
+    // The estimate saturates instead of overflowing
+    assert_eq!(
+        matrix_memory_estimate::<Matrix<Distance>>(usize::MAX),
+        usize::MAX
+    );
+    assert!(!can_build_matrix::<MatrixSym<Distance>>(
+        usize::MAX,
+        usize::MAX - 1
+    ));
+}
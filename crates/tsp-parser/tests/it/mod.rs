@@ -1,3 +1,19 @@
 mod check_canonical_tour_length;
 mod check_matrix_parsing;
+mod coordinate_formats;
+mod distance_functions;
+mod eof_handling;
+mod explicit_formats;
+mod fixed_edges;
+mod malformed_instances;
+mod memory_estimate;
+mod multiple_instances;
+mod parse_options;
 mod parse_without_error;
+#[cfg(feature = "plotters")]
+mod plot;
+mod problem_types;
+mod reader;
+mod statistics;
+mod tour_files;
+mod write_instance;
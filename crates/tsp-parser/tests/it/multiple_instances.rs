@@ -0,0 +1,83 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::MatrixSym};
+use tsp_parser::{ParserError, parse_all_instances, parse_tsp_instance};
+
+const PATHS: [&str; 5] = [
+    "../../instances/tsplib_symmetric/berlin52.tsp",
+    "tests/test_assets/instances/explicit_upper_row.tsp",
+    "tests/test_assets/instances/small_cvrp.vrp",
+    "tests/test_assets/instances/single_node.tsp",
+    "../../instances/tsplib_symmetric/burma14.tsp",
+];
+
+fn concatenate(paths: &[&str], separator: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for path in paths {
+        bytes.extend_from_slice(&std::fs::read(path).unwrap());
+        bytes.extend_from_slice(separator.as_bytes());
+    }
+    bytes
+}
+
+fn check_same_as_single_files(instances: &[TSPSymInstance<MatrixSym<Distance>>]) {
+    assert_eq!(instances.len(), PATHS.len());
+    for (instance, path) in instances.iter().zip(PATHS) {
+        let expected: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+        assert_eq!(instance.metadata().name, expected.metadata().name);
+        assert_eq!(instance.raw_distances(), expected.raw_distances(), "{path}");
+        assert_eq!(instance.demands(), expected.demands(), "{path}");
+    }
+}
+
+#[test]
+fn test_parse_all_instances() {
+    let instances = parse_all_instances(&concatenate(&PATHS, "")).unwrap();
+    check_same_as_single_files(&instances);
+}
+
+#[test]
+fn test_parse_all_instances_blank_lines_between() {
+    let instances = parse_all_instances(&concatenate(&PATHS, "\n  \n\n")).unwrap();
+    check_same_as_single_files(&instances);
+}
+
+#[test]
+fn test_parse_all_instances_empty() {
+    let instances: Vec<TSPSymInstance<MatrixSym<Distance>>> =
+        parse_all_instances(b" \n\n").unwrap();
+    assert!(instances.is_empty());
+}
+
+#[test]
+fn test_parse_all_instances_error_in_second_instance() {
+    let bytes = concatenate(
+        &[
+            PATHS[0],
+            "tests/test_assets/instances/explicit_missing_edge_weights.tsp",
+        ],
+        "",
+    );
+    let result = parse_all_instances::<MatrixSym<Distance>>(&bytes);
+    assert!(
+        matches!(result, Err(ParserError::DataSectionParsing(_))),
+        "Expected an error in the second instance, got {:?}",
+        result.map(|instances| instances.len())
+    );
+}
+
+#[test]
+fn test_parse_all_instances_invalid_utf8() {
+    let mut bytes = concatenate(&PATHS[..2], "");
+    // Invalid UTF-8 in the data section of the second instance
+    let position = bytes.len() - "EOF\n".len();
+    bytes.splice(position..position, *b"\xff\n");
+    let lines = bytes[..position]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count();
+    let result = parse_all_instances::<MatrixSym<Distance>>(&bytes);
+    assert!(
+        matches!(result, Err(ParserError::InvalidUtf8 { line }) if line == lines + 1),
+        "Expected invalid UTF-8, got {:?}",
+        result.map(|instances| instances.len())
+    );
+}
@@ -0,0 +1,387 @@
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_parser::{
+    ParseOptions, ParserError,
+    data_section::{
+        DataSectionParseError, Point2D,
+        distance_function::{geo_degrees_in_range, geo_minutes_valid},
+    },
+    parse_tsp_instance, parse_tsp_instance_with_diagnostics, parse_tsp_instance_with_options,
+};
+
+/// Checks that the distances of the instance parsed with the given scale are the exact distances
+/// times the scale up to rounding, i.e. within the given tolerance of the scaled rounded distances.
+fn check_scaled(path: &str, scale: u32, tolerance: i32) {
+    let unscaled: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+    let scaled: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance_with_options(
+        path,
+        &ParseOptions {
+            scale,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+
+    let dimension = unscaled.metadata().dimension;
+    for from in 0..dimension {
+        for to in 0..from {
+            let (from, to) = (Node(from), Node(to));
+            let unscaled = unscaled.distance_matrix().get_data(from, to).0;
+            let scaled = scaled.distance_matrix().get_data(from, to).0;
+            assert!(
+                (scaled - scale as i32 * unscaled).abs() <= tolerance,
+                "{path}: distance {scaled} between {from:?} and {to:?} is not {unscaled} scaled \
+                 by {scale}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_scale_euc_2d() {
+    // Rounding to the nearest integer is off by at most 0.5 before scaling
+    check_scaled("../../instances/tsplib_symmetric/berlin52.tsp", 100, 50);
+}
+
+#[test]
+fn test_scale_geo() {
+    // The GEO distance adds 1 and truncates, so it is off by at most 1 before scaling
+    check_scaled("../../instances/tsplib_symmetric/burma14.tsp", 100, 100);
+}
+
+#[test]
+fn test_scale_explicit_short() {
+    check_scaled("tests/test_assets/instances/explicit_upper_row.tsp", 10, 0);
+}
+
+#[test]
+fn test_scale_overflow_short() {
+    // The largest distance 9 times the scale exceeds i32::MAX
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        "tests/test_assets/instances/explicit_upper_row.tsp",
+        &ParseOptions {
+            scale: 300_000_000,
+            ..ParseOptions::default()
+        },
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::ScaledDistanceOverflow { .. }
+            ))
+        ),
+        "Expected an overflow of the scaled distances, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+/// Checks that parsing the coordinate based instance with the given scale fails, as a scaled
+/// distance does not fit into an `i32`.
+fn check_scaled_coordinates_overflow(path: &str, scale: u32) {
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            scale,
+            ..ParseOptions::default()
+        },
+    );
+    match result {
+        Err(ParserError::DataSectionParsing(DataSectionParseError::ScaledDistanceOverflow {
+            distance,
+            scale: error_scale,
+        })) => {
+            assert_eq!(error_scale, scale);
+            // The reported distance is the one before scaling, up to rounding
+            assert!(
+                (i64::from(distance) + 1) * i64::from(scale) > i64::from(i32::MAX),
+                "{path}: distance {distance} scaled by {scale} fits into an i32"
+            );
+        }
+        result => panic!(
+            "{path}: Expected an overflow of the scaled distances, got {:?}",
+            result.map(|_| ())
+        ),
+    }
+}
+
+#[test]
+fn test_scale_coordinates_overflow_short() {
+    // The largest distance 13 times the scale exceeds i32::MAX
+    check_scaled_coordinates_overflow("tests/test_assets/instances/euc_3d.tsp", 300_000_000);
+    check_scaled_coordinates_overflow("../../instances/tsplib_symmetric/berlin52.tsp", 10_000_000);
+    check_scaled_coordinates_overflow("../../instances/tsplib_symmetric/burma14.tsp", 10_000_000);
+
+    // Scaled distances up to i32::MAX still work
+    check_scaled(
+        "tests/test_assets/instances/euc_3d.tsp",
+        100_000_000,
+        50_000_000,
+    );
+}
+
+#[test]
+fn test_scale_keeps_sub_unit_precision_short() {
+    let path = "tests/test_assets/instances/sub_unit_distances.tsp";
+    let unscaled: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(path).unwrap();
+    let scaled: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance_with_options(
+        path,
+        &ParseOptions {
+            scale: 1000,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+
+    // Exact distances are sqrt(2), 0.3 and sqrt(1.49)
+    let pairs = [(0, 1), (0, 2), (1, 2)];
+    let expected_unscaled = [1, 0, 1];
+    let expected_scaled = [1414, 300, 1221];
+    for (index, (from, to)) in pairs.into_iter().enumerate() {
+        let (from, to) = (Node(from), Node(to));
+        assert_eq!(
+            unscaled.distance_matrix().get_data(from, to),
+            Distance(expected_unscaled[index])
+        );
+        assert_eq!(
+            scaled.distance_matrix().get_data(from, to),
+            Distance(expected_scaled[index])
+        );
+    }
+}
+
+#[test]
+fn test_strict_collects_rounded_zero_distances_short() {
+    let path = "tests/test_assets/instances/sub_unit_distances.tsp";
+    let strict: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance_with_options(
+        path,
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    // Nodes 0 and 2 have distance 0.3
+    assert_eq!(strict.rounded_zero_distances(), [(Node(0), Node(2))]);
+
+    // Scaling removes the zero distance
+    let scaled: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance_with_options(
+        path,
+        &ParseOptions {
+            scale: 10,
+            strict: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    assert!(scaled.rounded_zero_distances().is_empty());
+
+    let not_strict: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+    assert!(not_strict.rounded_zero_distances().is_empty());
+}
+
+#[test]
+fn test_strict_integer_coordinates() {
+    // Distinct integer coordinates have a distance of at least 1
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance_with_options(
+        "../../instances/tsplib_symmetric/dsj1000.tsp",
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    assert!(instance.rounded_zero_distances().is_empty());
+}
+
+#[test]
+fn test_strict_geo_coordinates() {
+    // The degrees of the TSPLIB GEO instances are in range
+    for name in [
+        "ali535",
+        "burma14",
+        "gr137",
+        "gr202",
+        "gr229",
+        "gr431",
+        "gr666",
+        "gr96",
+        "ulysses16",
+        "ulysses22",
+    ] {
+        let path = format!("../../instances/tsplib_symmetric/{name}.tsp");
+        let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+            &path,
+            &ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+        );
+        assert!(result.is_ok(), "{path}: {:?}", result.map(|_| ()));
+    }
+}
+
+#[test]
+fn test_strict_geo_out_of_range_short() {
+    let path = "tests/test_assets/instances/geo_out_of_range.tsp";
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    );
+    // Node 2 has a latitude of -122 degrees
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::InvalidGeoCoordinate { node: 2, .. }
+            ))
+        ),
+        "Expected out of range geographical coordinates of node 2, got {:?}",
+        result.map(|_| ())
+    );
+
+    // Without strict mode, the coordinates are accepted
+    assert!(parse_tsp_instance::<MatrixSym<Distance>>(path).is_ok());
+}
+
+#[test]
+fn test_geo_minutes_decimal_degrees() {
+    // In strict mode, decimal degrees only cause a warning, as a few points of the TSPLIB instance
+    // ali535 have 60 or more minutes as well
+    let path = "tests/test_assets/instances/geo_decimal_degrees.tsp";
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        },
+    );
+    assert!(result.is_ok());
+
+    // 48.85 has 85 minutes in the DDD.MM format
+    assert!(geo_minutes_valid(&Point2D { x: 52.52, y: 13.40 }));
+    assert!(!geo_minutes_valid(&Point2D { x: 48.85, y: 2.35 }));
+    assert!(!geo_minutes_valid(&Point2D { x: 41.59, y: -12.6 }));
+    assert!(geo_degrees_in_range(&Point2D {
+        x: -90.5,
+        y: 180.59
+    }));
+    assert!(!geo_degrees_in_range(&Point2D { x: 91.0, y: 0.0 }));
+}
+
+#[test]
+fn test_diagnostics_node_coords() {
+    let path = "../../instances/tsplib_symmetric/berlin52.tsp";
+    let (instance, diagnostics) = parse_tsp_instance_with_diagnostics::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            diagnostics: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    let diagnostics = diagnostics.unwrap();
+    assert_eq!(instance.metadata().dimension, 52);
+    assert_eq!(diagnostics.points_parsed, 52);
+    // Five lines of specification, the section keyword, the coordinates and the EOF line
+    assert_eq!(diagnostics.lines_read, 59);
+    // The instance is below the parallelism bound
+    assert_eq!(diagnostics.threads_used, 1);
+
+    // Without the option, no diagnostics are collected
+    let (_, diagnostics) =
+        parse_tsp_instance_with_diagnostics::<MatrixSym<Distance>>(path, &ParseOptions::default())
+            .unwrap();
+    assert!(diagnostics.is_none());
+}
+
+#[test]
+fn test_diagnostics_explicit_short() {
+    let (_, diagnostics) = parse_tsp_instance_with_diagnostics::<Matrix<Distance>>(
+        "tests/test_assets/instances/explicit_upper_row.tsp",
+        &ParseOptions {
+            diagnostics: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    let diagnostics = diagnostics.unwrap();
+    assert_eq!(diagnostics.points_parsed, 0);
+    assert_eq!(diagnostics.threads_used, 1);
+    // Reading stops after the last edge weight, before the EOF line
+    assert_eq!(diagnostics.lines_read, 10);
+}
+
+#[test]
+fn test_diagnostics_parallel_threads() {
+    // The full matrix of dsj1000 exceeds the parallelism bound
+    let (_, diagnostics) = parse_tsp_instance_with_diagnostics::<Matrix<Distance>>(
+        "../../instances/tsplib_symmetric/dsj1000.tsp",
+        &ParseOptions {
+            diagnostics: true,
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    let diagnostics = diagnostics.unwrap();
+    let available = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    assert_eq!(diagnostics.points_parsed, 1000);
+    assert!((1..=available).contains(&diagnostics.threads_used));
+    assert!(diagnostics.distance_compute_ns > 0);
+}
+
+/// Manhattan distances, standing in for a user-defined distance function.
+fn manhattan(point_a: &Point2D, point_b: &Point2D) -> Distance {
+    Distance(((point_a.x - point_b.x).abs() + (point_a.y - point_b.y).abs()) as i32)
+}
+
+#[test]
+fn test_special_distance_function_short() {
+    let path = "tests/test_assets/instances/special.tsp";
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance_with_options(
+        path,
+        &ParseOptions {
+            scale: 10,
+            special_distance_function: Some(manhattan),
+            ..ParseOptions::default()
+        },
+    )
+    .unwrap();
+    let distances = instance.distance_matrix();
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(70));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(60));
+    assert_eq!(distances.get_data(Node(1), Node(2)), Distance(70));
+
+    let result = parse_tsp_instance_with_options::<MatrixSym<Distance>>(
+        path,
+        &ParseOptions {
+            scale: u32::MAX,
+            special_distance_function: Some(manhattan),
+            ..ParseOptions::default()
+        },
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::ScaledDistanceOverflow { .. }
+            ))
+        ),
+        "Expected an overflow of the scaled distances, got {:?}",
+        result.map(|_| ())
+    );
+
+    let result = parse_tsp_instance::<MatrixSym<Distance>>(path);
+    assert!(
+        matches!(result, Err(ParserError::SpecialFunctionRequired)),
+        "Expected a missing distance function, got {:?}",
+        result.map(|_| ())
+    );
+}
@@ -1,47 +1,32 @@
-use std::any::Any;
-
 use tsp_core::instance::{
     distance::Distance,
     matrix::{Matrix, MatrixSym},
 };
 use tsp_macros::test_fn_on_all_instances;
+use tsp_parser::ParserError;
 
-fn parse_instance_symmetric(path: &str) {
-    let parsing_result = std::panic::catch_unwind(|| {
-        tsp_parser::parse_tsp_instance::<MatrixSym<Distance>>(path.to_owned())
-    });
-    if let Err(err) = parsing_result {
-        handle_error(err);
-    } else {
-        assert!(parsing_result.is_ok());
-    }
-}
+// These tests check that parsing succeeds. Features which are not implemented yet are reported as
+// `ParserError::Unsupported`, any other error fails the test.
 
-fn parse_instance_non_symmetric(path: &str) {
-    let parsing_result = std::panic::catch_unwind(|| {
-        tsp_parser::parse_tsp_instance::<Matrix<Distance>>(path.to_owned())
-    });
-    if let Err(err) = parsing_result {
-        handle_error(err);
-    } else {
-        assert!(parsing_result.is_ok());
+fn assert_parsed_or_unsupported<T>(path: &str, result: Result<T, ParserError>) {
+    match result {
+        Ok(_) | Err(ParserError::Unsupported { .. }) => {}
+        Err(error) => panic!("Failed to parse {path}: {error}"),
     }
 }
 
-fn handle_error(err: Box<dyn Any + Send>) {
-    let err_msg = if let Some(s) = err.downcast_ref::<&str>() {
-        s.to_string()
-    } else if let Some(s) = err.downcast_ref::<String>() {
-        s.clone()
-    } else {
-        "Unknown panic message".to_string()
-    };
+fn parse_instance_symmetric(path: &str) {
+    assert_parsed_or_unsupported(
+        path,
+        tsp_parser::parse_tsp_instance::<MatrixSym<Distance>>(path),
+    );
+}
 
-    match err_msg.as_str() {
-        "not yet implemented: Explicit distance matrix parsing is not supported yet" => {}
-        "not yet implemented: Fixed edges sections are not supported yet" => {}
-        _ => assert!(false, "Parsing failed with unexpected error: {}", err_msg),
-    }
+fn parse_instance_non_symmetric(path: &str) {
+    assert_parsed_or_unsupported(
+        path,
+        tsp_parser::parse_tsp_instance::<Matrix<Distance>>(path),
+    );
 }
 
 test_fn_on_all_instances!(parse_instance_symmetric, short_symmetric, 0, 50);
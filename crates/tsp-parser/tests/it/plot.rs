@@ -0,0 +1,36 @@
+use tsp_core::instance::{
+    TSPSymInstance, UnTour, distance::Distance, matrix::MatrixSym, tour_order::TourOrder,
+};
+use tsp_parser::{
+    data_section::Point2D,
+    parse_tsp_instance,
+    plot::{PlotError, plot_tour_png},
+};
+
+#[test]
+fn test_plot_tour_png() {
+    // bayg29 has display coordinates for its explicit distances
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/bayg29.tsp").unwrap();
+    let coordinates: Vec<_> = instance
+        .display_coordinates()
+        .iter()
+        .map(|&(x, y)| Point2D { x, y })
+        .collect();
+    let tour = UnTour {
+        edges: TourOrder::identity(coordinates.len()).edges().collect(),
+        cost: Distance(1234),
+    };
+
+    let output_path = std::env::temp_dir().join("tsp_parser_plot_tour.png");
+    plot_tour_png(&coordinates, &tour, &output_path).unwrap();
+    let written = std::fs::read(&output_path).unwrap();
+    std::fs::remove_file(&output_path).unwrap();
+    assert!(written.starts_with(b"\x89PNG"));
+
+    // The tour does not visit the additional node
+    let mut coordinates = coordinates;
+    coordinates.push(Point2D { x: 0.0, y: 0.0 });
+    let result = plot_tour_png(&coordinates, &tour, &output_path);
+    assert!(matches!(result, Err(PlotError::MalformedTour(_))));
+}
@@ -0,0 +1,93 @@
+use tsp_core::{
+    instance::{
+        TSPSymInstance,
+        distance::Distance,
+        matrix::{Matrix, MatrixSym},
+        node::Node,
+    },
+    tsp_lib_spec::ProblemType,
+};
+use tsp_parser::{ParserError, data_section::DataSectionParseError, parse_tsp_instance};
+
+#[test]
+fn test_sop_short() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/small_sop.sop").unwrap();
+    assert!(matches!(instance.metadata().problem_type, ProblemType::SOP));
+
+    // The -1 entries are precedences and are set to 0 in the distances
+    #[rustfmt::skip]
+    let expected = [
+        0, 5, 7, 1000000,
+        0, 0, 3, 8,
+        0, 0, 0, 2,
+        0, 0, 0, 0,
+    ];
+    assert_eq!(instance.raw_distances(), expected.map(Distance).as_slice());
+
+    let mut precedences = instance.precedences().to_vec();
+    precedences.sort();
+    assert_eq!(
+        precedences,
+        vec![
+            (Node(0), Node(1)),
+            (Node(0), Node(2)),
+            (Node(0), Node(3)),
+            (Node(1), Node(2)),
+            (Node(1), Node(3)),
+            (Node(2), Node(3)),
+        ]
+    );
+}
+
+#[test]
+fn test_sop_symmetric_container_keeps_lower_triangle_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/small_sop.sop").unwrap();
+    assert!(
+        instance
+            .distance_matrix()
+            .data()
+            .iter()
+            .all(|d| *d == Distance(0))
+    );
+    assert_eq!(instance.precedences().len(), 6);
+}
+
+#[test]
+fn test_sop_dimension_mismatch_short() {
+    let result = parse_tsp_instance::<Matrix<Distance>>(
+        "tests/test_assets/instances/sop_dimension_mismatch.sop",
+    );
+    assert!(
+        matches!(
+            result,
+            Err(ParserError::DataSectionParsing(
+                DataSectionParseError::SopDimensionMismatch {
+                    expected: 2,
+                    found: 3
+                }
+            ))
+        ),
+        "Expected a mismatching SOP dimension, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_cvrp_short() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("tests/test_assets/instances/small_cvrp.vrp").unwrap();
+    let distances = instance.distance_matrix();
+
+    assert!(matches!(
+        instance.metadata().problem_type,
+        ProblemType::CVRP
+    ));
+    assert_eq!(instance.metadata().capacity, Some(10));
+    assert_eq!(distances.get_data(Node(0), Node(1)), Distance(5));
+    assert_eq!(distances.get_data(Node(0), Node(2)), Distance(10));
+    assert_eq!(distances.get_data(Node(0), Node(3)), Distance(5));
+    assert_eq!(instance.demands(), &[0, 4, 6, 3]);
+    assert_eq!(instance.depots(), &[Node(0)]);
+}
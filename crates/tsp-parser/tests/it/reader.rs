@@ -0,0 +1,47 @@
+use std::fs::File;
+
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::MatrixSym, node::Node};
+use tsp_parser::{ParserError, parse_tsp_instance, parse_tsp_reader};
+
+#[test]
+fn test_parse_tsp_reader_same_as_file() {
+    for path in [
+        "../../instances/tsplib_symmetric/berlin52.tsp",
+        "tests/test_assets/instances/explicit_upper_row.tsp",
+        "tests/test_assets/instances/small_cvrp.vrp",
+    ] {
+        let expected: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(path).unwrap();
+        let instance: TSPSymInstance<MatrixSym<Distance>> =
+            parse_tsp_reader(File::open(path).unwrap()).unwrap();
+        assert_eq!(instance.metadata().name, expected.metadata().name);
+        assert_eq!(instance.raw_distances(), expected.raw_distances(), "{path}");
+        assert_eq!(instance.demands(), expected.demands(), "{path}");
+    }
+}
+
+#[test]
+fn test_parse_tsp_reader_from_bytes() {
+    let bytes = b"NAME: line\nTYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EUC_2D\n\
+        NODE_COORD_SECTION\n1 0 0\n2 3 4\n3 6 8\nEOF\n";
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_reader(&bytes[..]).unwrap();
+    assert_eq!(instance.dimension(), 3);
+    assert_eq!(
+        instance.distance_matrix().get_data(Node(2), Node(0)),
+        Distance(10)
+    );
+}
+
+#[test]
+fn test_parse_tsp_reader_empty() {
+    let result = parse_tsp_reader::<MatrixSym<Distance>>(std::io::empty());
+    assert!(result.is_err());
+    assert!(!matches!(result, Err(ParserError::Io(_))));
+}
+
+#[test]
+fn test_parse_tsp_reader_invalid_utf8() {
+    let bytes = b"NAME: line\nTYPE: TSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EUC_2D\n\
+        NODE_COORD_SECTION\n1 0 0\n2 3 4\xff\n3 6 8\nEOF\n";
+    let result = parse_tsp_reader::<MatrixSym<Distance>>(&bytes[..]);
+    assert!(matches!(result, Err(ParserError::InvalidUtf8 { line: 7 })));
+}
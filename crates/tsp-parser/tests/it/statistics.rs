@@ -0,0 +1,87 @@
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use tsp_parser::{
+    data_section::{GeoPoint, Point2D},
+    statistics::{coordinate_stats, geo_stats},
+};
+
+#[test]
+fn test_coordinate_stats_grid() {
+    // A 3x3 grid with spacing 2
+    let points: Vec<_> = (0..9)
+        .map(|index| Point2D {
+            x: f64::from(index % 3) * 2.0,
+            y: f64::from(index / 3) * 2.0,
+        })
+        .collect();
+    let stats = coordinate_stats(&points).unwrap();
+    assert_eq!(stats.min, Point2D { x: 0.0, y: 0.0 });
+    assert_eq!(stats.max, Point2D { x: 4.0, y: 4.0 });
+    assert_eq!(stats.centroid, Point2D { x: 2.0, y: 2.0 });
+    assert_eq!(stats.average_nearest_neighbor_distance, 2.0);
+}
+
+#[test]
+fn test_coordinate_stats_small() {
+    assert!(coordinate_stats(&[]).is_none());
+
+    let point = Point2D { x: 3.0, y: -1.0 };
+    let stats = coordinate_stats(&[point]).unwrap();
+    assert_eq!(
+        (stats.min, stats.max, stats.centroid),
+        (point, point, point)
+    );
+    assert_eq!(stats.average_nearest_neighbor_distance, 0.0);
+}
+
+#[test]
+fn test_coordinate_stats_matches_brute_force() {
+    let mut rng = SmallRng::seed_from_u64(0);
+    for _ in 0..20 {
+        let number_of_points = rng.random_range(2..200);
+        let points: Vec<_> = (0..number_of_points)
+            .map(|_| Point2D {
+                x: rng.random_range(0.0..100.0),
+                y: rng.random_range(0.0..100.0),
+            })
+            .collect();
+
+        let expected = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other_index, _)| other_index != index)
+                    .map(|(_, other)| (point.x - other.x).hypot(point.y - other.y))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .sum::<f64>()
+            / number_of_points as f64;
+        let stats = coordinate_stats(&points).unwrap();
+        assert!((stats.average_nearest_neighbor_distance - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_geo_stats() {
+    assert!(geo_stats(&[]).is_none());
+
+    let points = [
+        GeoPoint {
+            latitude: 0.5,
+            longitude: -1.0,
+        },
+        GeoPoint {
+            latitude: -0.25,
+            longitude: 2.0,
+        },
+        GeoPoint {
+            latitude: 0.0,
+            longitude: 0.0,
+        },
+    ];
+    let stats = geo_stats(&points).unwrap();
+    assert_eq!(stats.latitude_span, 0.75);
+    assert_eq!(stats.longitude_span, 3.0);
+}
@@ -0,0 +1,79 @@
+use tsp_core::instance::{distance::Distance, matrix::MatrixSym, node::Node};
+use tsp_parser::{
+    ParserError, data_section::DataSectionParseError, metadata::MetaDataParseError, parse_tour,
+    parse_tsp_instance, parse_tsp_tour,
+};
+
+#[test]
+fn test_parse_opt_tour() {
+    let tour = parse_tsp_tour("../../instances/tsp_rust/12.opt.tour").unwrap();
+    let ids: Vec<usize> = tour.iter().map(|node| node.to_tsplib()).collect();
+    assert_eq!(ids, vec![1, 6, 12, 10, 5, 3, 7, 2, 11, 4, 8, 9]);
+}
+
+#[test]
+fn test_parse_tour_without_terminator_and_dimension() {
+    let tour = parse_tour(b"NAME: short\nTYPE: TOUR\nTOUR_SECTION\n2 3\n1\n").unwrap();
+    assert_eq!(tour, vec![Node(1), Node(2), Node(0)]);
+}
+
+#[test]
+fn test_parse_tour_duplicate_node() {
+    let result = parse_tsp_tour("tests/test_assets/instances/duplicate_node.tour");
+    assert!(matches!(
+        result,
+        Err(ParserError::DataSectionParsing(
+            DataSectionParseError::DuplicateNodeIndex(3)
+        ))
+    ));
+}
+
+#[test]
+fn test_parse_tour_missing_and_out_of_range_nodes() {
+    let missing = parse_tour(b"TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1 3\n-1\nEOF\n");
+    assert!(matches!(
+        missing,
+        Err(ParserError::DataSectionParsing(
+            DataSectionParseError::MissingNodeIndex(2)
+        ))
+    ));
+    let out_of_range = parse_tour(b"TYPE: TOUR\nDIMENSION: 2\nTOUR_SECTION\n1 3\n-1\n");
+    assert!(matches!(
+        out_of_range,
+        Err(ParserError::DataSectionParsing(
+            DataSectionParseError::NodeIndexOutOfRange {
+                index: 3,
+                dimension: 2
+            }
+        ))
+    ));
+}
+
+#[test]
+fn test_parse_tour_rejects_instances() {
+    let result = parse_tsp_tour("../../instances/tsp_rust/12.tsp");
+    assert!(matches!(
+        result,
+        Err(ParserError::MetaDataParsing(
+            MetaDataParseError::InvalidProblemType(_)
+        ))
+    ));
+    // Tour files have no distances, so they are not instances either
+    let result = parse_tsp_instance::<MatrixSym<Distance>>("../../instances/tsp_rust/12.opt.tour");
+    assert!(
+        matches!(result, Err(ParserError::TourFile)),
+        "Expected a tour file error, got {:?}",
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_parse_tour_missing_type() {
+    let result = parse_tour(b"NAME: short\nDIMENSION: 2\nTOUR_SECTION\n1 2\n-1\n");
+    assert!(matches!(
+        result,
+        Err(ParserError::MetaDataParsing(
+            MetaDataParseError::MissingProblemType
+        ))
+    ));
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use tsp_core::instance::{
+    TSPSymInstance, UnTour,
+    distance::Distance,
+    edge::UnEdge,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_parser::{
+    parse_tsp_instance,
+    writer::{write_instance, write_tour_csv},
+};
+
+/// Returns a path in the temporary directory for the written instance.
+fn output_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tsp_parser_write_instance_{name}.tsp"))
+}
+
+fn check_round_trip_symmetric(instance_path: &str, name: &str) {
+    let instance: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(instance_path).unwrap();
+    let output_path = output_path(name);
+    write_instance(&instance, &output_path).unwrap();
+
+    let written: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(&output_path).unwrap();
+    std::fs::remove_file(&output_path).unwrap();
+
+    assert_eq!(written.metadata().name, instance.metadata().name);
+    assert_eq!(written.metadata().dimension, instance.metadata().dimension);
+    assert_eq!(written.raw_distances(), instance.raw_distances());
+    assert_eq!(written.fixed_edges(), instance.fixed_edges());
+}
+
+#[test]
+fn test_write_instance_round_trip_euc_2d() {
+    check_round_trip_symmetric("../../instances/tsplib_symmetric/eil51.tsp", "eil51");
+}
+
+#[test]
+fn test_write_instance_round_trip_explicit() {
+    check_round_trip_symmetric("../../instances/tsplib_symmetric/gr17.tsp", "gr17");
+}
+
+#[test]
+fn test_write_instance_round_trip_fixed_edges() {
+    check_round_trip_symmetric("../../instances/tsplib_symmetric/linhp318.tsp", "linhp318");
+}
+
+#[test]
+fn test_write_instance_round_trip_full_matrix() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap();
+    let output_path = output_path("att48_full");
+    write_instance(&instance, &output_path).unwrap();
+
+    let written: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(&output_path).unwrap();
+    std::fs::remove_file(&output_path).unwrap();
+
+    assert_eq!(written.raw_distances(), instance.raw_distances());
+}
+
+#[test]
+fn test_write_tour_csv() {
+    let edges = [(0, 2), (2, 3), (3, 1), (1, 0)]
+        .map(|(from, to)| UnEdge::new(Node(from), Node(to)))
+        .to_vec();
+    let tour = UnTour {
+        edges,
+        cost: Distance(0),
+    };
+    let output_path = std::env::temp_dir().join("tsp_parser_write_tour.csv");
+    write_tour_csv(&tour, 4, &output_path).unwrap();
+    let written = std::fs::read_to_string(&output_path).unwrap();
+    std::fs::remove_file(&output_path).unwrap();
+    assert_eq!(written, "0\n2\n3\n1\n");
+
+    // The tour does not visit node 4
+    let result = write_tour_csv(&tour, 5, &output_path);
+    assert_eq!(
+        result.map_err(|error| error.kind()),
+        Err(std::io::ErrorKind::InvalidInput)
+    );
+    assert!(!output_path.exists());
+}
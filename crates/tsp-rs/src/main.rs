@@ -9,14 +9,20 @@ use tsp_solvers::{held_karp, held_karp_mod::held_karp_parallel};
 fn main() {
     env_logger::init();
 
-    let tsp_instance =
-        tsp_parser::parse_tsp_instance::<Matrix<Distance>>(
-            "instances/tsplib_symmetric/eil76.tsp",
-        )
-        .unwrap();
+    // The path "-" reads the instance from stdin, e.g. in shell pipelines
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "instances/tsplib_symmetric/eil76.tsp".to_string());
+    let tsp_instance = if path == "-" {
+        tsp_parser::parse_tsp_reader::<Matrix<Distance>>(std::io::stdin().lock())
+    } else {
+        tsp_parser::parse_tsp_instance::<Matrix<Distance>>(&path)
+    }
+    .unwrap();
     let best_tour = held_karp(tsp_instance.distance_matrix());
-    if let Some(best_tour) = &best_tour {
-        println!("Best tour found: {:?}", best_tour.cost.0);
+    match &best_tour {
+        Ok(best_tour) => println!("Best tour found: {:?}", best_tour.cost.0),
+        Err(error) => eprintln!("Could not solve instance: {error}"),
     }
 }
 
@@ -0,0 +1,40 @@
+//! Helpers shared by the benchmarks.
+
+use tsp_core::instance::{distance::Distance, node::Node};
+
+/// Prim's algorithm in `O(n^2)`, which accesses the row of the node last added to the tree in each
+/// iteration. Returns the cost of the minimum spanning tree.
+pub fn prim(
+    dimension: usize,
+    mut row_of: impl FnMut(Node, &mut dyn FnMut(&[Distance])),
+) -> Distance {
+    let mut in_tree = vec![false; dimension];
+    let mut best_cost_to_node = vec![Distance::MAX; dimension];
+    let mut tree_cost = Distance(0);
+
+    let mut current = Node(0);
+    in_tree[0] = true;
+    for _ in 1..dimension {
+        let mut cheapest_node = Node(0);
+        let mut cheapest_cost = Distance::MAX;
+        row_of(current, &mut |row| {
+            for (next, &distance) in row.iter().enumerate() {
+                if in_tree[next] {
+                    continue;
+                }
+                if distance < best_cost_to_node[next] {
+                    best_cost_to_node[next] = distance;
+                }
+                if best_cost_to_node[next] <= cheapest_cost {
+                    cheapest_cost = best_cost_to_node[next];
+                    cheapest_node = Node(next);
+                }
+            }
+        });
+        in_tree[cheapest_node.0] = true;
+        tree_cost += cheapest_cost;
+        current = cheapest_node;
+    }
+
+    tree_cost
+}
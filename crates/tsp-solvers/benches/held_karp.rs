@@ -11,11 +11,12 @@ macro_rules! create_held_karp_benchmarks {
                 parse_tsp_instance::<MatrixSym<Distance>>(concat!("../../instances/", $file_path))
                     .unwrap();
             let lower_distance_matrix = concorde_rs::LowerDistanceMatrix {
-                num_nodes: tsp_instance.metadata().dimension as u32,
+                num_nodes: u32::try_from(tsp_instance.metadata().dimension)
+                    .expect("Dimension should fit into u32"),
                 values: tsp_instance
                     .raw_distances()
                     .iter()
-                    .map(|&d| d.0 as u32)
+                    .map(|&d| u32::try_from(d).expect("Distances should be non-negative"))
                     .collect(),
             };
 
@@ -28,7 +29,7 @@ macro_rules! create_held_karp_benchmarks {
             let tsp_instance =
                 parse_tsp_instance::<MatrixSym<Distance>>(concat!("../../instances/", $file_path))
                     .unwrap();
-            let non_symmetric_matrix = tsp_instance.distance_matrix().to_edge_data_matrix();
+            let non_symmetric_matrix = tsp_instance.distance_matrix().to_non_symmetric();
 
             c.bench_function(
                 concat!("Held Karp using own implementation: ", $file_path),
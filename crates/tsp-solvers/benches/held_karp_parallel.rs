@@ -10,7 +10,7 @@ fn att48_parallel(c: &mut Criterion) {
     let tsp_instance =
         parse_tsp_instance::<MatrixSym<Distance>>("../../instances/tsplib_symmetric/att48.tsp")
             .unwrap();
-    let non_symmetric_matrix = tsp_instance.distance_matrix().to_edge_data_matrix();
+    let non_symmetric_matrix = tsp_instance.distance_matrix().to_non_symmetric();
 
     group.bench_function("Held Karp Parallel: att48.tsp", |b| {
         b.iter(|| held_karp_parallel(&non_symmetric_matrix).unwrap())
@@ -25,7 +25,7 @@ fn berlin52_parallel(c: &mut Criterion) {
     let tsp_instance =
         parse_tsp_instance::<MatrixSym<Distance>>("../../instances/tsplib_symmetric/berlin52.tsp")
             .unwrap();
-    let non_symmetric_matrix = tsp_instance.distance_matrix().to_edge_data_matrix();
+    let non_symmetric_matrix = tsp_instance.distance_matrix().to_non_symmetric();
 
     group.bench_function("Held Karp Parallel: berlin52.tsp", |b| {
         b.iter(|| held_karp_parallel(&non_symmetric_matrix).unwrap())
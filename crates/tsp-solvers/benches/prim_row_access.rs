@@ -0,0 +1,55 @@
+//! Compares the row access patterns of full and symmetric matrices in Prim's algorithm.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_parser::parse_tsp_instance;
+
+use crate::common::prim;
+
+mod common;
+
+fn prim_row_access_benchmark(c: &mut Criterion) {
+    let instance_path = "../../instances/tsplib_symmetric/d493.tsp";
+    let full: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(instance_path).unwrap();
+    let symmetric: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(instance_path).unwrap();
+    let full = full.distance_matrix();
+    let symmetric = symmetric.distance_matrix();
+    let dimension = full.dimension();
+
+    let mut group = c.benchmark_group("prim_row_access_d493");
+    group.bench_function("full matrix adjacency list", |b| {
+        b.iter(|| {
+            prim(dimension, |from, visit| {
+                visit(full.get_adjacency_list(from))
+            })
+        });
+    });
+    group.bench_function("symmetric matrix row_into", |b| {
+        let mut buffer = Vec::with_capacity(dimension);
+        b.iter(|| {
+            prim(dimension, |from, visit| {
+                symmetric.row_into(from, &mut buffer);
+                visit(&buffer);
+            })
+        });
+    });
+    group.bench_function("symmetric matrix get_data", |b| {
+        let mut buffer = Vec::with_capacity(dimension);
+        b.iter(|| {
+            prim(dimension, |from, visit| {
+                buffer.clear();
+                buffer.extend((0..dimension).map(|to| symmetric.get_data(from, Node(to))));
+                visit(&buffer);
+            })
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(prim_row_access, prim_row_access_benchmark);
+criterion_main!(prim_row_access);
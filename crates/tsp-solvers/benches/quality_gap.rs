@@ -0,0 +1,57 @@
+//! Measures the running time and the gap to the optimal tour of the multi-start nearest neighbor
+//! heuristic, serial and with all available threads. With the `rayon` feature, the start nodes
+//! are balanced by work stealing instead of being split into one chunk per thread.
+
+use std::thread::available_parallelism;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node,
+};
+use tsp_parser::parse_tsp_instance;
+use tsp_solvers::local_search::multi_start_nearest_neighbor;
+
+/// Instances with their optimal tour lengths, see `tests/test_assets/tour_lengths/solutions.txt`.
+const INSTANCES: [(&str, Distance); 4] = [
+    ("eil51", Distance(426)),
+    ("berlin52", Distance(7542)),
+    ("kroA100", Distance(21282)),
+    ("a280", Distance(2579)),
+];
+
+fn quality_gap_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quality_gap");
+    group.sample_size(10);
+
+    let threads = available_parallelism().map_or(1, |threads| threads.get());
+    let mut thread_counts = vec![1];
+    if threads > 1 {
+        thread_counts.push(threads);
+    }
+    let fixed = FixedEdgeSet::new();
+
+    for (name, optimum) in INSTANCES {
+        let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+            parse_tsp_instance(format!("../../instances/tsplib_symmetric/{name}.tsp")).unwrap();
+        let distances = tsp_instance.distance_matrix();
+        let starts: Vec<Node> = (0..distances.dimension()).map(Node).collect();
+
+        let tour = multi_start_nearest_neighbor(distances, &starts, threads, &fixed);
+        println!("{name}: {}", tour.to_un_tour(distances).report(optimum));
+
+        for &thread_count in &thread_counts {
+            let label = if thread_count == 1 {
+                format!("Multi-start nearest neighbor on {name} (serial)")
+            } else {
+                format!("Multi-start nearest neighbor on {name} ({thread_count} threads)")
+            };
+            group.bench_function(label, |b| {
+                b.iter(|| multi_start_nearest_neighbor(distances, &starts, thread_count, &fixed))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(quality_gap, quality_gap_benchmark);
+criterion_main!(quality_gap);
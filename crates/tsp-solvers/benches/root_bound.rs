@@ -0,0 +1,38 @@
+use std::thread::available_parallelism;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix};
+use tsp_parser::parse_tsp_instance;
+use tsp_solvers::held_karp_mod::{SubgradientParams, held_karp_bound_with_params};
+
+fn root_bound_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("root_bound");
+    group.sample_size(10);
+
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/pr1002.tsp").unwrap();
+    let threads = available_parallelism().map_or(1, |threads| threads.get());
+
+    let mut thread_counts = vec![1];
+    if threads > 1 {
+        thread_counts.push(threads);
+    }
+    for one_tree_threads in thread_counts {
+        let params = SubgradientParams {
+            one_tree_threads,
+            ..SubgradientParams::default()
+        };
+        let name = if one_tree_threads == 1 {
+            "Root bound of pr1002 (serial)".to_string()
+        } else {
+            format!("Root bound of pr1002 ({one_tree_threads} threads)")
+        };
+        group.bench_function(name, |b| {
+            b.iter(|| held_karp_bound_with_params(tsp_instance.distance_matrix(), &params).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(root_bound, root_bound_benchmark);
+criterion_main!(root_bound);
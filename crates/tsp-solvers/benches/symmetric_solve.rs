@@ -0,0 +1,46 @@
+//! Compares solving a symmetric instance with Held-Karp when it is parsed into a full matrix
+//! directly and when it is parsed into a symmetric matrix, which has to be converted first.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_parser::parse_tsp_instance;
+use tsp_solvers::{HeldKarpConfig, construction::nearest_neighbor, held_karp_with_initial_tour};
+
+/// Solves the instance, warm started with a nearest neighbor tour to keep the search small.
+fn solve(distances: &Matrix<Distance>) -> Distance {
+    held_karp_with_initial_tour(distances, &HeldKarpConfig::default(), |distances| {
+        nearest_neighbor(distances, Node(0)).to_un_tour(distances)
+    })
+    .unwrap()
+    .tour
+    .cost
+}
+
+fn symmetric_solve_benchmark(c: &mut Criterion) {
+    let instance_path = "../../instances/tsplib_symmetric/att48.tsp";
+    let full: TSPSymInstance<Matrix<Distance>> = parse_tsp_instance(instance_path).unwrap();
+    let symmetric: TSPSymInstance<MatrixSym<Distance>> = parse_tsp_instance(instance_path).unwrap();
+    let full = full.distance_matrix();
+    let symmetric = symmetric.distance_matrix();
+
+    let mut group = c.benchmark_group("symmetric_solve_att48");
+    group.sample_size(10);
+    group.bench_function("conversion to full matrix", |b| {
+        b.iter(|| symmetric.to_non_symmetric());
+    });
+    group.bench_function("solve full matrix", |b| {
+        b.iter(|| solve(full));
+    });
+    group.bench_function("solve symmetric matrix including conversion", |b| {
+        b.iter(|| solve(&symmetric.to_non_symmetric()));
+    });
+    group.finish();
+}
+
+criterion_group!(symmetric_solve, symmetric_solve_benchmark);
+criterion_main!(symmetric_solve);
@@ -0,0 +1,55 @@
+//! Compares the lower- and upper-triangular layouts of symmetric matrices in Prim's algorithm.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{MatrixSym, UpperTriangular},
+};
+use tsp_parser::parse_tsp_instance;
+use tsp_solvers::spanning_tree::minimum_spanning_tree;
+
+use crate::common::prim;
+
+mod common;
+
+fn triangular_layout_benchmark(c: &mut Criterion) {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        parse_tsp_instance("../../instances/tsplib_symmetric/d1291.tsp").unwrap();
+    let lower = instance.distance_matrix();
+    let dimension = lower.dimension();
+    let upper = MatrixSym::<_, UpperTriangular>::new_from_distance_function_with_layout(
+        dimension,
+        |from, to| lower.get_data(from, to),
+    );
+
+    let mut group = c.benchmark_group("triangular_layout_d1291");
+    group.bench_function("lower triangle minimum_spanning_tree", |b| {
+        b.iter(|| minimum_spanning_tree(lower))
+    });
+    group.bench_function("upper triangle minimum_spanning_tree", |b| {
+        b.iter(|| minimum_spanning_tree(&upper))
+    });
+    group.bench_function("lower triangle row_into", |b| {
+        let mut buffer = Vec::with_capacity(dimension);
+        b.iter(|| {
+            prim(dimension, |from, visit| {
+                lower.row_into(from, &mut buffer);
+                visit(&buffer);
+            })
+        });
+    });
+    group.bench_function("upper triangle row_into", |b| {
+        let mut buffer = Vec::with_capacity(dimension);
+        b.iter(|| {
+            prim(dimension, |from, visit| {
+                upper.row_into(from, &mut buffer);
+                visit(&buffer);
+            })
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(triangular_layout, triangular_layout_benchmark);
+criterion_main!(triangular_layout);
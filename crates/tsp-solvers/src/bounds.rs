@@ -0,0 +1,406 @@
+/*!
+Lower bounds on the cost of optimal tours, which do not require running a full solver.
+*/
+
+use std::borrow::Cow;
+
+use tsp_core::instance::{
+    distance::{Distance, DistanceSource, ScaledDistance},
+    edge::UnEdge,
+    matrix::Matrix,
+    node::Node,
+};
+
+use crate::{
+    SolverError,
+    feasibility::check_finite_graph,
+    held_karp_bound,
+    held_karp_mod::{EdgeState, min_one_tree},
+    spanning_tree::minimum_spanning_tree,
+};
+
+/// Lower bounds on the cost of an optimal tour of an instance, as computed by
+/// [`compare_lower_bounds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowerBounds {
+    /// Cost of a minimum spanning tree.
+    pub minimum_spanning_tree: Distance,
+    /// Cost of a minimum 1-tree without node penalties.
+    pub one_tree: Distance,
+    /// Held-Karp bound, i.e. the best 1-tree bound found by subgradient optimization, see
+    /// [`held_karp_bound`].
+    pub held_karp: Distance,
+    /// Assignment bound, see [`assignment_lower_bound`]. Only computed for asymmetric instances.
+    pub assignment: Option<Distance>,
+}
+
+/// Computes the minimum spanning tree, 1-tree and Held-Karp lower bounds of the instance, as well
+/// as the assignment bound if the distances are asymmetric. The Held-Karp bound is computed with
+/// the given maximum number of subgradient iterations.
+///
+/// The tree based bounds assume symmetric distances. For asymmetric instances, they are computed
+/// on the symmetric distances `min(d(i, j), d(j, i))`, which still yields lower bounds on the cost
+/// of directed tours. For symmetric instances, the bounds satisfy
+/// `minimum_spanning_tree <= one_tree <= held_karp`.
+///
+/// Returns an error if the instance does not have a tour, see [`check_finite_graph`].
+pub fn compare_lower_bounds(
+    distances: &Matrix<Distance>,
+    held_karp_iterations: usize,
+) -> Result<LowerBounds, SolverError> {
+    let is_symmetric = distances.is_symmetric();
+    let symmetric_distances = if is_symmetric {
+        Cow::Borrowed(distances)
+    } else {
+        Cow::Owned(Matrix::new_from_distance_function(
+            distances.dimension(),
+            |from, to| {
+                distances
+                    .get_data(from, to)
+                    .min(distances.get_data(to, from))
+            },
+        ))
+    };
+    let symmetric_distances = symmetric_distances.as_ref();
+    check_finite_graph(symmetric_distances)?;
+
+    let one_tree = one_tree_cost(symmetric_distances)?;
+    let held_karp = if distances.dimension() <= 2 {
+        one_tree
+    } else {
+        held_karp_bound(symmetric_distances, held_karp_iterations)?.bound
+    };
+    Ok(LowerBounds {
+        minimum_spanning_tree: minimum_spanning_tree_cost(symmetric_distances),
+        one_tree,
+        held_karp,
+        assignment: (!is_symmetric).then(|| assignment_lower_bound(distances)),
+    })
+}
+
+/// Computes the cost of a [minimum spanning tree](minimum_spanning_tree) of the (symmetric)
+/// distances, capped at [`Distance::MAX`].
+fn minimum_spanning_tree_cost(distances: &Matrix<Distance>) -> Distance {
+    let total_cost: i64 = minimum_spanning_tree(distances)
+        .iter()
+        .map(|edge| distances.get_data(edge.from, edge.to).0 as i64)
+        .sum();
+    Distance(total_cost.min(Distance::MAX.0 as i64) as i32)
+}
+
+/// Computes the cost of a minimum 1-tree of the (symmetric) distances without node penalties.
+///
+/// With two nodes, the only 1-tree (and tour) uses the edge between them twice.
+fn one_tree_cost(distances: &Matrix<Distance>) -> Result<Distance, SolverError> {
+    let dimension = distances.dimension();
+    if dimension <= 1 {
+        return Ok(Distance(0));
+    }
+    if dimension == 2 {
+        let distance = distances.get_data(Node(0), Node(1)).0 as i64;
+        return Ok(Distance((2 * distance).min(Distance::MAX.0 as i64) as i32));
+    }
+
+    let (_, cost) = compute_one_tree(distances, &vec![Distance(0); dimension])
+        .ok_or(SolverError::NoTourFound)?;
+    Ok(cost)
+}
+
+/// Computes a minimum 1-tree of the (symmetric) distances with the given node penalties and
+/// returns its edges and cost.
+///
+/// The special node of the 1-tree is node 0, see
+/// [`min_one_tree`](crate::held_karp_mod::min_one_tree), which this wraps without requiring scaled
+/// distances and edge states. The cost of each edge is reduced by the penalties of both endpoints
+/// and twice the sum of the penalties is added to the total, so the cost is a lower bound on the
+/// cost of a tour for any penalties. The cost is capped at [`Distance::MAX`].
+///
+/// Returns `None` for instances with less than three nodes, which have no 1-trees without
+/// duplicate edges. The distances and penalties have to fit into the fixed-point representation,
+/// see [`FIXED_POINT_FRACTIONAL_BITS`](tsp_core::instance::distance::FIXED_POINT_FRACTIONAL_BITS).
+///
+/// Panics if the number of penalties does not match the dimension.
+pub fn compute_one_tree(
+    distances: &impl DistanceSource,
+    penalties: &[Distance],
+) -> Option<(Vec<UnEdge>, Distance)> {
+    let dimension = distances.dimension();
+    assert_eq!(
+        penalties.len(),
+        dimension,
+        "There has to be one penalty per node"
+    );
+    if dimension < 3 {
+        return None;
+    }
+
+    let scaled_distances = Matrix::new_from_distance_function(dimension, |from, to| {
+        ScaledDistance::from_distance(distances.distance(from, to))
+    });
+    let edge_states = Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+    let scaled_penalties: Vec<_> = penalties
+        .iter()
+        .map(|&penalty| ScaledDistance::from_distance(penalty))
+        .collect();
+    let one_tree = min_one_tree(&scaled_distances, &edge_states, &scaled_penalties)?;
+
+    let penalty_sum: i64 = penalties.iter().map(|penalty| penalty.0 as i64).sum();
+    let total_cost: i64 = 2 * penalty_sum
+        + one_tree
+            .iter()
+            .map(|edge| {
+                distances.distance(edge.from, edge.to).0 as i64
+                    - penalties[edge.from.0].0 as i64
+                    - penalties[edge.to.0].0 as i64
+            })
+            .sum::<i64>();
+    Some((
+        one_tree,
+        Distance(total_cost.min(Distance::MAX.0 as i64) as i32),
+    ))
+}
+
+/// Computes the [assignment problem](https://en.wikipedia.org/wiki/Assignment_problem) lower bound
+/// on the cost of a (directed) tour using the Hungarian algorithm in `O(n^3)`.
+///
+/// Each node is assigned a successor (other than itself) such that every node is the successor of
+/// exactly one node. Each tour is such an assignment, so the cost of a minimum assignment is a
+/// lower bound on the cost of a tour. In contrast to 1-trees, this bound takes the direction of
+/// edges into account and is thus usually much stronger for asymmetric instances.
+///
+/// The bound is not used by the [Held-Karp search](crate::held_karp_mod), as the search only
+/// considers undirected tours, whose cost the bound of the directed problem does not bound.
+///
+/// The result is capped at [`Distance::MAX`]. Instances with at most one node have a bound of 0.
+pub fn assignment_lower_bound(distances: &Matrix<Distance>) -> Distance {
+    let dimension = distances.dimension();
+    if dimension <= 1 {
+        return Distance(0);
+    }
+
+    // Self loops are not allowed, so the diagonal gets a cost bigger than any possible assignment
+    // without self loops, but still small enough to avoid overflows in the potentials.
+    let self_loop_cost = (Distance::MAX.0 as i64 + 1) * dimension as i64;
+    let cost = |row: usize, column: usize| -> i64 {
+        if row == column {
+            self_loop_cost
+        } else {
+            distances.get_data(Node(row), Node(column)).0 as i64
+        }
+    };
+
+    // The following uses 1-based indices for rows and columns, where index 0 is an artificial
+    // column used to start the search for augmenting paths.
+    // Potentials of the rows
+    let mut row_potentials = vec![0i64; dimension + 1];
+    // Potentials of the columns
+    let mut column_potentials = vec![0i64; dimension + 1];
+    // The row assigned to each column (0 if unassigned)
+    let mut column_assignment = vec![0usize; dimension + 1];
+    // The previous column on the current augmenting path
+    let mut previous_column = vec![0usize; dimension + 1];
+
+    for row in 1..=dimension {
+        column_assignment[0] = row;
+        let mut current_column = 0;
+        let mut min_reduced_cost = vec![i64::MAX; dimension + 1];
+        let mut used = vec![false; dimension + 1];
+
+        // Grow the alternating tree until an unassigned column is reached
+        loop {
+            used[current_column] = true;
+            let current_row = column_assignment[current_column];
+            let mut delta = i64::MAX;
+            let mut next_column = 0;
+
+            for column in 1..=dimension {
+                if used[column] {
+                    continue;
+                }
+                let reduced_cost = cost(current_row - 1, column - 1)
+                    - row_potentials[current_row]
+                    - column_potentials[column];
+                if reduced_cost < min_reduced_cost[column] {
+                    min_reduced_cost[column] = reduced_cost;
+                    previous_column[column] = current_column;
+                }
+                if min_reduced_cost[column] < delta {
+                    delta = min_reduced_cost[column];
+                    next_column = column;
+                }
+            }
+
+            for column in 0..=dimension {
+                if used[column] {
+                    row_potentials[column_assignment[column]] += delta;
+                    column_potentials[column] -= delta;
+                } else {
+                    min_reduced_cost[column] -= delta;
+                }
+            }
+
+            current_column = next_column;
+            if column_assignment[current_column] == 0 {
+                break;
+            }
+        }
+
+        // Augment along the path found
+        while current_column != 0 {
+            let column = previous_column[current_column];
+            column_assignment[current_column] = column_assignment[column];
+            current_column = column;
+        }
+    }
+
+    let total_cost: i64 = (1..=dimension)
+        .map(|column| cost(column_assignment[column] - 1, column - 1))
+        .sum();
+
+    Distance(total_cost.min(Distance::MAX.0 as i64) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the cost of an optimal directed tour by trying all permutations starting at node 0.
+    fn brute_force_directed_tour(distances: &Matrix<Distance>) -> Distance {
+        fn extend(
+            distances: &Matrix<Distance>,
+            path: &mut Vec<usize>,
+            visited: &mut [bool],
+            best: &mut i32,
+        ) {
+            let dimension = distances.dimension();
+            if path.len() == dimension {
+                let cost: i32 = (0..dimension)
+                    .map(|i| {
+                        distances
+                            .get_data(Node(path[i]), Node(path[(i + 1) % dimension]))
+                            .0
+                    })
+                    .sum();
+                *best = (*best).min(cost);
+                return;
+            }
+            for next in 1..dimension {
+                if !visited[next] {
+                    visited[next] = true;
+                    path.push(next);
+                    extend(distances, path, visited, best);
+                    path.pop();
+                    visited[next] = false;
+                }
+            }
+        }
+
+        let mut best = i32::MAX;
+        let mut visited = vec![false; distances.dimension()];
+        visited[0] = true;
+        extend(distances, &mut vec![0], &mut visited, &mut best);
+        Distance(best)
+    }
+
+    #[test]
+    fn test_assignment_lower_bound_small() {
+        // The optimal assignment consists of the two cycles 0 -> 1 -> 0 and 2 -> 3 -> 2, which is
+        // cheaper than any tour
+        #[rustfmt::skip]
+        let data = vec![
+            0, 1, 9, 9,
+            1, 0, 9, 9,
+            9, 9, 0, 1,
+            9, 9, 1, 0,
+        ];
+        let distances = Matrix::new(data.into_iter().map(Distance).collect(), 4);
+        assert_eq!(assignment_lower_bound(&distances), Distance(4));
+        assert_eq!(brute_force_directed_tour(&distances), Distance(20));
+    }
+
+    #[test]
+    fn test_assignment_lower_bound_below_optimal_tour() {
+        // Deterministic pseudo-random asymmetric matrices
+        let mut state = 42u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 100) as i32
+        };
+
+        for dimension in 2..=7 {
+            let data = (0..dimension * dimension)
+                .map(|_| Distance(next()))
+                .collect();
+            let distances = Matrix::new(data, dimension);
+            let bound = assignment_lower_bound(&distances);
+            let optimum = brute_force_directed_tour(&distances);
+            assert!(
+                bound <= optimum,
+                "Bound {bound:?} exceeds optimum {optimum:?}"
+            );
+
+            // Reversing all arcs preserves both the optimum and the bound
+            let transposed = distances.transpose();
+            assert_eq!(brute_force_directed_tour(&transposed), optimum);
+            assert_eq!(assignment_lower_bound(&transposed), bound);
+        }
+    }
+
+    #[test]
+    fn test_assignment_lower_bound_trivial_instances() {
+        assert_eq!(assignment_lower_bound(&Matrix::new(vec![], 0)), Distance(0));
+        assert_eq!(
+            assignment_lower_bound(&Matrix::new(vec![Distance(5)], 1)),
+            Distance(0)
+        );
+    }
+
+    #[test]
+    fn test_compare_lower_bounds_below_optimal_tour() {
+        let mut state = 7u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 100) as i32 + 1
+        };
+
+        for dimension in 3..=7 {
+            let asymmetric = Matrix::new(
+                (0..dimension * dimension)
+                    .map(|_| Distance(next()))
+                    .collect(),
+                dimension,
+            );
+            let symmetric = Matrix::new_from_distance_function(dimension, |from, to| {
+                asymmetric.get_data(from.min(to), from.max(to))
+            });
+
+            let bounds = compare_lower_bounds(&symmetric, 100).unwrap();
+            let optimum = brute_force_directed_tour(&symmetric);
+            assert_eq!(bounds.assignment, None);
+            assert!(bounds.minimum_spanning_tree <= bounds.one_tree);
+            assert!(bounds.one_tree <= bounds.held_karp);
+            assert!(bounds.held_karp <= optimum, "{bounds:?} vs {optimum:?}");
+
+            let bounds = compare_lower_bounds(&asymmetric, 100).unwrap();
+            let optimum = brute_force_directed_tour(&asymmetric);
+            assert_eq!(bounds.assignment, Some(assignment_lower_bound(&asymmetric)));
+            assert!(bounds.held_karp <= optimum, "{bounds:?} vs {optimum:?}");
+            assert!(bounds.assignment.unwrap() <= optimum);
+        }
+    }
+
+    #[test]
+    fn test_compare_lower_bounds_trivial_instances() {
+        for dimension in 0..=2 {
+            let distances = Matrix::new_from_dimension_with_value(dimension, Distance(3));
+            let bounds = compare_lower_bounds(&distances, 10).unwrap();
+            let tour_cost = Distance(if dimension == 2 { 6 } else { 0 });
+            assert_eq!(bounds.one_tree, tour_cost);
+            assert_eq!(bounds.held_karp, tour_cost);
+        }
+    }
+}
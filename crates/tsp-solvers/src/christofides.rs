@@ -0,0 +1,143 @@
+/*!
+This module contains an implementation of the
+[Christofides algorithm](https://en.wikipedia.org/wiki/Christofides_algorithm) for constructing
+tours.
+
+## Top-level Description of the Algorithm
+
+1. Compute a [minimum spanning tree](minimum_spanning_tree) of all nodes.
+2. Compute a perfect matching on the nodes with odd degree in the spanning tree.
+3. Combine the spanning tree and the matching into a multigraph in which all nodes have even
+   degree and compute an Eulerian circuit of it.
+4. Shortcut the Eulerian circuit by skipping nodes which have already been visited.
+
+If the matching in step 2 is a minimum weight perfect matching and the distances satisfy the
+triangle inequality, the resulting tour is at most 1.5 times as long as an optimal tour. Computing
+such a matching requires the blossom algorithm, which is not implemented yet. Instead, the matching
+can be chosen via [`MatchingStrategy`], which currently only offers a greedy matching.
+*/
+
+use alloc::{vec, vec::Vec};
+
+use tsp_core::instance::{distance::Distance, matrix::Matrix, node::Node, tour_order::TourOrder};
+
+use crate::{matching::greedy_perfect_matching, spanning_tree::minimum_spanning_tree};
+
+/// Strategy to compute the perfect matching on the odd-degree nodes of the spanning tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchingStrategy {
+    /// Repeatedly match the two closest unmatched nodes, see [`greedy_perfect_matching`].
+    ///
+    /// This is simple and fast, but the matching is not necessarily of minimum weight. Thus, the
+    /// approximation guarantee of 1.5 is lost.
+    #[default]
+    Greedy,
+}
+
+/// Construct a tour with the Christofides algorithm using the greedy matching.
+///
+/// See [`christofides`] and [`MatchingStrategy::Greedy`] for details.
+pub fn christofides_greedy(distances: &Matrix<Distance>) -> TourOrder {
+    christofides(distances, MatchingStrategy::Greedy)
+}
+
+/// Construct a tour with the Christofides algorithm, computing the matching with the given
+/// strategy.
+///
+/// The distances are assumed to be symmetric. For a detailed explanation of the algorithm, see the
+/// [module-level documentation][crate::christofides].
+pub fn christofides(
+    distances: &Matrix<Distance>,
+    matching_strategy: MatchingStrategy,
+) -> TourOrder {
+    let dimension = distances.dimension();
+    if dimension <= 2 {
+        return TourOrder::identity(dimension);
+    }
+
+    let spanning_tree = minimum_spanning_tree(distances);
+
+    let mut adjacency_lists = vec![Vec::new(); dimension];
+    for edge in spanning_tree {
+        adjacency_lists[edge.from.0].push(edge.to);
+        adjacency_lists[edge.to.0].push(edge.from);
+    }
+
+    let odd_degree_nodes: Vec<Node> = (0..dimension)
+        .filter(|&node| !adjacency_lists[node].len().is_multiple_of(2))
+        .map(Node)
+        .collect();
+
+    let matching = match matching_strategy {
+        MatchingStrategy::Greedy => greedy_perfect_matching(&odd_degree_nodes, distances),
+    };
+    for edge in matching {
+        adjacency_lists[edge.from.0].push(edge.to);
+        adjacency_lists[edge.to.0].push(edge.from);
+    }
+
+    let eulerian_circuit = eulerian_circuit(adjacency_lists);
+
+    // Shortcut the circuit by only keeping the first occurrence of each node
+    let mut visited = vec![false; dimension];
+    let tour = eulerian_circuit
+        .into_iter()
+        .filter(|node| !core::mem::replace(&mut visited[node.0], true))
+        .collect();
+
+    TourOrder::new(tour)
+}
+
+/// Compute an Eulerian circuit starting at node 0 using Hierholzer's algorithm.
+///
+/// The multigraph is given by adjacency lists, in which each edge appears in the lists of both its
+/// endpoints. All nodes are required to have even degree and the graph to be connected.
+fn eulerian_circuit(mut adjacency_lists: Vec<Vec<Node>>) -> Vec<Node> {
+    let number_of_edges = adjacency_lists.iter().map(Vec::len).sum::<usize>() / 2;
+    let mut circuit = Vec::with_capacity(number_of_edges + 1);
+    let mut stack = vec![Node(0)];
+
+    while let Some(&current) = stack.last() {
+        if let Some(next) = adjacency_lists[current.0].pop() {
+            // Remove the reverse direction of the edge as well
+            let reverse_index = adjacency_lists[next.0]
+                .iter()
+                .position(|&node| node == current)
+                .expect("Each edge should appear in the adjacency lists of both endpoints");
+            adjacency_lists[next.0].swap_remove(reverse_index);
+            stack.push(next);
+        } else {
+            circuit.push(current);
+            stack.pop();
+        }
+    }
+
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_christofides_small_instances() {
+        for dimension in 0..6 {
+            let distances = Matrix::new_from_distance_function(dimension, |from, to| {
+                Distance(from.0.abs_diff(to.0) as i32)
+            });
+            let tour = christofides_greedy(&distances);
+            assert_eq!(tour.len(), dimension);
+        }
+    }
+
+    #[test]
+    fn test_christofides_points_on_line() {
+        // Nodes on a line in shuffled order, the optimal tour goes there and back again
+        let positions: [i32; 6] = [3, 0, 5, 1, 4, 2];
+        let distances = Matrix::new_from_distance_function(positions.len(), |from, to| {
+            Distance((positions[from.0] - positions[to.0]).abs())
+        });
+        let tour = christofides_greedy(&distances);
+        assert_eq!(tour.cost(&distances), Distance(10));
+    }
+}
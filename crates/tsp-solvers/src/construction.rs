@@ -0,0 +1,758 @@
+/*!
+Simple heuristics for constructing tours from scratch.
+
+- [`nearest_neighbor`]: Starting at a node, repeatedly visit the closest unvisited node. Runs in
+  `O(n^2)` and usually yields tours about 25% longer than optimal on Euclidean instances.
+  [`nearest_neighbor_knn`] constructs the same tour, but checks precomputed
+  [neighbor lists](crate::neighbor_lists) first, which takes about `O(n * k)` for large instances.
+- [`cheapest_insertion`]: Starting with a single node, repeatedly insert the node which increases
+  the cost of the partial tour the least. Runs in `O(n^2)` in practice and usually yields tours
+  about 10-20% longer than optimal on Euclidean instances. If the distances satisfy the triangle
+  inequality, the tours are at most twice as long as optimal ones.
+- [`greedy_edge`]: Repeatedly add the cheapest edge which keeps the partial tour a set of paths.
+  Only the edges to the nearest neighbors are considered, the resulting paths are joined by a
+  nearest neighbor search on their endpoints. Runs in about `O(n * k log(n * k))` plus `O(n)` per
+  joined path and usually yields tours about 15-20% longer than optimal on Euclidean instances.
+- [`double_tree`]: Visit the nodes in the depth-first preorder of a minimum spanning tree. Runs in
+  `O(n^2)` and usually yields tours about 30-40% longer than optimal on Euclidean instances. If
+  the distances satisfy the triangle inequality, the tours are at most twice as long as optimal
+  ones.
+- [`beam_search`]: Like nearest neighbor, extend paths from a start node one node at a time, but
+  keep the `w` cheapest paths instead of only one. Each path is extended by the unvisited nodes
+  in the [neighbor list](crate::neighbor_lists) of its last node. Runs in about `O(n^2 * w)` and
+  often yields better tours than greedy construction for a modest beam width `w`.
+
+The variants [`nearest_neighbor_with_endpoints`] and [`cheapest_insertion_with_endpoints`] fix
+the first and optionally the last node of the tour, or construct an open tour, i.e. a Hamiltonian
+path, see [`Endpoints`].
+
+See also [`christofides`](crate::christofides) for a construction heuristic with a better
+approximation guarantee.
+*/
+
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+
+use tsp_core::instance::{
+    UnTour,
+    distance::{Distance, DistanceSource},
+    matrix::Matrix,
+    node::Node,
+    tour_order::TourOrder,
+};
+
+use crate::{neighbor_lists::NeighborLists, spanning_tree::minimum_spanning_tree};
+
+/// The first and last node of a tour constructed by [`nearest_neighbor_with_endpoints`] or
+/// [`cheapest_insertion_with_endpoints`], and whether the tour is open.
+///
+/// The tour visits `start` first and, if set, `end` last. An open tour is a Hamiltonian path from
+/// `start` to `end`, whose cost omits the edge back to `start`, see [`TourOrder::path_cost`].
+/// For a closed tour, a fixed `end` forces the edge between `end` and `start` into the tour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoints {
+    /// The first node of the tour.
+    pub start: Node,
+    /// The last node of the tour, if it is fixed.
+    pub end: Option<Node>,
+    /// Whether the tour is a path, i.e. does not return from the last node to the first one.
+    pub open: bool,
+}
+
+impl Default for Endpoints {
+    /// A closed tour starting at node 0.
+    fn default() -> Self {
+        Self {
+            start: Node(0),
+            end: None,
+            open: false,
+        }
+    }
+}
+
+impl Endpoints {
+    /// Returns the fixed end node, unless it is the start node of an instance with a single node.
+    ///
+    /// Panics if the start or end node is not a node of the instance, or if they are the same
+    /// node of an instance with more than one node.
+    fn checked_end(&self, dimension: usize) -> Option<Node> {
+        assert!(self.start.0 < dimension, "The start has to be a node");
+        let end = self.end.filter(|&end| end != self.start || dimension > 1)?;
+        assert!(end.0 < dimension, "The end has to be a node");
+        assert_ne!(
+            end, self.start,
+            "The start and end have to be different nodes"
+        );
+        Some(end)
+    }
+}
+
+/// Construct a tour with the nearest neighbor heuristic, starting at the given node.
+///
+/// See the [module-level documentation][crate::construction] for details.
+///
+/// Panics if the start node is not a node of the instance (unless the instance is empty).
+pub fn nearest_neighbor(distances: &Matrix<Distance>, start: Node) -> TourOrder {
+    nearest_neighbor_with_endpoints(
+        distances,
+        Endpoints {
+            start,
+            ..Endpoints::default()
+        },
+    )
+}
+
+/// Construct a tour with the nearest neighbor heuristic, starting at the start node of the
+/// endpoints and visiting their end node (if any) last.
+///
+/// The end node is skipped by the nearest neighbor search and appended to the tour. Whether the
+/// tour is open does not change the order of the nodes, only its cost, see [`Endpoints`].
+///
+/// Panics if the endpoints are not nodes of the instance (unless the instance is empty), see
+/// [`Endpoints`].
+pub fn nearest_neighbor_with_endpoints(
+    distances: &Matrix<Distance>,
+    endpoints: Endpoints,
+) -> TourOrder {
+    let dimension = distances.dimension();
+    if dimension == 0 {
+        return TourOrder::identity(0);
+    }
+    let end = endpoints.checked_end(dimension);
+
+    let mut visited = vec![false; dimension];
+    let mut tour = Vec::with_capacity(dimension);
+    let mut current = endpoints.start;
+    visited[current.0] = true;
+    tour.push(current);
+    if let Some(end) = end {
+        visited[end.0] = true;
+    }
+
+    let number_of_searched_nodes = dimension - usize::from(end.is_some());
+    for _ in 1..number_of_searched_nodes {
+        let (next, _) = distances
+            .get_adjacency_list(current)
+            .iter()
+            .enumerate()
+            .filter(|&(node, _)| !visited[node])
+            .min_by_key(|&(_, &distance)| distance)
+            .expect("There is always an unvisited node left");
+        current = Node(next);
+        visited[current.0] = true;
+        tour.push(current);
+    }
+    tour.extend(end);
+
+    TourOrder::new(tour)
+}
+
+/// Construct the same tour as [`nearest_neighbor`], but look for the closest unvisited node in
+/// the neighbor lists of the current node first.
+///
+/// Only if all `k` neighbors of the current node are visited, all nodes are scanned. As this
+/// rarely happens for large instances, this takes about `O(n * k)` instead of `O(n^2)`, in
+/// addition to computing the neighbor lists once.
+///
+/// Panics if the start node is not a node of the instance (unless the instance is empty).
+pub fn nearest_neighbor_knn(
+    distances: &Matrix<Distance>,
+    neighbors: &NeighborLists,
+    start: Node,
+) -> TourOrder {
+    let dimension = distances.dimension();
+    if dimension == 0 {
+        return TourOrder::identity(0);
+    }
+
+    let mut visited = vec![false; dimension];
+    let mut tour = Vec::with_capacity(dimension);
+    let mut current = start;
+    visited[current.0] = true;
+    tour.push(current);
+
+    for _ in 1..dimension {
+        // The neighbor lists are sorted like the full scan, so the first unvisited neighbor is
+        // the node the full scan would find
+        let next = match neighbors
+            .neighbors(current)
+            .iter()
+            .find(|neighbor| !visited[neighbor.0])
+        {
+            Some(&neighbor) => neighbor,
+            None => distances
+                .get_adjacency_list(current)
+                .iter()
+                .enumerate()
+                .filter(|&(node, _)| !visited[node])
+                .min_by_key(|&(_, &distance)| distance)
+                .map(|(node, _)| Node(node))
+                .expect("There is always an unvisited node left"),
+        };
+        current = next;
+        visited[current.0] = true;
+        tour.push(current);
+    }
+
+    TourOrder::new(tour)
+}
+
+/// Construct a tour with the cheapest insertion heuristic, starting with node 0.
+///
+/// For each node not yet in the tour, the cheapest edge of the partial tour to insert it into is
+/// tracked. After an insertion, only the nodes whose cheapest edge got removed have to be
+/// recomputed from scratch, while all other nodes only need to consider the two new edges.
+///
+/// The distances are assumed to be symmetric. See the [module-level
+/// documentation][crate::construction] for details.
+pub fn cheapest_insertion(distances: &Matrix<Distance>) -> TourOrder {
+    cheapest_insertion_with_endpoints(distances, Endpoints::default())
+}
+
+/// Construct a tour with the cheapest insertion heuristic, starting with the start node of the
+/// endpoints, or with the edge between the start and end node if the end is fixed.
+///
+/// The edge from the last node back to the start is the closing edge. Nodes are never inserted
+/// into the closing edge of a tour with a fixed end, so the end stays last. For an open tour
+/// without a fixed end, inserting a node into the closing edge appends it to the path and only
+/// costs the edge from the last node to it.
+///
+/// The distances are assumed to be symmetric. Panics if the endpoints are not nodes of the
+/// instance (unless the instance is empty), see [`Endpoints`].
+pub fn cheapest_insertion_with_endpoints(
+    distances: &Matrix<Distance>,
+    endpoints: Endpoints,
+) -> TourOrder {
+    let dimension = distances.dimension();
+    if dimension == 0 {
+        return TourOrder::identity(0);
+    }
+    let start = endpoints.start;
+    let end = endpoints.checked_end(dimension);
+
+    let insertion_cost = |from: Node, node: Node, to: Node| match (to == start, end) {
+        (true, Some(_)) => Distance::MAX,
+        (true, None) if endpoints.open => distances.get_data(from, node),
+        _ => {
+            distances.get_data(from, node) + distances.get_data(node, to)
+                - distances.get_data(from, to)
+        }
+    };
+
+    // The partial tour as successors of the nodes in the tour, starting with the self loop of
+    // the start node, or the cycle of the start and end node
+    let mut successors = vec![start; dimension];
+    let mut tour_nodes = Vec::with_capacity(dimension);
+    tour_nodes.push(start);
+    let mut in_tour = vec![false; dimension];
+    in_tour[start.0] = true;
+    if let Some(end) = end {
+        successors[start.0] = end;
+        tour_nodes.push(end);
+        in_tour[end.0] = true;
+    }
+
+    // For each node not in the tour, the cost of its cheapest insertion and the start of the edge
+    // it is inserted into
+    let mut best_insertion: Vec<(Distance, Node)> = (0..dimension)
+        .map(|node| {
+            tour_nodes
+                .iter()
+                .map(|&from| (insertion_cost(from, Node(node), successors[from.0]), from))
+                .min()
+                .expect("The tour is not empty")
+        })
+        .collect();
+
+    for _ in tour_nodes.len()..dimension {
+        let node = (0..dimension)
+            .filter(|&node| !in_tour[node])
+            .min_by_key(|&node| best_insertion[node].0)
+            .map(Node)
+            .expect("There is always a node left to insert");
+        let from = best_insertion[node.0].1;
+        let to = successors[from.0];
+
+        successors[node.0] = to;
+        successors[from.0] = node;
+        tour_nodes.push(node);
+        in_tour[node.0] = true;
+
+        for other in (0..dimension).filter(|&other| !in_tour[other]).map(Node) {
+            if best_insertion[other.0].1 == from {
+                // The cheapest edge of this node has been removed, so recompute from scratch
+                best_insertion[other.0] = tour_nodes
+                    .iter()
+                    .map(|&start| (insertion_cost(start, other, successors[start.0]), start))
+                    .min()
+                    .expect("The tour is not empty");
+            } else {
+                for start in [from, node] {
+                    let cost = insertion_cost(start, other, successors[start.0]);
+                    if cost < best_insertion[other.0].0 {
+                        best_insertion[other.0] = (cost, start);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut tour = Vec::with_capacity(dimension);
+    let mut current = start;
+    for _ in 0..dimension {
+        tour.push(current);
+        current = successors[current.0];
+    }
+
+    TourOrder::new(tour)
+}
+
+/// Construct a tour with the greedy edge heuristic.
+///
+/// The edges between each node and the nodes in its neighbor list are added in order of
+/// increasing distance, skipping edges which would give a node more than two incident edges or
+/// close a cycle. This leaves a set of paths (possibly single nodes), as edges to more distant
+/// nodes are never considered. Starting at an endpoint of some path, the paths are then joined
+/// into a tour by traversing the current path and continuing at the closest endpoint of a path
+/// not visited yet.
+///
+/// Ties between edges of the same distance are broken by their nodes, so the tour is
+/// deterministic. See the [module-level documentation][crate::construction] for details.
+pub fn greedy_edge(distances: &Matrix<Distance>, neighbors: &NeighborLists) -> TourOrder {
+    let dimension = distances.dimension();
+    if dimension < 3 {
+        return TourOrder::identity(dimension);
+    }
+
+    // The candidate edges in order of increasing distance, each one only once
+    let mut candidates: Vec<_> = (0..dimension)
+        .map(Node)
+        .flat_map(|from| {
+            neighbors.neighbors(from).iter().map(move |&to| {
+                let (from, to) = if from < to { (from, to) } else { (to, from) };
+                (distances.get_data(from, to), from, to)
+            })
+        })
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    // The at most two neighbors of each node in the paths, and a union-find forest of the paths
+    let mut adjacent = vec![[None; 2]; dimension];
+    let mut degrees = vec![0; dimension];
+    let mut parents: Vec<usize> = (0..dimension).collect();
+    for (_, from, to) in candidates {
+        if degrees[from.0] == 2 || degrees[to.0] == 2 {
+            continue;
+        }
+        let (from_root, to_root) = (
+            find_root(&mut parents, from.0),
+            find_root(&mut parents, to.0),
+        );
+        if from_root == to_root {
+            continue;
+        }
+        parents[from_root] = to_root;
+        adjacent[from.0][degrees[from.0]] = Some(to);
+        adjacent[to.0][degrees[to.0]] = Some(from);
+        degrees[from.0] += 1;
+        degrees[to.0] += 1;
+    }
+
+    let mut visited = vec![false; dimension];
+    let mut tour = Vec::with_capacity(dimension);
+    // There are less than n edges, so some node is the endpoint of a path
+    let mut current = Node(
+        degrees
+            .iter()
+            .position(|&degree| degree < 2)
+            .expect("The paths contain less edges than nodes"),
+    );
+    loop {
+        // Traverse the path starting at its endpoint `current`
+        visited[current.0] = true;
+        tour.push(current);
+        while let Some(next) = adjacent[current.0]
+            .iter()
+            .flatten()
+            .copied()
+            .find(|next| !visited[next.0])
+        {
+            current = next;
+            visited[current.0] = true;
+            tour.push(current);
+        }
+        if tour.len() == dimension {
+            break;
+        }
+
+        // Continue at the closest endpoint of another path
+        let (next, _) = distances
+            .get_adjacency_list(current)
+            .iter()
+            .enumerate()
+            .filter(|&(node, _)| !visited[node] && degrees[node] < 2)
+            .min_by_key(|&(_, &distance)| distance)
+            .expect("Paths which are not visited yet have endpoints");
+        current = Node(next);
+    }
+
+    TourOrder::new(tour)
+}
+
+/// Returns the root of the tree containing the node in the union-find forest given by the parents
+/// of the nodes, halving the path to the root on the way.
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+/// Construct a tour with the double-tree heuristic, starting at node 0.
+///
+/// Doubling the edges of a [minimum spanning tree](minimum_spanning_tree) gives a closed walk
+/// visiting all nodes, which is traversed by a depth-first search of the tree. Shortcutting the
+/// nodes visited before yields the preorder of the search as the tour. If the distances satisfy
+/// the triangle inequality, shortcuts do not increase the cost, so the tour costs at most twice
+/// the spanning tree and thus at most twice the optimal tour.
+///
+/// The distances are assumed to be symmetric. See the [module-level
+/// documentation][crate::construction] for details.
+pub fn double_tree(distances: &impl DistanceSource) -> UnTour {
+    let dimension = distances.dimension();
+    let mut adjacency_lists = vec![Vec::new(); dimension];
+    for edge in minimum_spanning_tree(distances) {
+        adjacency_lists[edge.from.0].push(edge.to);
+        adjacency_lists[edge.to.0].push(edge.from);
+    }
+
+    let mut tour = Vec::with_capacity(dimension);
+    let mut visited = vec![false; dimension];
+    let mut stack = if dimension == 0 {
+        Vec::new()
+    } else {
+        vec![Node(0)]
+    };
+    while let Some(node) = stack.pop() {
+        if core::mem::replace(&mut visited[node.0], true) {
+            continue;
+        }
+        tour.push(node);
+        // Push in reverse to visit the neighbors in the order they were added to the tree
+        stack.extend(
+            adjacency_lists[node.0]
+                .iter()
+                .rev()
+                .filter(|neighbor| !visited[neighbor.0]),
+        );
+    }
+
+    let edges: Vec<_> = TourOrder::new(tour).edges().collect();
+    let cost = Distance::saturating_sum(
+        edges
+            .iter()
+            .map(|edge| distances.distance(edge.from, edge.to)),
+    );
+    UnTour { edges, cost }
+}
+
+/// A path from the start node in the beam of [`beam_search`].
+#[derive(Debug, Clone)]
+struct BeamPath {
+    nodes: Vec<Node>,
+    visited: Vec<bool>,
+    cost: Distance,
+}
+
+/// Construct a tour with beam search, starting at node 0.
+///
+/// Like [`nearest_neighbor_knn`], paths starting at node 0 are extended by one node at a time.
+/// Instead of following only the closest unvisited node, each path of the beam is extended by
+/// every unvisited node in the neighbor list of its last node, or by the closest unvisited node if
+/// all neighbors are visited, and the `beam_width` cheapest extensions form the next beam. Once
+/// the paths contain all nodes, they are closed and the cheapest tour is returned.
+///
+/// A beam width of 1 yields the tour of [`nearest_neighbor`] starting at node 0, a beam width of 0
+/// is treated as 1. Larger beam widths usually yield cheaper tours, but this is not guaranteed, as
+/// paths which are cheap early on can push paths out of the beam which would have led to cheaper
+/// tours. Takes `O(n * w * (n + k log w))` for a beam width `w`.
+///
+/// See the [module-level documentation][crate::construction] for details.
+pub fn beam_search(
+    distances: &Matrix<Distance>,
+    neighbors: &NeighborLists,
+    beam_width: usize,
+) -> UnTour {
+    let dimension = distances.dimension();
+    if dimension == 0 {
+        return TourOrder::identity(0).to_un_tour(distances);
+    }
+    let beam_width = beam_width.max(1);
+
+    let mut visited = vec![false; dimension];
+    visited[0] = true;
+    let mut beam = vec![BeamPath {
+        nodes: vec![Node(0)],
+        visited,
+        cost: Distance(0),
+    }];
+    // Max-heap of the extensions (cost, index of the path in the beam, next node), such that the
+    // most expensive extension is removed once there are more than `beam_width`
+    let mut extensions = BinaryHeap::with_capacity(beam_width + 1);
+
+    for _ in 1..dimension {
+        for (index, path) in beam.iter().enumerate() {
+            let last = *path
+                .nodes
+                .last()
+                .expect("Paths contain at least the start node");
+            let mut extend = |next: Node| {
+                let cost = path.cost.saturating_add(distances.distance(last, next));
+                extensions.push((cost, index, next));
+                if extensions.len() > beam_width {
+                    extensions.pop();
+                }
+            };
+
+            let mut unvisited_neighbors = neighbors
+                .neighbors(last)
+                .iter()
+                .filter(|neighbor| !path.visited[neighbor.0])
+                .peekable();
+            if unvisited_neighbors.peek().is_some() {
+                unvisited_neighbors.for_each(|&neighbor| extend(neighbor));
+            } else {
+                let (next, _) = distances
+                    .get_adjacency_list(last)
+                    .iter()
+                    .enumerate()
+                    .filter(|&(node, _)| !path.visited[node])
+                    .min_by_key(|&(_, &distance)| distance)
+                    .expect("There is always an unvisited node left");
+                extend(Node(next));
+            }
+        }
+
+        // Sorting makes the order of the beam and thus the tie-breaking deterministic
+        beam = core::mem::take(&mut extensions)
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(cost, index, next)| {
+                let mut path = beam[index].clone();
+                path.nodes.push(next);
+                path.visited[next.0] = true;
+                path.cost = cost;
+                path
+            })
+            .collect();
+    }
+
+    beam.into_iter()
+        .map(|path| TourOrder::new(path.nodes).to_un_tour(distances))
+        .min_by(UnTour::cmp_by_cost)
+        .expect("The beam is never empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::test_support::line_distances;
+
+    use super::*;
+
+    #[test]
+    fn test_construction_small_instances() {
+        for dimension in 0..6 {
+            let positions: Vec<i32> = (0..dimension).rev().collect();
+            let distances = line_distances(&positions);
+            assert_eq!(
+                nearest_neighbor(&distances, Node(0)).len(),
+                dimension as usize
+            );
+            assert_eq!(cheapest_insertion(&distances).len(), dimension as usize);
+            let neighbor_lists = NeighborLists::new(&distances, 2);
+            assert_eq!(
+                greedy_edge(&distances, &neighbor_lists).len(),
+                dimension as usize
+            );
+            if dimension > 0 {
+                let endpoints = Endpoints {
+                    start: Node(0),
+                    end: Some(Node(dimension as usize - 1)),
+                    open: true,
+                };
+                for tour in [
+                    nearest_neighbor_with_endpoints(&distances, endpoints),
+                    cheapest_insertion_with_endpoints(&distances, endpoints),
+                ] {
+                    assert_eq!(tour.len(), dimension as usize);
+                    assert_eq!(tour.nodes().last(), endpoints.end.as_ref());
+                }
+            }
+            let expected_edges = if dimension < 2 { 0 } else { dimension as usize };
+            assert_eq!(double_tree(&distances).len(), expected_edges);
+        }
+    }
+
+    #[test]
+    fn test_nearest_neighbor_points_on_line() {
+        let distances = line_distances(&[0, 3, 1, 2]);
+        let tour = nearest_neighbor(&distances, Node(0));
+        assert_eq!(tour.nodes(), &[Node(0), Node(2), Node(3), Node(1)]);
+    }
+
+    #[test]
+    fn test_nearest_neighbor_with_endpoints() {
+        let distances = line_distances(&[3, 0, 5, 1, 4, 2]);
+        let endpoints = Endpoints {
+            start: Node(1),
+            end: Some(Node(0)),
+            open: true,
+        };
+        let tour = nearest_neighbor_with_endpoints(&distances, endpoints);
+        assert_eq!(
+            tour.nodes(),
+            &[Node(1), Node(3), Node(5), Node(4), Node(2), Node(0)]
+        );
+        assert_eq!(tour.path_cost(&distances), Distance(7));
+    }
+
+    #[test]
+    fn test_cheapest_insertion_with_endpoints() {
+        // Both paths visit the nodes in order along the line
+        let distances = line_distances(&[3, 0, 5, 1, 4, 2]);
+        for end in [Some(Node(2)), None] {
+            let endpoints = Endpoints {
+                start: Node(1),
+                end,
+                open: true,
+            };
+            let tour = cheapest_insertion_with_endpoints(&distances, endpoints);
+            assert_eq!(
+                tour.nodes(),
+                &[Node(1), Node(3), Node(5), Node(0), Node(4), Node(2)]
+            );
+            assert_eq!(tour.path_cost(&distances), Distance(5));
+        }
+
+        // A closed tour with a fixed end contains the edge from the end to the start
+        let endpoints = Endpoints {
+            start: Node(1),
+            end: Some(Node(3)),
+            open: false,
+        };
+        let tour = cheapest_insertion_with_endpoints(&distances, endpoints);
+        assert_eq!(tour.nodes().first(), Some(&Node(1)));
+        assert_eq!(tour.nodes().last(), Some(&Node(3)));
+        assert_eq!(tour.cost(&distances), Distance(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "The start and end have to be different nodes")]
+    fn test_endpoints_same_node() {
+        let distances = line_distances(&[0, 1, 2]);
+        let endpoints = Endpoints {
+            start: Node(1),
+            end: Some(Node(1)),
+            open: true,
+        };
+        nearest_neighbor_with_endpoints(&distances, endpoints);
+    }
+
+    #[test]
+    fn test_nearest_neighbor_knn_same_as_nearest_neighbor() {
+        // Includes ties, which both have to break towards the smaller index
+        let positions = [7, 0, 12, 3, 9, 1, 15, 4, 10, 6, 2, 11, 3, 14];
+        let distances = line_distances(&positions);
+        for k in 0..positions.len() {
+            let neighbor_lists = NeighborLists::new(&distances, k);
+            for start in (0..positions.len()).map(Node) {
+                assert_eq!(
+                    nearest_neighbor_knn(&distances, &neighbor_lists, start),
+                    nearest_neighbor(&distances, start),
+                    "k = {k}, start = {start:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cheapest_insertion_points_on_line() {
+        // Any tour visiting the nodes on a line in order and returning is optimal
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        let tour = cheapest_insertion(&distances);
+        assert_eq!(tour.cost(&distances), Distance(18));
+    }
+
+    #[test]
+    fn test_greedy_edge_points_on_line() {
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        for k in 0..positions.len() {
+            let tour = greedy_edge(&distances, &NeighborLists::new(&distances, k));
+            assert_eq!(tour.len(), positions.len());
+            // With two neighbors, the edges between consecutive nodes along the line are
+            // candidates, which form the optimal path
+            if k >= 2 {
+                assert_eq!(tour.cost(&distances), Distance(18), "k = {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_double_tree_points_on_line() {
+        // The spanning tree is the path along the line, whose preorder is optimal
+        let positions = [3, 0, 5, 1, 4, 2];
+        let distances = line_distances(&positions);
+        let tour = double_tree(&distances);
+        assert_eq!(tour.cost, Distance(10));
+        assert!((0..positions.len()).all(|node| tour.degree(Node(node)) == 2));
+    }
+
+    #[test]
+    fn test_beam_search_width_one_is_nearest_neighbor() {
+        let positions = [7, 0, 12, 3, 9, 1, 15, 4, 10, 6, 2, 11, 3, 14];
+        let distances = line_distances(&positions);
+        for k in [0, 1, 3, positions.len()] {
+            let neighbor_lists = NeighborLists::new(&distances, k);
+            assert_eq!(
+                beam_search(&distances, &neighbor_lists, 1),
+                nearest_neighbor(&distances, Node(0)).to_un_tour(&distances),
+                "k = {k}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_beam_search_wider_beam_never_worse() {
+        // Points on a skewed grid in a scrambled order, with ties in the distances
+        let dimension = 16;
+        let points: Vec<(i32, i32)> = (0..dimension)
+            .map(|i| (i * 7) % dimension)
+            .map(|i| (i % 4 * 10 + i / 4 * 3, i / 4 * 10))
+            .collect();
+        let distances = Matrix::new_from_distance_function(dimension as usize, |from, to| {
+            let (from, to) = (points[from.0], points[to.0]);
+            Distance((from.0 - to.0).abs() + (from.1 - to.1).abs())
+        });
+        let neighbor_lists = NeighborLists::new(&distances, 5);
+
+        let mut previous_cost = Distance::MAX;
+        for beam_width in 1..=32 {
+            let tour = beam_search(&distances, &neighbor_lists, beam_width);
+            assert_eq!(tour.len(), dimension as usize);
+            assert!((0..dimension as usize).all(|node| tour.degree(Node(node)) == 2));
+            assert!(
+                tour.cost <= previous_cost,
+                "beam width {beam_width} yields cost {:?} worse than {previous_cost:?}",
+                tour.cost
+            );
+            previous_cost = tour.cost;
+        }
+        assert!(
+            previous_cost < nearest_neighbor(&distances, Node(0)).cost(&distances),
+            "a wide beam should beat the nearest neighbor tour"
+        );
+    }
+}
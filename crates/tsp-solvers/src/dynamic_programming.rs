@@ -0,0 +1,136 @@
+/*!
+The dynamic programming algorithm of Bellman, Held and Karp for solving small instances exactly.
+
+For each subset `S` of the nodes `1..n` and each node `j` in `S`, the cost of the cheapest path
+starting at node 0, visiting exactly the nodes of `S` and ending at `j` is computed from the
+costs of the subsets `S \ {j}`. The optimal tour is the cheapest of these paths for `S = 1..n`,
+closed by the edge back to node 0.
+
+This takes `O(n^2 * 2^n)` time and `O(n * 2^n)` memory, independent of the distances. Thus, it
+is only feasible for instances with up to about 20 nodes, see [`MAX_DIMENSION`], but then
+usually faster than the [branch-and-bound search](crate::held_karp_mod), which does not need
+exponential memory.
+*/
+
+use alloc::{vec, vec::Vec};
+
+use tsp_core::instance::{UnTour, distance::Distance, edge::UnEdge, matrix::Matrix, node::Node};
+
+use crate::{SolverError, feasibility::check_finite_graph};
+
+/// Largest dimension [`dynamic_programming`] accepts. The table for this dimension takes about
+/// 80 MB.
+pub const MAX_DIMENSION: usize = 20;
+
+/// Solve the Traveling Salesman Problem with the dynamic programming algorithm of Bellman, Held
+/// and Karp.
+///
+/// See the [module-level documentation][crate::dynamic_programming] for details. Like
+/// [`held_karp`](crate::held_karp), edges with distance [`Distance::MAX`] are treated as missing
+/// and instances with at most one node are solved by the empty tour.
+///
+/// Panics if the instance has more than [`MAX_DIMENSION`] nodes.
+pub fn dynamic_programming(distances: &Matrix<Distance>) -> Result<UnTour, SolverError> {
+    let dimension = distances.dimension();
+    assert!(
+        dimension <= MAX_DIMENSION,
+        "Dynamic programming supports at most {MAX_DIMENSION} nodes, got {dimension}"
+    );
+    check_finite_graph(distances)?;
+    if dimension <= 1 {
+        return Ok(UnTour {
+            edges: Vec::new(),
+            cost: Distance(0),
+        });
+    }
+
+    // The subsets only contain the nodes 1..n, node i is represented by bit i - 1. Costs are summed
+    // up as i64, as paths using missing edges would overflow otherwise.
+    let nodes = dimension - 1;
+    let distance = |from: usize, to: usize| i64::from(distances.get_data(Node(from), Node(to)).0);
+    let mut costs = vec![i64::MAX; (1 << nodes) * nodes];
+    for last in 0..nodes {
+        costs[(1 << last) * nodes + last] = distance(0, last + 1);
+    }
+    for subset in 1..1usize << nodes {
+        for last in (0..nodes).filter(|&last| subset & (1 << last) != 0) {
+            let previous_subset = subset & !(1 << last);
+            if previous_subset == 0 {
+                continue;
+            }
+            costs[subset * nodes + last] = (0..nodes)
+                .filter(|&previous| previous_subset & (1 << previous) != 0)
+                .map(|previous| {
+                    costs[previous_subset * nodes + previous] + distance(previous + 1, last + 1)
+                })
+                .min()
+                .expect("The previous subset is not empty");
+        }
+    }
+
+    // Close the cheapest path and walk the table backwards to recover the tour
+    let full_subset = (1 << nodes) - 1;
+    let closed_cost = |last: usize| costs[full_subset * nodes + last] + distance(last + 1, 0);
+    let mut last = (0..nodes)
+        .min_by_key(|&last| closed_cost(last))
+        .expect("There is at least one node besides node 0");
+    let mut edges = vec![UnEdge::new(Node(last + 1), Node(0))];
+    let mut subset = full_subset;
+    while subset != 1 << last {
+        let previous_subset = subset & !(1 << last);
+        let previous = (0..nodes)
+            .filter(|&previous| previous_subset & (1 << previous) != 0)
+            .find(|&previous| {
+                costs[previous_subset * nodes + previous] + distance(previous + 1, last + 1)
+                    == costs[subset * nodes + last]
+            })
+            .expect("The cost of a path is attained by one of its predecessors");
+        edges.push(UnEdge::new(Node(previous + 1), Node(last + 1)));
+        subset = previous_subset;
+        last = previous;
+    }
+    edges.push(UnEdge::new(Node(0), Node(last + 1)));
+
+    let cost = Distance::saturating_sum(
+        edges
+            .iter()
+            .map(|edge| distances.get_data(edge.from, edge.to)),
+    );
+    Ok(UnTour { edges, cost })
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::instance::TSPSymInstance;
+
+    use super::*;
+
+    #[test]
+    fn test_dynamic_programming_matches_held_karp() {
+        for instance_path in [
+            "../../instances/tsp_rust/12.tsp",
+            "../../instances/tsplib_symmetric/burma14.tsp",
+            "../../instances/tsplib_symmetric/gr17.tsp",
+        ] {
+            let instance: TSPSymInstance<Matrix<Distance>> =
+                tsp_parser::parse_tsp_instance(instance_path).unwrap();
+            let distances = instance.distance_matrix();
+
+            let tour = dynamic_programming(distances).unwrap();
+            assert_eq!(tour.len(), distances.dimension());
+            assert!((0..distances.dimension()).all(|node| tour.degree(Node(node)) == 2));
+            assert_eq!(tour.cost, crate::held_karp(distances).unwrap().cost);
+        }
+    }
+
+    #[test]
+    fn test_dynamic_programming_small_instances() {
+        let distances = Matrix::new_from_distance_function(2, |_, _| Distance(3));
+        let tour = dynamic_programming(&distances).unwrap();
+        assert_eq!(tour.cost, Distance(6));
+        assert_eq!(tour.len(), 2);
+
+        let distances = Matrix::new_from_distance_function(1, |_, _| Distance(0));
+        assert!(dynamic_programming(&distances).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use thiserror::Error;
+use tsp_core::instance::{edge::UnEdge, node::Node};
+
+/// Errors that can occur when running one of the solvers.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SolverError {
+    /// The graph consisting of all edges with a finite distance (i.e. not [`Distance::MAX`]) is
+    /// not connected, so no tour using only finite edges exists.
+    ///
+    /// [`Distance::MAX`]: tsp_core::instance::distance::Distance::MAX
+    #[error("The graph of finite edges is not connected")]
+    NotConnected,
+    /// The given node has fewer than two incident finite edges, so a tour would have to use an
+    /// edge with distance [`Distance::MAX`].
+    ///
+    /// [`Distance::MAX`]: tsp_core::instance::distance::Distance::MAX
+    #[error("Node {} has fewer than two finite edges", .0.0)]
+    NodeDegreeTooSmall(Node),
+    /// Removing the given node disconnects the graph of finite edges. Such a graph has no tour,
+    /// and the 1-trees used for lower bounds would have to use an edge with distance
+    /// [`Distance::MAX`].
+    ///
+    /// [`Distance::MAX`]: tsp_core::instance::distance::Distance::MAX
+    #[error("Removing node {} disconnects the graph of finite edges", .0.0)]
+    CutNode(Node),
+    /// Tours or 1-trees of the instance may cost up to the given amount, which exceeds
+    /// [`Distance::MAX`], so their cost would overflow the fixed-point arithmetic of the Held-Karp
+    /// solvers (see [`FIXED_POINT_FRACTIONAL_BITS`]).
+    ///
+    /// [`Distance::MAX`]: tsp_core::instance::distance::Distance::MAX
+    /// [`FIXED_POINT_FRACTIONAL_BITS`]: tsp_core::instance::distance::FIXED_POINT_FRACTIONAL_BITS
+    #[error("Costs of up to {0} overflow the fixed-point arithmetic")]
+    CostOverflow(i64),
+    /// A fixed edge is a self loop or references a node outside of the instance.
+    #[error("Invalid fixed edge between nodes {} and {}", .0.from.0, .0.to.0)]
+    InvalidFixedEdge(UnEdge),
+    /// More than two fixed edges are incident to the given node.
+    #[error("Node {} has more than two fixed edges", .0.0)]
+    FixedDegreeTooLarge(Node),
+    /// The fixed edges contain a cycle which does not visit all nodes.
+    #[error("The fixed edges contain a cycle which does not visit all nodes")]
+    FixedEdgesContainSubtour,
+    /// A candidate edge is a self loop or references a node outside of the instance.
+    #[error("Invalid candidate edge between nodes {} and {}", .0.from.0, .0.to.0)]
+    InvalidCandidateEdge(UnEdge),
+    /// The given node has fewer than two incident candidate (or fixed) edges, so no tour within
+    /// the candidate edges exists.
+    #[error("Node {} has fewer than two candidate edges", .0.0)]
+    CandidateDegreeTooSmall(Node),
+    /// The instance has the first given number of nodes, but the solver supports at most the
+    /// second.
+    #[error("The instance has {0} nodes, but the solver supports at most {1}")]
+    DimensionTooLarge(usize, usize),
+    /// No tour containing all fixed edges (and no missing edges) has been found.
+    #[error("No tour containing all fixed edges exists")]
+    NoTourFound,
+    /// The search state of a [`Checkpoint`](crate::held_karp_mod::checkpoint::Checkpoint) does not
+    /// fit the instance with the given number of nodes, as it belongs to another instance or is
+    /// corrupted.
+    #[error("The checkpoint does not belong to the instance with {0} nodes or is corrupted")]
+    InvalidCheckpoint(usize),
+}
@@ -0,0 +1,324 @@
+/*!
+Checks whether an instance can be solved without using sentinel edges.
+
+Sparse graphs (e.g. derived from Hamiltonian cycle instances) are encoded as full distance
+matrices, where missing edges have the distance [`Distance::MAX`]. The solvers assume that such
+edges are never required, as sums of them overflow. The checks in this module allow rejecting
+such instances before starting a search.
+*/
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use tsp_core::instance::{distance::Distance, edge::UnEdge, matrix::Matrix, node::Node};
+
+use crate::SolverError;
+
+/// Returns whether the graph consisting of all edges with a distance smaller than
+/// [`Distance::MAX`] is connected.
+pub fn is_connected_ignoring_max(distances: &Matrix<Distance>) -> bool {
+    is_connected_ignoring_max_without(distances, None)
+}
+
+/// Checks that the graph of finite edges can contain a tour and that the 1-trees used by the
+/// Held-Karp solvers never have to resort to an edge with distance [`Distance::MAX`].
+///
+/// That is, the graph of finite edges has to be connected, every node needs at least two incident
+/// finite edges and removing node 0 (the special node of the 1-trees) must not disconnect the
+/// graph.
+pub fn check_finite_graph(distances: &Matrix<Distance>) -> Result<(), SolverError> {
+    let dimension = distances.dimension();
+
+    for from in 0..dimension {
+        let finite_degree = distances
+            .get_adjacency_list(Node(from))
+            .iter()
+            .enumerate()
+            .filter(|&(to, &distance)| to != from && distance < Distance::MAX)
+            .count();
+        // Instances with at most two nodes cannot have two distinct neighbors for each node
+        if finite_degree < 2.min(dimension.saturating_sub(1)) {
+            return Err(SolverError::NodeDegreeTooSmall(Node(from)));
+        }
+    }
+
+    if !is_connected_ignoring_max(distances) {
+        return Err(SolverError::NotConnected);
+    }
+
+    if dimension > 2 && !is_connected_ignoring_max_without(distances, Some(Node(0))) {
+        return Err(SolverError::CutNode(Node(0)));
+    }
+
+    Ok(())
+}
+
+/// Checks that the costs of all tours and 1-trees fit into the fixed-point arithmetic of the
+/// Held-Karp solvers, that is, they are at most [`Distance::MAX`].
+///
+/// Every edge of a 1-tree (and thus of a tour) can be charged to one of its endpoints, such that
+/// each node is charged at most once, except for node 0 which is charged twice. So the costs are
+/// bounded by the sum of the largest finite distance incident to each node plus the one of node 0.
+/// The node penalties of the Lagrangian relaxation are not taken into account.
+pub fn check_cost_headroom(distances: &Matrix<Distance>) -> Result<(), SolverError> {
+    let bound = finite_cost_bound(distances);
+    if bound > i64::from(Distance::MAX.0) {
+        return Err(SolverError::CostOverflow(bound));
+    }
+
+    Ok(())
+}
+
+/// Returns an upper bound on the costs of all tours and 1-trees which only use edges with a
+/// distance smaller than [`Distance::MAX`], see [`check_cost_headroom`].
+pub fn finite_cost_bound(distances: &Matrix<Distance>) -> i64 {
+    let largest_finite_distance = |from: usize| {
+        distances
+            .iter_row(Node(from))
+            .map(|(_, distance)| distance)
+            .filter(|&distance| distance < Distance::MAX)
+            .max()
+            .map_or(0, |distance| i64::from(distance.0.max(0)))
+    };
+    let dimension = distances.dimension();
+    if dimension <= 1 {
+        return 0;
+    }
+
+    (0..dimension).map(largest_finite_distance).sum::<i64>() + largest_finite_distance(0)
+}
+
+/// Checks that the given fixed edges can be part of a tour on `dimension` nodes and returns the
+/// number of fixed edges incident to each node.
+///
+/// That is, each edge has to connect two distinct nodes of the instance, no node may have more than
+/// two fixed edges and the fixed edges may not contain a cycle, unless it is a tour visiting all
+/// nodes.
+pub fn check_fixed_edges(
+    dimension: usize,
+    fixed_edges: &[UnEdge],
+) -> Result<Vec<u32>, SolverError> {
+    let mut fixed_degrees = vec![0u32; dimension];
+    // Union-find structure to detect cycles, with path halving
+    let mut parent: Vec<usize> = (0..dimension).collect();
+    let find = |parent: &mut Vec<usize>, mut node: usize| {
+        while parent[node] != node {
+            parent[node] = parent[parent[node]];
+            node = parent[node];
+        }
+        node
+    };
+
+    for (index, &edge) in fixed_edges.iter().enumerate() {
+        if edge.from == edge.to || edge.from.0 >= dimension || edge.to.0 >= dimension {
+            return Err(SolverError::InvalidFixedEdge(edge));
+        }
+
+        for node in [edge.from, edge.to] {
+            fixed_degrees[node.0] += 1;
+            if fixed_degrees[node.0] > 2 {
+                return Err(SolverError::FixedDegreeTooLarge(node));
+            }
+        }
+
+        let root_from = find(&mut parent, edge.from.0);
+        let root_to = find(&mut parent, edge.to.0);
+        if root_from == root_to {
+            // Closing a cycle is only fine, if it is the last edge of a tour
+            let is_tour = index + 1 == dimension && fixed_edges.len() == dimension;
+            if !is_tour {
+                return Err(SolverError::FixedEdgesContainSubtour);
+            }
+        }
+        parent[root_from] = root_to;
+    }
+
+    Ok(fixed_degrees)
+}
+
+/// Checks that the given candidate edges, together with the fixed edges, can contain a tour on
+/// `dimension` nodes, see
+/// [`HeldKarpConfig::candidate_edges`](crate::HeldKarpConfig::candidate_edges).
+///
+/// That is, each candidate edge has to connect two distinct nodes of the instance and every node
+/// needs at least two incident candidate or fixed edges. The fixed edges are assumed to be valid,
+/// see [`check_fixed_edges`].
+pub fn check_candidate_edges(
+    dimension: usize,
+    candidate_edges: &[UnEdge],
+    fixed_edges: &[UnEdge],
+) -> Result<(), SolverError> {
+    let mut neighbors = vec![Vec::new(); dimension];
+    for &edge in candidate_edges {
+        if edge.from == edge.to || edge.from.0 >= dimension || edge.to.0 >= dimension {
+            return Err(SolverError::InvalidCandidateEdge(edge));
+        }
+    }
+    for edge in candidate_edges.iter().chain(fixed_edges) {
+        neighbors[edge.from.0].push(edge.to);
+        neighbors[edge.to.0].push(edge.from);
+    }
+
+    for (node, neighbors) in neighbors.iter_mut().enumerate() {
+        // Duplicate edges do not count twice
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        // Instances with at most two nodes cannot have two distinct neighbors for each node
+        if neighbors.len() < 2.min(dimension.saturating_sub(1)) {
+            return Err(SolverError::CandidateDegreeTooSmall(Node(node)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Breadth-first search over the finite edges, optionally ignoring the given node entirely.
+fn is_connected_ignoring_max_without(distances: &Matrix<Distance>, ignored: Option<Node>) -> bool {
+    let dimension = distances.dimension();
+    let mut visited = vec![false; dimension];
+    if let Some(ignored) = ignored {
+        visited[ignored.0] = true;
+    }
+
+    let Some(start) = (0..dimension).find(|&node| !visited[node]) else {
+        // No nodes (left), which is trivially connected
+        return true;
+    };
+
+    let mut queue = VecDeque::from([Node(start)]);
+    visited[start] = true;
+    let mut number_visited = 1;
+
+    while let Some(current) = queue.pop_front() {
+        for (next, &distance) in distances.get_adjacency_list(current).iter().enumerate() {
+            if !visited[next] && distance < Distance::MAX {
+                visited[next] = true;
+                number_visited += 1;
+                queue.push_back(Node(next));
+            }
+        }
+    }
+
+    number_visited == dimension - usize::from(ignored.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cycle 0 - 1 - ... - (dimension - 1) - 0 with all other edges missing.
+    fn cycle_matrix(dimension: usize) -> Matrix<Distance> {
+        Matrix::new_from_distance_function(dimension, |from, to| {
+            if from.0.abs_diff(to.0) == 1 || from.0.abs_diff(to.0) == dimension - 1 {
+                Distance(1)
+            } else if from == to {
+                Distance(0)
+            } else {
+                Distance::MAX
+            }
+        })
+    }
+
+    #[test]
+    fn test_cycle_is_feasible() {
+        let distances = cycle_matrix(6);
+        assert!(is_connected_ignoring_max(&distances));
+        assert_eq!(check_finite_graph(&distances), Ok(()));
+    }
+
+    #[test]
+    fn test_two_components_not_connected() {
+        // Two triangles {0, 1, 2} and {3, 4, 5}
+        let distances = Matrix::new_from_distance_function(6, |from, to| {
+            if from.0 / 3 == to.0 / 3 {
+                Distance(1)
+            } else {
+                Distance::MAX
+            }
+        });
+        assert!(!is_connected_ignoring_max(&distances));
+        assert_eq!(
+            check_finite_graph(&distances),
+            Err(SolverError::NotConnected)
+        );
+    }
+
+    #[test]
+    fn test_path_has_too_small_degree() {
+        let mut distances = cycle_matrix(6);
+        distances.set_data_symmetric(Node(0), Node(5), Distance::MAX);
+        assert!(is_connected_ignoring_max(&distances));
+        assert_eq!(
+            check_finite_graph(&distances),
+            Err(SolverError::NodeDegreeTooSmall(Node(0)))
+        );
+    }
+
+    #[test]
+    fn test_check_cost_headroom() {
+        assert_eq!(check_cost_headroom(&cycle_matrix(6)), Ok(()));
+
+        // Node 0 is charged twice, so five nodes with distances of a sixth of the maximum fit
+        let sixth = Distance::MAX.0 / 6;
+        let distances = Matrix::new_from_distance_function(5, |_, _| Distance(sixth));
+        assert_eq!(check_cost_headroom(&distances), Ok(()));
+        let distances = Matrix::new_from_distance_function(6, |_, _| Distance(sixth));
+        assert_eq!(
+            check_cost_headroom(&distances),
+            Err(SolverError::CostOverflow(7 * i64::from(sixth)))
+        );
+    }
+
+    #[test]
+    fn test_check_fixed_edges() {
+        let path = [UnEdge::new(Node(0), Node(1)), UnEdge::new(Node(1), Node(2))];
+        assert_eq!(check_fixed_edges(4, &path), Ok(vec![1, 2, 1, 0]));
+
+        let tour = [
+            UnEdge::new(Node(0), Node(1)),
+            UnEdge::new(Node(1), Node(2)),
+            UnEdge::new(Node(2), Node(0)),
+        ];
+        assert_eq!(check_fixed_edges(3, &tour), Ok(vec![2, 2, 2]));
+        assert_eq!(
+            check_fixed_edges(4, &tour),
+            Err(SolverError::FixedEdgesContainSubtour)
+        );
+
+        let star = [
+            UnEdge::new(Node(0), Node(1)),
+            UnEdge::new(Node(0), Node(2)),
+            UnEdge::new(Node(0), Node(3)),
+        ];
+        assert_eq!(
+            check_fixed_edges(4, &star),
+            Err(SolverError::FixedDegreeTooLarge(Node(0)))
+        );
+
+        let self_loop = UnEdge::new(Node(1), Node(1));
+        assert_eq!(
+            check_fixed_edges(4, &[self_loop]),
+            Err(SolverError::InvalidFixedEdge(self_loop))
+        );
+        let out_of_range = UnEdge::new(Node(1), Node(4));
+        assert_eq!(
+            check_fixed_edges(4, &[out_of_range]),
+            Err(SolverError::InvalidFixedEdge(out_of_range))
+        );
+    }
+
+    #[test]
+    fn test_node_zero_is_cut_node() {
+        // Two triangles sharing node 0: {0, 1, 2} and {0, 3, 4}
+        let distances = Matrix::new_from_distance_function(5, |from, to| {
+            if from.0 == 0 || to.0 == 0 || from.0.div_ceil(2) == to.0.div_ceil(2) {
+                Distance(1)
+            } else {
+                Distance::MAX
+            }
+        });
+        assert_eq!(
+            check_finite_graph(&distances),
+            Err(SolverError::CutNode(Node(0)))
+        );
+    }
+}
@@ -0,0 +1,335 @@
+/*!
+Checkpoints of the depth-first branch-and-bound search, which allow to continue long runs later.
+
+A [`Checkpoint`] contains the complete state of the search in [`explore_nodes`]: the edge states,
+node penalties and fixed degrees of the current node, the explicit stack of pending
+[`SearchStep`]s, the best tour found so far and the number of explored nodes. Resuming from a
+checkpoint with [`held_karp_resume`] thus explores exactly the nodes the interrupted search would
+have explored next. A fingerprint of the distances ensures that a checkpoint is only resumed on the
+instance it has been taken of.
+
+A checkpoint is returned in [`HeldKarpSolution::checkpoint`] whenever the
+[time limit](HeldKarpConfig::time_limit) stops the search. With the `serde` feature, checkpoints
+can additionally be written to disk periodically, see [`CheckpointConfig`], and resumed with
+[`held_karp_resume_from`].
+
+Only the depth-first search supports checkpoints, as the open nodes of the best-first search each
+carry their own copy of the edge states.
+*/
+
+#[cfg(feature = "serde")]
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use log::info;
+#[cfg(feature = "serde")]
+use log::{debug, warn};
+#[cfg(feature = "serde")]
+use thiserror::Error;
+use tsp_core::instance::{
+    UnTour,
+    distance::{Distance, ScaledDistance},
+    matrix::Matrix,
+    node::Node,
+};
+
+use crate::{
+    HeldKarpConfig, HeldKarpSolution, SolverError,
+    feasibility::{check_cost_headroom, check_finite_graph},
+    held_karp_mod::{
+        EdgeState, SearchState, SearchStep, checked_initial_tour, explore_nodes,
+        initial_upper_bound_cost, publish_incumbent,
+    },
+};
+
+/// Snapshot of an interrupted depth-first branch-and-bound search, see the
+/// [module-level documentation][crate::held_karp_mod::checkpoint].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    dimension: usize,
+    /// Fingerprint of the distances of the instance, see [`distances_fingerprint`].
+    distances_fingerprint: u64,
+    /// Edge states of the current node in row-major order.
+    edge_states: Vec<EdgeState>,
+    node_penalties: Vec<ScaledDistance>,
+    fixed_degrees: Vec<u32>,
+    /// Pending steps of the search, the last one is processed first.
+    stack: Vec<SearchStep>,
+    best_tour: Option<UnTour>,
+    nodes_explored: usize,
+}
+
+impl Checkpoint {
+    /// Captures the state of the search in [`explore_nodes`].
+    pub(super) fn new(stack: Vec<SearchStep>, state: &SearchState) -> Self {
+        Self {
+            dimension: state.edge_states.dimension(),
+            distances_fingerprint: distances_fingerprint(state.distances),
+            edge_states: state.edge_states.data().to_vec(),
+            node_penalties: state.node_penalties.clone(),
+            fixed_degrees: state.fixed_degrees.clone(),
+            stack,
+            best_tour: state.best_tour.clone(),
+            nodes_explored: state.bb_counter,
+        }
+    }
+
+    /// Number of nodes of the instance the checkpoint belongs to.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// The best tour found before the checkpoint was taken, if any.
+    pub fn best_tour(&self) -> Option<&UnTour> {
+        self.best_tour.as_ref()
+    }
+
+    /// Number of nodes of the search tree explored before the checkpoint was taken.
+    pub fn nodes_explored(&self) -> usize {
+        self.nodes_explored
+    }
+
+    /// Returns whether the checkpoint is a state of a search on the given distances.
+    ///
+    /// Besides the dimension and the fingerprint of the distances, the state itself is checked for
+    /// consistency, as the checkpoint might have been corrupted on disk: the fixed degrees have to
+    /// match the fixed edges, every undo record on the stack has to revert a branching decision
+    /// which is part of the edge states and the best tour has to be a tour through all nodes.
+    fn fits(&self, distances: &Matrix<Distance>) -> bool {
+        let dimension = distances.dimension();
+        if self.dimension != dimension
+            || self.edge_states.len() != dimension * dimension
+            || self.node_penalties.len() != dimension
+            || self.fixed_degrees.len() != dimension
+            || self.distances_fingerprint != distances_fingerprint(distances)
+        {
+            return false;
+        }
+
+        let mut edge_states = Matrix::new(self.edge_states.clone(), dimension);
+        for (from, &expected_degree) in self.fixed_degrees.iter().enumerate() {
+            let mut fixed_degree = 0;
+            for (to, state) in edge_states.iter_row(Node(from)) {
+                if state != edge_states.get_data(to, Node(from)) {
+                    return false;
+                }
+                if state == EdgeState::Fixed {
+                    fixed_degree += 1;
+                }
+            }
+            if fixed_degree != expected_degree || fixed_degree > 2 {
+                return false;
+            }
+        }
+
+        // Undo the branching decisions in the order the search would. As the fixed degrees match
+        // the fixed edges, undoing a fixed edge cannot make them negative
+        for step in self.stack.iter().rev() {
+            let (edge, state) = match *step {
+                SearchStep::Explore { .. } => continue,
+                SearchStep::Branch { edge, state, .. } | SearchStep::Undo { edge, state } => {
+                    (edge, state)
+                }
+            };
+            if edge.from == edge.to
+                || edge.from.0 >= dimension
+                || edge.to.0 >= dimension
+                || state == EdgeState::Available
+            {
+                return false;
+            }
+            if let SearchStep::Undo { .. } = step {
+                if edge_states.get_data(edge.from, edge.to) != state {
+                    return false;
+                }
+                edge_states.set_data_symmetric(edge.from, edge.to, EdgeState::Available);
+            }
+        }
+
+        self.best_tour.as_ref().is_none_or(|tour| {
+            checked_initial_tour(distances, tour.clone())
+                .is_some_and(|checked| checked.cost == tour.cost)
+        })
+    }
+
+    /// Write the checkpoint to the given file as JSON.
+    ///
+    /// The checkpoint is first written to a temporary file next to it, which then replaces the
+    /// given file. Thus, an interrupted write does not destroy an earlier checkpoint.
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let mut temporary_path = path.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temporary_path, path)?;
+        Ok(())
+    }
+
+    /// Read a checkpoint written by [`Checkpoint::save`].
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Configuration of the periodic checkpoints of the Held-Karp solver, see
+/// [`HeldKarpConfig::checkpoint`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    /// File the checkpoints are written to. Each checkpoint replaces the previous one.
+    pub path: PathBuf,
+    /// Minimum duration between two checkpoints.
+    ///
+    /// The time is only checked between the nodes of the search tree. If the
+    /// [time limit](HeldKarpConfig::time_limit) stops the search, a final checkpoint is written
+    /// regardless of the interval.
+    pub interval: Duration,
+}
+
+#[cfg(feature = "serde")]
+impl CheckpointConfig {
+    /// Write the checkpoint to the configured file.
+    ///
+    /// Failures are only logged, as a failed checkpoint should not abort a long search.
+    pub(super) fn write(&self, checkpoint: &Checkpoint) {
+        match checkpoint.save(&self.path) {
+            Ok(()) => debug!(
+                "Wrote checkpoint after {} explored nodes to {}",
+                checkpoint.nodes_explored,
+                self.path.display()
+            ),
+            Err(error) => warn!(
+                "Failed to write checkpoint to {}: {error}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+/// Errors that can occur when writing, reading or resuming from a [`Checkpoint`] file.
+#[cfg(feature = "serde")]
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    /// Reading or writing the checkpoint file failed.
+    #[error("Failed to access the checkpoint file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The checkpoint file could not be serialized or deserialized.
+    #[error("Invalid checkpoint file: {0}")]
+    Format(#[from] serde_json::Error),
+    /// Resuming the search from the checkpoint failed.
+    #[error(transparent)]
+    Solver(#[from] SolverError),
+}
+
+/// Continue the depth-first branch-and-bound search from the given checkpoint.
+///
+/// The distances have to be those of the interrupted search. The configuration is used for the
+/// remaining search, except for the [search order](HeldKarpConfig::search_order), which is always
+/// depth-first, and the [fixed edges](HeldKarpConfig::fixed_edges), which are already part of the
+/// checkpoint. The returned number of explored nodes includes those explored before the
+/// checkpoint was taken.
+///
+/// Returns [`SolverError::InvalidCheckpoint`] if the checkpoint does not fit the distances, e.g.
+/// because it belongs to another instance or is corrupted.
+pub fn held_karp_resume(
+    distances: &Matrix<Distance>,
+    config: &HeldKarpConfig,
+    checkpoint: Checkpoint,
+) -> Result<HeldKarpSolution, SolverError> {
+    check_finite_graph(distances)?;
+    check_cost_headroom(distances)?;
+    let dimension = distances.dimension();
+    if !checkpoint.fits(distances) {
+        return Err(SolverError::InvalidCheckpoint(dimension));
+    }
+
+    info!(
+        "Resuming Held-Karp solver after {} explored nodes",
+        checkpoint.nodes_explored
+    );
+    let Checkpoint {
+        dimension: _,
+        distances_fingerprint: _,
+        edge_states,
+        node_penalties,
+        fixed_degrees,
+        stack,
+        best_tour,
+        nodes_explored,
+    } = checkpoint;
+    let edge_states = Matrix::new(edge_states, dimension);
+
+    let scaled_distances = Matrix::new(
+        distances
+            .data()
+            .iter()
+            .map(|&d| ScaledDistance::from_distance(d))
+            .collect(),
+        dimension,
+    );
+
+    let upper_bound = initial_upper_bound_cost(distances, best_tour.as_ref());
+    if let Some(tour) = &best_tour {
+        publish_incumbent(config, tour);
+    }
+
+    let mut state = SearchState {
+        distances,
+        scaled_distances,
+        config,
+        bb_limit: None,
+        edge_states,
+        node_penalties,
+        fixed_degrees,
+        upper_bound,
+        best_tour,
+        bb_counter: nodes_explored,
+    };
+    let checkpoint = explore_nodes(&mut state, stack);
+
+    Ok(HeldKarpSolution {
+        tour: state.best_tour.ok_or(SolverError::NoTourFound)?,
+        nodes_explored: state.bb_counter,
+        is_optimal: checkpoint.is_none(),
+        checkpoint,
+    })
+}
+
+/// Continue the depth-first branch-and-bound search from the checkpoint in the given file, see
+/// [`Checkpoint::load`] and [`held_karp_resume`].
+#[cfg(feature = "serde")]
+pub fn held_karp_resume_from(
+    distances: &Matrix<Distance>,
+    config: &HeldKarpConfig,
+    path: impl AsRef<Path>,
+) -> Result<HeldKarpSolution, CheckpointError> {
+    let checkpoint = Checkpoint::load(path)?;
+    Ok(held_karp_resume(distances, config, checkpoint)?)
+}
+
+/// Returns a fingerprint of the distances, which identifies the instance a checkpoint belongs to.
+///
+/// This is the 64-bit FNV-1a hash of the distances, which unlike
+/// [`DefaultHasher`](std::hash::DefaultHasher) does not change between Rust versions, such that
+/// checkpoint files remain valid.
+fn distances_fingerprint(distances: &Matrix<Distance>) -> u64 {
+    distances
+        .data()
+        .iter()
+        .flat_map(|distance| distance.0.to_le_bytes())
+        .fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+        })
+}
@@ -27,24 +27,30 @@ upper bound found so far and use it to prune branches in the branch-and-bound se
 
 The call structure of the algorithm and sub-methods is as follows. Indented functions indicate
 that they are called by the function above them.
-- `held_karp`:  Main entry point for the Held-Karp solver. Sets up parameters and initiates the
-                branch-and-bound search.
-    - `explore_node`:   Performs depth-first branch-and-bound search.
-        - `held_karp_lower_bound`:  Computes a lower bound using 1-trees and Lagrangian relaxation.
-            - `min_one_tree`:   Computes a minimum 1-tree given current edge states and node penalties.
-                - `min_spanning_tree`:  Computes a minimum spanning tree of all nodes except the
-                                        first using Prim's algorithm.
-        - `edge_to_branch_on`:  Selects an edge (from the 1-tree) to branch on.
-        - `explore_node`:   Is called twice (recursively) to explore the branches including or excluding
-                            the selected edge.
+- `held_karp`: Main entry point for the Held-Karp solver. Sets up parameters and initiates the
+  branch-and-bound search.
+    - `explore_nodes`: Performs depth-first branch-and-bound search using an explicit stack. With
+      [`SearchOrder::BestFirst`], `explore_nodes_best_first` is used instead, which keeps the open
+      nodes in a priority queue.
+        - `held_karp_lower_bound`: Computes a lower bound using 1-trees and Lagrangian relaxation.
+            - `min_one_tree`: Computes a minimum 1-tree given current edge states and node
+              penalties.
+                - `min_spanning_tree`: Computes a minimum spanning tree of all nodes except the
+                  first using Prim's algorithm.
+        - `edge_to_branch_on`: Selects an edge (from the 1-tree) to branch on.
+        - Both branches including or excluding the selected edge are pushed onto the stack,
+          together with undo records that restore the edge states after the branch is explored.
+- `held_karp_resume`: Continues an interrupted depth-first search from a [`Checkpoint`] of its
+  stack, see the [`checkpoint`] module.
 
 ## 1-trees
 
 1-trees are minimum spanning trees that span nodes 2 to n, plus two minimum cost edges
-connecting node 1 to the tree. This is always a lower bound on the cost of a TSP tour, since
-any TSP tour is a 1-tree. To see the latter, take any valid TSP tour, remove the edges adjacent to
-the first node, and one obtains a spanning tree. Thus, the cheapest 1-tree provides a lower bound
-on the TSP tour cost.
+connecting node 1 to the tree. In the implementation (using 0-based indices), the special node is
+always node 0 and the spanning tree covers nodes `1..n`, see [`min_one_tree`]. This is always a
+lower bound on the cost of a TSP tour, since any TSP tour is a 1-tree. To see the latter, take any
+valid TSP tour, remove the edges adjacent to the first node, and one obtains a spanning tree.
+Thus, the cheapest 1-tree provides a lower bound on the TSP tour cost.
 
 ## Lagrangian Relaxation
 
@@ -57,6 +63,8 @@ TSP tour cost.
 Once an actual tour is found, we can use that as an upper bound to prune the search space in the branch-and-bound
 exploration.
 
+The bound obtained at the root of the search is also available on its own via [`held_karp_bound`].
+
 This is considered a lagrangian relaxation ([wikipedia](https://en.wikipedia.org/wiki/Lagrangian_relaxation))
 since instead of enforcing the degree-2 constraints strictly for our 1-trees, we instead penalize
 deviations from degree 2 via the node penalties.
@@ -78,19 +86,46 @@ by forcibly including or excluding edges.
 
 */
 
-use std::u32;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 use log::{debug, info, trace};
 use tsp_core::instance::{
     UnTour,
-    distance::{Distance, ScaledDistance},
+    distance::{Distance, FIXED_POINT_FRACTIONAL_BITS, ScaledDistance},
     edge::UnEdge,
-    matrix::Matrix,
+    edge_set::FixedEdgeSet,
+    matrix::{Matrix, ReadDistance},
     node::Node,
+    tour_order::TourOrder,
 };
 
-pub use crate::held_karp_mod::{parallel::held_karp_parallel, trees::min_one_tree};
+#[cfg(feature = "serde")]
+pub use crate::held_karp_mod::checkpoint::{
+    CheckpointConfig, CheckpointError, held_karp_resume_from,
+};
+pub use crate::held_karp_mod::{
+    checkpoint::{Checkpoint, held_karp_resume},
+    parallel::{
+        ParallelConfig, held_karp_parallel, held_karp_parallel_with_config,
+        held_karp_parallel_with_initial_tour,
+    },
+    trees::{max_degree_violation, min_one_tree, min_one_tree_parallel, one_tree_degrees},
+};
+use crate::{
+    SolverError,
+    construction::{Endpoints, nearest_neighbor, nearest_neighbor_with_endpoints},
+    feasibility::{
+        check_candidate_edges, check_cost_headroom, check_finite_graph, check_fixed_edges,
+        finite_cost_bound,
+    },
+};
 
+pub mod checkpoint;
 mod parallel;
 mod trees;
 
@@ -98,12 +133,371 @@ mod trees;
 ///
 /// For a detailed explanation of the algorithm, see the [module-level
 /// documentation][crate::held_karp_mod].
-pub fn held_karp(distances: &Matrix<Distance>) -> Option<UnTour> {
+///
+/// Edges with distance [`Distance::MAX`] are treated as missing. Returns an error if the graph of
+/// the remaining edges cannot contain a tour, see [`check_finite_graph`] for details.
+///
+/// Instances with at most one node are solved by the empty tour with cost 0.
+///
+/// Uses the default [`HeldKarpConfig`], see [`held_karp_with_config`] for more control.
+pub fn held_karp(distances: &Matrix<Distance>) -> Result<UnTour, SolverError> {
+    held_karp_with_config(distances, &HeldKarpConfig::default()).map(|solution| solution.tour)
+}
+
+/// Configuration of the Held-Karp solver, see [`held_karp_with_config`].
+#[derive(Debug, Clone)]
+pub struct HeldKarpConfig {
+    /// Strategy to select the edge to branch on.
+    pub branch_strategy: BranchStrategy,
+    /// Order in which the nodes of the branch-and-bound search tree are explored.
+    pub search_order: SearchOrder,
+    /// Shared slot the solver writes its incumbent (the best tour found so far) into.
+    ///
+    /// The slot is updated with the initial tour and then whenever a better tour is found, so
+    /// other threads can poll it during a long solve. The lock is only held for storing the tour.
+    ///
+    /// Defaults to `None`.
+    pub incumbent: Option<Arc<Mutex<Option<UnTour>>>>,
+    /// Edges which are required to be part of the tour, e.g. from
+    /// [`TSPSymInstance::fixed_edges`](tsp_core::instance::TSPSymInstance::fixed_edges).
+    ///
+    /// They are validated with [`check_fixed_edges`] before the search starts.
+    ///
+    /// Defaults to no fixed edges.
+    pub fixed_edges: Vec<UnEdge>,
+    /// Edges the search is restricted to, e.g. a sparse candidate graph of the nearest neighbors
+    /// of each node. All other edges are [excluded](EdgeState::Excluded) from the start, except
+    /// for the [fixed edges](Self::fixed_edges).
+    ///
+    /// The returned tour is then only optimal among the tours using candidate edges, which may be
+    /// more expensive than the optimal tour of the instance. The candidate edges are validated
+    /// with [`check_candidate_edges`] before the search starts. An initial tour using other edges
+    /// is ignored.
+    ///
+    /// Defaults to `None`, that is, all edges are candidates.
+    pub candidate_edges: Option<Vec<UnEdge>>,
+    /// Whether to permanently exclude edges after the bound computation at the root of the search,
+    /// if their reduced costs show that they cannot be part of a tour cheaper than the initial
+    /// upper bound. This shrinks the search, especially with a good
+    /// [initial tour](held_karp_with_initial_tour).
+    ///
+    /// Defaults to `false`.
+    pub eliminate_edges: bool,
+    /// Maximum duration of the branch-and-bound search.
+    ///
+    /// Once it has passed, the search stops and the best tour found so far is returned, which is
+    /// then not necessarily optimal, see [`HeldKarpSolution::is_optimal`]. The time is only
+    /// checked between the nodes of the search tree, so it might be exceeded slightly.
+    ///
+    /// Defaults to `None`, that is, no time limit.
+    pub time_limit: Option<Duration>,
+    /// Number of threads to compute the spanning trees of the 1-trees with, see
+    /// [`min_one_tree_parallel`] and [`SubgradientParams::one_tree_threads`].
+    ///
+    /// The threads are synchronized in every step of Prim's algorithm, so more than one thread
+    /// only pays off for instances with thousands of nodes.
+    ///
+    /// Defaults to 1.
+    pub one_tree_threads: usize,
+    /// Periodically write a [`Checkpoint`] of the depth-first search to a file, from which the
+    /// search can be resumed with [`held_karp_resume_from`].
+    ///
+    /// Ignored for [`SearchOrder::BestFirst`].
+    ///
+    /// Defaults to `None`.
+    #[cfg(feature = "serde")]
+    pub checkpoint: Option<CheckpointConfig>,
+}
+
+impl Default for HeldKarpConfig {
+    fn default() -> Self {
+        Self {
+            branch_strategy: BranchStrategy::default(),
+            search_order: SearchOrder::default(),
+            incumbent: None,
+            fixed_edges: Vec::new(),
+            candidate_edges: None,
+            eliminate_edges: false,
+            time_limit: None,
+            one_tree_threads: 1,
+            #[cfg(feature = "serde")]
+            checkpoint: None,
+        }
+    }
+}
+
+/// Strategy to select the edge to branch on in the branch-and-bound search.
+///
+/// In both cases, only edges of the current 1-tree which are not yet fixed or excluded are
+/// considered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BranchStrategy {
+    /// Branch on the edge with the smallest reduced cost, that is, its cost minus the penalties
+    /// of both endpoints.
+    #[default]
+    CheapestReducedCost,
+    /// Branch on an edge incident to the node whose degree in the 1-tree deviates the most from 2.
+    /// Ties are broken by the smallest reduced cost.
+    MostConstrainedNode,
+}
+
+/// Order in which the nodes of the branch-and-bound search tree are explored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchOrder {
+    /// Explore the branch excluding the edge and then the branch including the edge, each down to
+    /// its leaves. Only the edge states of the current path are kept in memory.
+    #[default]
+    DepthFirst,
+    /// Always explore the open node with the smallest lower bound (that of its parent), preferring
+    /// deeper nodes on ties. This tends to find good tours earlier and explores no node whose
+    /// lower bound exceeds the optimum.
+    ///
+    /// Each open node stores its own copy of the edge states, that is, `n^2` bytes for an
+    /// instance with `n` nodes, and the number of open nodes can grow exponentially. On large
+    /// instances, or with a weak initial tour, this can exhaust the memory.
+    BestFirst,
+}
+
+/// Result of [`held_karp_with_config`].
+#[derive(Debug, Clone)]
+pub struct HeldKarpSolution {
+    /// The optimal tour, or the best tour found if the search has been stopped early.
+    pub tour: UnTour,
+    /// The number of nodes explored in the branch-and-bound search tree.
+    pub nodes_explored: usize,
+    /// Whether the search has been completed, such that the tour is optimal. This is only `false`
+    /// if the [time limit](HeldKarpConfig::time_limit) has been reached.
+    pub is_optimal: bool,
+    /// State of the depth-first search when the time limit stopped it, from which it can be
+    /// continued with [`held_karp_resume`].
+    ///
+    /// `None` if the search has been completed or if it used [`SearchOrder::BestFirst`].
+    pub checkpoint: Option<Checkpoint>,
+}
+
+/// Solve the Traveling Salesman Problem using the Held-Karp algorithm with the given
+/// configuration.
+///
+/// The search is warm started with the [`identity_tour`], see [`held_karp_with_initial_tour`] to
+/// use a different heuristic. See [`held_karp`] for details.
+pub fn held_karp_with_config(
+    distances: &Matrix<Distance>,
+    config: &HeldKarpConfig,
+) -> Result<HeldKarpSolution, SolverError> {
+    held_karp_with_initial_tour(distances, config, identity_tour)
+}
+
+/// Solve the Traveling Salesman Problem using the Held-Karp algorithm, restricted to tours using
+/// only the given candidate edges, e.g. the edges to the `k` nearest neighbors of each node.
+///
+/// All other edges are excluded from the start, which turns the search into a search on a sparse
+/// graph. Thus, the returned tour is only guaranteed to be optimal among the tours within the
+/// candidate edges. Returns [`SolverError::NoTourFound`] if there is no such tour. See
+/// [`HeldKarpConfig::candidate_edges`] to combine the restriction with other options.
+pub fn held_karp_restricted(
+    distances: &Matrix<Distance>,
+    candidates: &[UnEdge],
+) -> Result<UnTour, SolverError> {
+    let config = HeldKarpConfig {
+        candidate_edges: Some(candidates.to_vec()),
+        ..HeldKarpConfig::default()
+    };
+    held_karp_with_config(distances, &config).map(|solution| solution.tour)
+}
+
+/// Finds any tour which only uses edges with a distance smaller than [`Distance::MAX`], that is, a
+/// Hamiltonian cycle of the graph of finite edges. Returns `None` if there is no such tour.
+///
+/// This runs the branch-and-bound search of [`held_karp`] on the finite edges, but with all of
+/// them costing 0. Thus, the first tour found is optimal, which stops the search right away. Only
+/// if there is no tour, the whole search tree has to be explored. The cost of the returned tour is
+/// computed with the original distances.
+///
+/// Useful for Hamiltonian cycle problems (HCP), whose graphs are encoded with missing edges, see
+/// the [`feasibility`](crate::feasibility) module.
+pub fn find_any_hamiltonian_cycle(distances: &Matrix<Distance>) -> Option<UnTour> {
+    let zero_distances = Matrix::new_from_distance_function(distances.dimension(), |from, to| {
+        if distances.get_data(from, to) < Distance::MAX {
+            Distance(0)
+        } else {
+            Distance::MAX
+        }
+    });
+    let config = HeldKarpConfig {
+        candidate_edges: Some(
+            (0..distances.dimension())
+                .flat_map(|from| (0..from).map(move |to| UnEdge::new(Node(from), Node(to))))
+                .filter(|edge| {
+                    distances.get_data(edge.from, edge.to) < Distance::MAX
+                        && distances.get_data(edge.to, edge.from) < Distance::MAX
+                })
+                .collect(),
+        ),
+        ..HeldKarpConfig::default()
+    };
+
+    // Errors of the checks before the search, e.g. a node with less than two finite edges, prove
+    // that there is no tour
+    let tour = held_karp_with_config(&zero_distances, &config).ok()?.tour;
+    let costs: Vec<_> = tour
+        .edges
+        .iter()
+        .map(|edge| distances.get_data(edge.from, edge.to))
+        .collect();
+    if costs.iter().any(|&cost| cost >= Distance::MAX) {
+        return None;
+    }
+    Some(UnTour {
+        cost: Distance::saturating_sum(costs),
+        edges: tour.edges,
+    })
+}
+
+/// Solve the open Traveling Salesman Problem, that is, find a shortest Hamiltonian path, using the
+/// Held-Karp algorithm. The path starts at `start` and ends at `end`, if they are given.
+///
+/// The path problem is reduced to a tour problem by adding a virtual node, which is connected to
+/// all nodes with distance 0. Removing it from an optimal tour of the extended instance yields an
+/// optimal path, and the 1-tree bounds of the extended instance are lower bounds on the path
+/// costs. Fixed endpoints are enforced by [fixing](HeldKarpConfig::fixed_edges) the edges between
+/// them and the virtual node. The search is warm started with
+/// [`nearest_neighbor_with_endpoints`].
+///
+/// Returns the nodes in the order of the path, whose cost is [`TourOrder::path_cost`]. If only
+/// `end` is given, the path ends there, and otherwise it starts at `start` or an arbitrary
+/// endpoint. Edges with distance [`Distance::MAX`] are treated as missing, and
+/// [`SolverError::NoTourFound`] is returned if there is no path without them.
+///
+/// Panics if `start` or `end` is not a node of the instance, or if they are the same node of an
+/// instance with more than one node.
+pub fn held_karp_path(
+    distances: &Matrix<Distance>,
+    start: Option<Node>,
+    end: Option<Node>,
+) -> Result<TourOrder, SolverError> {
+    let dimension = distances.dimension();
+    for endpoint in [start, end].into_iter().flatten() {
+        assert!(endpoint.0 < dimension, "The endpoints have to be nodes");
+    }
+    if dimension <= 1 {
+        return Ok(TourOrder::identity(dimension));
+    }
+    assert!(
+        start.is_none() || start != end,
+        "The start and end have to be different nodes"
+    );
+
+    let virtual_node = Node(dimension);
+    let extended_distances = Matrix::new_from_distance_function(dimension + 1, |from, to| {
+        if from == virtual_node || to == virtual_node {
+            Distance(0)
+        } else {
+            distances.get_data(from, to)
+        }
+    });
+    let config = HeldKarpConfig {
+        fixed_edges: [start, end]
+            .into_iter()
+            .flatten()
+            .map(|endpoint| UnEdge::new(endpoint, virtual_node))
+            .collect(),
+        ..HeldKarpConfig::default()
+    };
+    // A nearest neighbor path closed by the virtual node contains the fixed edges
+    let initial_path = |extended_distances: &Matrix<Distance>| {
+        let endpoints = Endpoints {
+            start: start.or(end).unwrap_or(Node(0)),
+            end: start.and(end),
+            open: true,
+        };
+        let mut nodes = nearest_neighbor_with_endpoints(distances, endpoints)
+            .nodes()
+            .to_vec();
+        nodes.push(virtual_node);
+        let mut tour = TourOrder::new(nodes).to_un_tour(extended_distances);
+        tour.cost = tour.cost.min(Distance::MAX);
+        tour
+    };
+
+    let tour = held_karp_with_initial_tour(&extended_distances, &config, initial_path)?.tour;
+    let order = tour
+        .to_order(dimension + 1)
+        .expect("Held-Karp returns valid tours");
+    let virtual_position = order
+        .nodes()
+        .iter()
+        .position(|&node| node == virtual_node)
+        .expect("The tour visits the virtual node");
+    let mut path = order.nodes()[virtual_position + 1..].to_vec();
+    path.extend_from_slice(&order.nodes()[..virtual_position]);
+    if (start.is_some() && path.first() != start.as_ref())
+        || (start.is_none() && end.is_some() && path.last() != end.as_ref())
+    {
+        path.reverse();
+    }
+
+    let path = TourOrder::new(path);
+    if path.path_cost(distances) >= Distance::MAX {
+        return Err(SolverError::NoTourFound);
+    }
+    Ok(path)
+}
+
+/// Solve the Traveling Salesman Problem using the Held-Karp algorithm with the given
+/// configuration, warm starting the search with the tour computed by `initial_upper_bound`.
+///
+/// The cost of the initial tour is used as the upper bound to prune the search, so a tight tour,
+/// e.g. from [`nearest_neighbor`] followed by [`two_opt`](crate::local_search::two_opt), can
+/// reduce the number of explored nodes considerably. The cost reported with the tour is ignored
+/// and recomputed from the distances. If the edges do not form a single tour through all nodes,
+/// the tour does not contain all [fixed edges](HeldKarpConfig::fixed_edges) or it uses a missing
+/// edge or one which is not a [candidate edge](HeldKarpConfig::candidate_edges), it is ignored
+/// and the search starts without an upper bound.
+///
+/// The closure is not called for instances with at most one node. See [`held_karp`] for details.
+pub fn held_karp_with_initial_tour(
+    distances: &Matrix<Distance>,
+    config: &HeldKarpConfig,
+    initial_upper_bound: impl FnOnce(&Matrix<Distance>) -> UnTour,
+) -> Result<HeldKarpSolution, SolverError> {
+    check_finite_graph(distances)?;
+    check_cost_headroom(distances)?;
+    let fixed_degrees = check_fixed_edges(distances.dimension(), &config.fixed_edges)?;
+    if let Some(candidate_edges) = &config.candidate_edges {
+        check_candidate_edges(distances.dimension(), candidate_edges, &config.fixed_edges)?;
+    }
+
+    // Instances with at most one node only have the empty tour, which 1-trees cannot represent
+    if distances.dimension() <= 1 {
+        let tour = UnTour {
+            edges: Vec::new(),
+            cost: Distance(0),
+        };
+        publish_incumbent(config, &tour);
+        return Ok(HeldKarpSolution {
+            tour,
+            nodes_explored: 0,
+            is_optimal: true,
+            checkpoint: None,
+        });
+    }
+
     info!("Starting Held-Karp solver");
     let mut edge_states = Matrix::new(
-        vec![EdgeState::Available; distances.data().len()],
+        vec![
+            if config.candidate_edges.is_some() {
+                EdgeState::Excluded
+            } else {
+                EdgeState::Available
+            };
+            distances.data().len()
+        ],
         distances.dimension(),
     );
+    if let Some(candidate_edges) = &config.candidate_edges {
+        edge_states.set_edges(candidate_edges, EdgeState::Available);
+    }
+    exclude_missing_edges(distances, &mut edge_states);
 
     let scaled_distances = Matrix::new(
         distances
@@ -114,38 +508,284 @@ pub fn held_karp(distances: &Matrix<Distance>) -> Option<UnTour> {
         distances.dimension(),
     );
 
-    let mut node_penalties = initial_penalties(&scaled_distances, distances.dimension());
-    let mut fixed_degrees = vec![0u32; distances.dimension()];
-    let mut bb_counter = 0;
+    let node_penalties = initial_penalties(&scaled_distances, distances.dimension());
+    edge_states.set_edges(&config.fixed_edges, EdgeState::Fixed);
+
+    // If the initial tour is invalid or does not respect the fixed or candidate edges, we start
+    // without an upper bound
+    let best_tour =
+        checked_initial_tour(distances, initial_upper_bound(distances)).filter(|initial_tour| {
+            let initial_tour_edges = initial_tour.edge_set();
+            initial_tour
+                .edges
+                .iter()
+                .all(|edge| edge_states.get_data(edge.from, edge.to) != EdgeState::Excluded)
+                && config
+                    .fixed_edges
+                    .iter()
+                    .all(|&edge| initial_tour_edges.contains(edge))
+        });
+    let upper_bound = initial_upper_bound_cost(distances, best_tour.as_ref());
+    if let Some(initial_tour) = &best_tour {
+        publish_incumbent(config, initial_tour);
+    }
 
-    let mut initial_upper_bound = Distance(0);
-    let mut initial_tour = Vec::with_capacity(distances.dimension());
-    for i in 0..distances.dimension() {
-        initial_tour.push(UnEdge {
+    let mut state = SearchState {
+        distances,
+        scaled_distances,
+        config,
+        bb_limit: None,
+        edge_states,
+        node_penalties,
+        fixed_degrees,
+        upper_bound,
+        best_tour,
+        bb_counter: 0,
+    };
+    let (is_optimal, checkpoint) = match config.search_order {
+        SearchOrder::DepthFirst => {
+            let checkpoint = explore_nodes(&mut state, vec![SearchStep::Explore { depth: 0 }]);
+            (checkpoint.is_none(), checkpoint)
+        }
+        SearchOrder::BestFirst => (explore_nodes_best_first(&mut state), None),
+    };
+
+    Ok(HeldKarpSolution {
+        tour: state.best_tour.ok_or(SolverError::NoTourFound)?,
+        nodes_explored: state.bb_counter,
+        is_optimal,
+        checkpoint,
+    })
+}
+
+/// Returns the tour visiting the nodes in the order of their indices, that is, `0, 1, ..., n - 1`,
+/// together with its cost.
+///
+/// This is the default initial tour of [`held_karp_with_config`]. Its cost is capped at
+/// [`Distance::MAX`], as the tour might use missing edges.
+pub fn identity_tour(distances: &Matrix<Distance>) -> UnTour {
+    let dimension = distances.dimension();
+    let mut cost = Distance(0);
+    let mut edges = Vec::with_capacity(dimension);
+    for i in 0..dimension {
+        let next = Node((i + 1) % dimension);
+        edges.push(UnEdge {
             from: Node(i),
-            to: Node((i + 1) % distances.dimension()),
+            to: next,
         });
-        initial_upper_bound += distances.get_data(Node(i), Node((i + 1) % distances.dimension()));
+        cost += distances.get_data(Node(i), next);
+        cost = cost.min(Distance::MAX);
     }
-    let mut best_tour = Some(UnTour {
-        edges: initial_tour,
-        cost: initial_upper_bound,
-    });
+    UnTour { edges, cost }
+}
 
-    explore_node(
+/// Compute the Held-Karp lower bound of the instance, that is, the best 1-tree bound found by
+/// Lagrangian relaxation at the root of the branch-and-bound search, with the given maximum number
+/// of iterations.
+///
+/// Returns the lower bound together with the 1-tree of the last iteration and why the subgradient
+/// optimization stopped, see [`BoundInfo`]. If a 1-tree happens to be a tour, the lower bound is
+/// its cost and the instance is solved. See [`held_karp_bound_with_params`] for more control over
+/// the subgradient optimization.
+pub fn held_karp_bound(
+    distances: &Matrix<Distance>,
+    iterations: usize,
+) -> Result<BoundInfo, SolverError> {
+    held_karp_bound_with_params(
+        distances,
+        &SubgradientParams {
+            max_iterations: iterations,
+            ..SubgradientParams::default()
+        },
+    )
+}
+
+/// Compute the Held-Karp lower bound of the instance with the given parameters of the subgradient
+/// optimization.
+///
+/// The cost of a nearest neighbor tour is used as the upper bound which determines the step sizes.
+/// See [`held_karp_bound`] for details.
+///
+/// Edges with distance [`Distance::MAX`] are treated as missing, see [`check_finite_graph`].
+/// Instances whose tours may cost more than [`Distance::MAX`] are rejected, see
+/// [`check_cost_headroom`].
+/// Instances with at most one node have the bound 0 and an empty 1-tree.
+pub fn held_karp_bound_with_params(
+    distances: &Matrix<Distance>,
+    params: &SubgradientParams,
+) -> Result<BoundInfo, SolverError> {
+    check_finite_graph(distances)?;
+    check_cost_headroom(distances)?;
+    if distances.dimension() <= 1 {
+        return Ok(BoundInfo {
+            bound: Distance(0),
+            one_tree: Vec::new(),
+            node_penalties: vec![ScaledDistance(0); distances.dimension()],
+            iterations_run: 0,
+            termination: SubgradientTermination::Converged,
+        });
+    }
+
+    let mut edge_states = Matrix::new(
+        vec![EdgeState::Available; distances.data().len()],
+        distances.dimension(),
+    );
+    exclude_missing_edges(distances, &mut edge_states);
+    let scaled_distances = Matrix::new(
+        distances
+            .data()
+            .iter()
+            .map(|&d| ScaledDistance::from_distance(d))
+            .collect(),
+        distances.dimension(),
+    );
+    let mut node_penalties = initial_penalties(&scaled_distances, distances.dimension());
+    let upper_bound = nearest_neighbor(distances, Node(0))
+        .cost(distances)
+        .min(finite_upper_bound(distances));
+
+    let (output, iterations_run, termination) = held_karp_lower_bound_with_termination(
         distances,
         &scaled_distances,
-        &mut edge_states,
-        node_penalties.as_mut_slice(),
-        fixed_degrees.as_mut_slice(),
-        &mut initial_upper_bound,
-        &mut best_tour,
-        &mut bb_counter,
-        None,
-        0,
+        &edge_states,
+        &mut node_penalties,
+        upper_bound,
+        params,
     );
+    let (bound, one_tree) = match output {
+        Some(LowerBoundOutput::LowerBound(lower_bound, one_tree)) => (lower_bound, one_tree),
+        Some(LowerBoundOutput::Tour(tour)) => (tour.cost, tour.edges),
+        None => return Err(SolverError::NoTourFound),
+    };
+    Ok(BoundInfo {
+        bound,
+        one_tree,
+        node_penalties,
+        iterations_run,
+        termination,
+    })
+}
+
+/// The result of [`held_karp_bound`] and [`held_karp_bound_with_params`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundInfo {
+    /// The Held-Karp lower bound.
+    pub bound: Distance,
+    /// The 1-tree of the last iteration of the subgradient optimization. Its cost with respect to
+    /// [`node_penalties`](BoundInfo::node_penalties) may be below the bound, which is the best
+    /// one over all iterations.
+    pub one_tree: Vec<UnEdge>,
+    /// The node penalties of the last iteration of the subgradient optimization, i.e. the ones
+    /// for which [`one_tree`](BoundInfo::one_tree) is a minimum 1-tree.
+    pub node_penalties: Vec<ScaledDistance>,
+    /// Number of iterations of the subgradient optimization, i.e. of computed 1-trees.
+    pub iterations_run: usize,
+    /// Why the subgradient optimization stopped.
+    pub termination: SubgradientTermination,
+}
+
+impl BoundInfo {
+    /// Returns the reduced costs of the edges of the instance with respect to the node penalties
+    /// of the last iteration, see [`ReducedCosts`].
+    ///
+    /// The distances have to be the ones the bound was computed for.
+    pub fn reduced_costs<'a>(&'a self, distances: &'a Matrix<Distance>) -> ReducedCosts<'a> {
+        ReducedCosts::new(distances, &self.node_penalties)
+    }
+}
+
+/// The reduced costs of edges with respect to node penalties, that is, the distance of an edge
+/// minus the penalties of both of its nodes.
+///
+/// A minimum 1-tree minimizes the sum of reduced costs, so after the subgradient optimization of
+/// [`held_karp_bound`], edges with small reduced costs are the ones most likely to be part of an
+/// optimal tour. This makes them a good choice for candidate lists.
+#[derive(Debug, Clone, Copy)]
+pub struct ReducedCosts<'a> {
+    distances: &'a Matrix<Distance>,
+    node_penalties: &'a [ScaledDistance],
+}
+
+impl<'a> ReducedCosts<'a> {
+    /// Creates the reduced costs of the given distances and node penalties, with one penalty per
+    /// node.
+    pub fn new(distances: &'a Matrix<Distance>, node_penalties: &'a [ScaledDistance]) -> Self {
+        assert_eq!(
+            node_penalties.len(),
+            distances.dimension(),
+            "There has to be one penalty per node"
+        );
+        Self {
+            distances,
+            node_penalties,
+        }
+    }
+
+    /// Returns the reduced cost of the edge, rounded down to a whole distance.
+    ///
+    /// Missing edges, i.e. ones with distance [`Distance::MAX`], have the reduced cost
+    /// [`Distance::MAX`].
+    pub fn reduced_cost(&self, edge: UnEdge) -> Distance {
+        let distance = self.distances.get_data(edge.from, edge.to);
+        if distance >= Distance::MAX {
+            return Distance::MAX;
+        }
+        // Summed up as i64, as the penalties of far away nodes may overflow the scaled distances
+        let scaled_reduced_cost = i64::from(ScaledDistance::from_distance(distance).0)
+            - i64::from(self.node_penalties[edge.from.0].0)
+            - i64::from(self.node_penalties[edge.to.0].0);
+        let reduced_cost = scaled_reduced_cost >> FIXED_POINT_FRACTIONAL_BITS;
+        Distance(reduced_cost.clamp(i64::from(i32::MIN), i64::from(Distance::MAX.0)) as i32)
+    }
+}
+
+/// The reason the subgradient optimization of [`held_karp_bound_with_params`] stopped.
+///
+/// Useful for tuning the [`SubgradientParams`]: a bound stopped by
+/// [`MaxIterations`](SubgradientTermination::MaxIterations) might improve with more iterations,
+/// while one stopped by [`SmallStep`](SubgradientTermination::SmallStep) rather needs a larger
+/// initial alpha or beta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubgradientTermination {
+    /// The 1-tree is a tour or its cost reached the upper bound, so the bound is optimal.
+    Converged,
+    /// The maximum number of iterations was reached.
+    MaxIterations,
+    /// The step size of the penalty updates got too small to make progress.
+    SmallStep,
+}
+
+/// Parameters of the subgradient optimization which adjusts the node penalties when computing
+/// 1-tree lower bounds, see [`held_karp_bound_with_params`].
+///
+/// In each iteration, the penalties are moved along the degree deviations of the current 1-tree
+/// with a step size of `alpha * (upper_bound - one_tree_cost) / sum(deviation^2)`, after which
+/// alpha is multiplied by beta. The optimization stops after the maximum number of iterations or
+/// once the step size gets too small.
+///
+/// The defaults are the ones used at the root of the branch-and-bound search of [`held_karp`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubgradientParams {
+    /// Maximum number of iterations, i.e. of computed 1-trees.
+    pub max_iterations: usize,
+    /// Initial value of alpha.
+    pub initial_alpha: f64,
+    /// Factor alpha is multiplied by after each iteration.
+    pub beta: f64,
+    /// Number of threads to compute the spanning tree of each 1-tree with, see
+    /// [`min_one_tree_parallel`].
+    pub one_tree_threads: usize,
+}
 
-    best_tour
+impl Default for SubgradientParams {
+    fn default() -> Self {
+        Self {
+            max_iterations: INITIAL_MAX_ITERATIONS,
+            initial_alpha: INITIAL_ALPHA,
+            beta: INITIAL_BETA,
+            one_tree_threads: 1,
+        }
+    }
 }
 
 const INITIAL_MAX_ITERATIONS: usize = 1_000;
@@ -158,6 +798,7 @@ const BETA: f64 = 0.9;
 
 #[repr(i8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// State of an edge in the branch-and-bound search.
 pub enum EdgeState {
     /// Edge is available for inclusion or exclusion, i.e. not yet decided.
@@ -168,31 +809,332 @@ pub enum EdgeState {
     Fixed = -1,
 }
 
-/// Depth-first branch-and-bound search exploring nodes recursively.
+/// State of a branch-and-bound search, shared by [`explore_nodes`], [`explore_nodes_best_first`]
+/// and [`bound_and_select_branch`].
+///
+/// The edge states, node penalties and fixed degrees describe the current node of the search, the
+/// remaining fields are carried from node to node.
+struct SearchState<'a> {
+    distances: &'a Matrix<Distance>,
+    scaled_distances: Matrix<ScaledDistance>,
+    config: &'a HeldKarpConfig,
+    /// Maximum number of nodes to explore, if any.
+    bb_limit: Option<usize>,
+    edge_states: Matrix<EdgeState>,
+    node_penalties: Vec<ScaledDistance>,
+    /// Number of fixed edges incident to each node.
+    fixed_degrees: Vec<u32>,
+    /// Cost of the best tour found so far.
+    upper_bound: Distance,
+    best_tour: Option<UnTour>,
+    /// Number of explored nodes.
+    bb_counter: usize,
+}
+
+/// A pending step of the depth-first branch-and-bound search in [`explore_nodes`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum SearchStep {
+    /// Compute the lower bound of the current node and branch on one of its edges.
+    Explore { depth: usize },
+    /// Set the state of the edge to the branching decision and explore the resulting child.
+    Branch {
+        edge: UnEdge,
+        state: EdgeState,
+        depth: usize,
+    },
+    /// Undo a branching decision, that is, make the edge available again.
+    Undo { edge: UnEdge, state: EdgeState },
+}
+
+/// Depth-first branch-and-bound search using an explicit stack of [`SearchStep`]s.
 /// Computes a lower bound at each node using Held-Karp lower bound computation and then branches
 /// on an edge from the resulting 1-tree.
 ///
-/// TODO: Summarize arguments in Held-Karp State Struct or Smth
+/// Instead of recursing, every branching decision pushes an undo record onto the stack, which
+/// restores `edge_states` and `fixed_degrees` once the subtree below the decision is explored. This
+/// keeps the memory of deep searches on the heap and visits the nodes in the same order as a
+/// recursive search that explores the branch excluding the edge first.
+///
+/// The search starts with the given stack, which is a single [`SearchStep::Explore`] of the root
+/// for a new search or the stack of a [`Checkpoint`] for a resumed one.
+///
+/// Returns `None` if the search tree has been explored completely, or a [`Checkpoint`] of the
+/// search if the [time limit](HeldKarpConfig::time_limit) has been reached. In the latter case, the
+/// edge states and fixed degrees are not restored. If [`HeldKarpConfig::checkpoint`] is set,
+/// checkpoints are also written to disk periodically.
+///
 /// TODO: Possibly remove upper_bound as best_tour.cost already contains that information
-fn explore_node(
-    distances: &Matrix<Distance>,
-    scaled_distances: &Matrix<ScaledDistance>,
-    edge_states: &mut Matrix<EdgeState>,
-    node_penalties: &mut [ScaledDistance],
-    fixed_degrees: &mut [u32],
-    upper_bound: &mut Distance,
-    best_tour: &mut Option<UnTour>,
-    bb_counter: &mut usize,
-    bb_limit: Option<usize>,
+fn explore_nodes(state: &mut SearchState, mut stack: Vec<SearchStep>) -> Option<Checkpoint> {
+    let config = state.config;
+    let deadline = config
+        .time_limit
+        .map(|time_limit| Instant::now() + time_limit);
+    #[cfg(feature = "serde")]
+    let mut last_checkpoint = Instant::now();
+
+    while let Some(step) = stack.pop() {
+        match step {
+            SearchStep::Explore { depth } => {
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    debug!("Stopping the search after reaching the time limit");
+                    stack.push(step);
+                    let checkpoint = Checkpoint::new(stack, state);
+                    #[cfg(feature = "serde")]
+                    if let Some(checkpoint_config) = &config.checkpoint {
+                        checkpoint_config.write(&checkpoint);
+                    }
+                    return Some(checkpoint);
+                }
+                #[cfg(feature = "serde")]
+                if let Some(checkpoint_config) = &config.checkpoint
+                    && last_checkpoint.elapsed() >= checkpoint_config.interval
+                {
+                    // The checkpoint has to contain the current step to explore this node again
+                    let checkpoint = Checkpoint::new([stack.as_slice(), &[step]].concat(), state);
+                    checkpoint_config.write(&checkpoint);
+                    last_checkpoint = Instant::now();
+                }
+                let Some((branching_edge, _)) = bound_and_select_branch(state, depth) else {
+                    continue;
+                };
+
+                // The stack is LIFO, so the branch excluding the edge is explored first
+                stack.push(SearchStep::Branch {
+                    edge: branching_edge,
+                    state: EdgeState::Fixed,
+                    depth: depth + 1,
+                });
+                stack.push(SearchStep::Branch {
+                    edge: branching_edge,
+                    state: EdgeState::Excluded,
+                    depth: depth + 1,
+                });
+            }
+            SearchStep::Branch {
+                edge,
+                state: edge_state,
+                depth,
+            } => {
+                let fixed_degrees = &mut state.fixed_degrees;
+                if edge_state == EdgeState::Fixed {
+                    // We might not be able to explore this branch, if the edge inclusion would
+                    // violate the already fixed degrees / edges.
+                    if fixed_degrees[edge.from.0] >= 2 || fixed_degrees[edge.to.0] >= 2 {
+                        continue;
+                    }
+                    fixed_degrees[edge.from.0] += 1;
+                    fixed_degrees[edge.to.0] += 1;
+                }
+                state
+                    .edge_states
+                    .set_data_symmetric(edge.from, edge.to, edge_state);
+
+                stack.push(SearchStep::Undo {
+                    edge,
+                    state: edge_state,
+                });
+                stack.push(SearchStep::Explore { depth });
+            }
+            SearchStep::Undo {
+                edge,
+                state: edge_state,
+            } => {
+                // Backtrack
+                state
+                    .edge_states
+                    .set_data_symmetric(edge.from, edge.to, EdgeState::Available);
+                if edge_state == EdgeState::Fixed {
+                    state.fixed_degrees[edge.from.0] -= 1;
+                    state.fixed_degrees[edge.to.0] -= 1;
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// An open node of the best-first branch-and-bound search in [`explore_nodes_best_first`].
+#[derive(Debug)]
+struct OpenNode {
+    /// Lower bound of the parent, which is also a lower bound of this node.
+    lower_bound: Distance,
     depth: usize,
-) {
+    edge_states: Matrix<EdgeState>,
+    fixed_degrees: Vec<u32>,
+    /// Node penalties of the parent, from which the subgradient optimization of this node starts.
+    node_penalties: Vec<ScaledDistance>,
+}
+
+impl Ord for OpenNode {
+    /// Orders by the lower bound in reverse, such that [`BinaryHeap`] pops the node with the
+    /// smallest lower bound first, and then by depth.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .lower_bound
+            .cmp(&self.lower_bound)
+            .then(self.depth.cmp(&other.depth))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+/// Best-first branch-and-bound search using a priority queue of [`OpenNode`]s, see
+/// [`SearchOrder::BestFirst`].
+///
+/// Each open node carries a snapshot of the edge states, fixed degrees and node penalties, which
+/// are copied into the search state before the node is explored. Unlike [`explore_nodes`], the
+/// state of the last explored node is left behind.
+/// Returns whether the search tree has been explored completely, that is, `false` if the
+/// [time limit](HeldKarpConfig::time_limit) has been reached.
+fn explore_nodes_best_first(state: &mut SearchState) -> bool {
+    let deadline = state
+        .config
+        .time_limit
+        .map(|time_limit| Instant::now() + time_limit);
+    let mut open_nodes = BinaryHeap::from([OpenNode {
+        lower_bound: Distance(0),
+        depth: 0,
+        edge_states: state.edge_states.clone(),
+        fixed_degrees: state.fixed_degrees.clone(),
+        node_penalties: state.node_penalties.clone(),
+    }]);
+
+    while let Some(node) = open_nodes.pop() {
+        if node.lower_bound >= state.upper_bound {
+            // All remaining nodes have at least this lower bound, so none can improve the tour
+            break;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            debug!("Stopping the search after reaching the time limit");
+            return false;
+        }
+        state.edge_states = node.edge_states;
+        state.fixed_degrees = node.fixed_degrees;
+        state.node_penalties = node.node_penalties;
+
+        let Some((branching_edge, lower_bound)) = bound_and_select_branch(state, node.depth) else {
+            continue;
+        };
+
+        let SearchState {
+            edge_states,
+            fixed_degrees,
+            node_penalties,
+            ..
+        } = &mut *state;
+        let mut excluded_edge_states = edge_states.clone();
+        excluded_edge_states.set_data_symmetric(
+            branching_edge.from,
+            branching_edge.to,
+            EdgeState::Excluded,
+        );
+        open_nodes.push(OpenNode {
+            lower_bound,
+            depth: node.depth + 1,
+            edge_states: excluded_edge_states,
+            fixed_degrees: fixed_degrees.clone(),
+            node_penalties: node_penalties.clone(),
+        });
+
+        // The branch including the edge might violate the already fixed degrees / edges
+        if fixed_degrees[branching_edge.from.0] < 2 && fixed_degrees[branching_edge.to.0] < 2 {
+            edge_states.set_data_symmetric(
+                branching_edge.from,
+                branching_edge.to,
+                EdgeState::Fixed,
+            );
+            fixed_degrees[branching_edge.from.0] += 1;
+            fixed_degrees[branching_edge.to.0] += 1;
+            open_nodes.push(OpenNode {
+                lower_bound,
+                depth: node.depth + 1,
+                edge_states: edge_states.clone(),
+                fixed_degrees: fixed_degrees.clone(),
+                node_penalties: node_penalties.clone(),
+            });
+        }
+    }
+
+    true
+}
+
+/// Depth-first branch-and-bound search exploring nodes recursively.
+///
+/// Reference implementation of [`explore_nodes`], which is used to check that both explore the
+/// same search tree.
+#[cfg(test)]
+fn explore_node_recursive(state: &mut SearchState, depth: usize) {
+    let Some((branching_edge, _)) = bound_and_select_branch(state, depth) else {
+        return;
+    };
+    let UnEdge { from, to } = branching_edge;
+
+    // Explore the branch excluding the edge
+    state
+        .edge_states
+        .set_data_symmetric(from, to, EdgeState::Excluded);
+    explore_node_recursive(state, depth + 1);
+    state
+        .edge_states
+        .set_data_symmetric(from, to, EdgeState::Available);
+
+    // Try exploring the branch including the edge
+    if (state.fixed_degrees[from.0] < 2) && (state.fixed_degrees[to.0] < 2) {
+        state
+            .edge_states
+            .set_data_symmetric(from, to, EdgeState::Fixed);
+        state.fixed_degrees[from.0] += 1;
+        state.fixed_degrees[to.0] += 1;
+
+        explore_node_recursive(state, depth + 1);
+
+        // Backtrack
+        state
+            .edge_states
+            .set_data_symmetric(from, to, EdgeState::Available);
+        state.fixed_degrees[from.0] -= 1;
+        state.fixed_degrees[to.0] -= 1;
+    }
+}
+
+/// Process a single node of the branch-and-bound search.
+///
+/// Computes the Held-Karp lower bound of the node, updating the best tour if the bound computation
+/// found a new one. Returns the edge to branch on together with the lower bound of the node, or
+/// `None` if the node can be pruned.
+fn bound_and_select_branch(state: &mut SearchState, depth: usize) -> Option<(UnEdge, Distance)> {
+    let SearchState {
+        distances,
+        scaled_distances,
+        config,
+        bb_limit,
+        edge_states,
+        node_penalties,
+        fixed_degrees: _,
+        upper_bound,
+        best_tour,
+        bb_counter,
+    } = state;
+
     // Increment the branch count
     *bb_counter += 1;
 
-    if let Some(limit) = bb_limit {
-        if *bb_counter >= limit {
-            return;
-        }
+    if bb_limit.is_some_and(|limit| *bb_counter >= limit) {
+        return None;
     }
 
     let (max_iterations, beta) = if depth == 0 {
@@ -201,21 +1143,27 @@ fn explore_node(
         (MAX_ITERATIONS, BETA)
     };
 
-    let one_tree = match held_karp_lower_bound(
+    let params = SubgradientParams {
+        max_iterations,
+        initial_alpha: INITIAL_ALPHA,
+        beta,
+        one_tree_threads: config.one_tree_threads,
+    };
+    let (lower_bound, one_tree) = match held_karp_lower_bound(
         distances,
         scaled_distances,
         edge_states,
         node_penalties,
         *upper_bound,
-        max_iterations,
-        beta,
+        &params,
     ) {
         Some(LowerBoundOutput::Tour(tour)) => {
             // Found a new tour, that is, an upper bound
             debug!("Found a new best tour with cost {}", tour.cost.0);
             *upper_bound = tour.cost;
+            publish_incumbent(config, &tour);
             *best_tour = Some(tour);
-            return;
+            return None;
         }
         Some(LowerBoundOutput::LowerBound(lower_bound, one_tree)) => {
             // Check if the lower bound is better than the current best cost
@@ -225,77 +1173,95 @@ fn explore_node(
                     "Pruning node with lower bound {} >= upper bound {}",
                     lower_bound.0, upper_bound.0
                 );
-                return;
+                return None;
             } else {
-                one_tree
+                (lower_bound, one_tree)
             }
         }
         None => {
             // Infeasible node, prune
-            return;
+            return None;
         }
     };
 
-    let Some(branching_edge) =
-        edge_to_branch_on(scaled_distances, edge_states, node_penalties, &one_tree)
-    else {
-        // No edge to branch on, so we prune
-        return;
-    };
-
-    // Explore the branch excluding the edge
-    {
-        edge_states.set_data_symmetric(branching_edge.from, branching_edge.to, EdgeState::Excluded);
-
-        explore_node(
-            distances,
+    if depth == 0 && config.eliminate_edges {
+        let eliminated = eliminate_edges(
             scaled_distances,
             edge_states,
             node_penalties,
-            fixed_degrees,
-            upper_bound,
-            best_tour,
-            bb_counter,
-            bb_limit,
-            depth + 1,
+            &one_tree,
+            *upper_bound,
         );
+        debug!("Eliminated {eliminated} edges by their reduced costs");
+    }
 
-        edge_states.set_data_symmetric(
-            branching_edge.from,
-            branching_edge.to,
-            EdgeState::Available,
-        );
+    let Some(branching_edge) = edge_to_branch_on(
+        scaled_distances,
+        &*edge_states,
+        node_penalties,
+        &one_tree,
+        config.branch_strategy,
+    ) else {
+        // No edge to branch on, so we prune
+        return None;
+    };
+
+    Some((branching_edge, lower_bound))
+}
+
+/// Excludes the missing edges, that is, the ones with distance [`Distance::MAX`].
+///
+/// They cannot be part of a tour, and their reduced costs would overflow once the node penalties
+/// become negative.
+fn exclude_missing_edges(distances: &Matrix<Distance>, edge_states: &mut Matrix<EdgeState>) {
+    for from in 0..distances.dimension() {
+        for (to, distance) in distances.iter_row(Node(from)) {
+            if distance >= Distance::MAX {
+                edge_states.set_data(Node(from), to, EdgeState::Excluded);
+            }
+        }
     }
+}
 
-    // Try exploring the branch including the edge.
-    // That is, we might not be able to explore this branch, if we the edge inclusion would violate
-    // the already fixed degrees / edges.
-    if (fixed_degrees[branching_edge.from.0] < 2) && (fixed_degrees[branching_edge.to.0] < 2) {
-        edge_states.set_data_symmetric(branching_edge.from, branching_edge.to, EdgeState::Fixed);
-        fixed_degrees[branching_edge.from.0] += 1;
-        fixed_degrees[branching_edge.to.0] += 1;
+/// Returns the upper bound to start the search with, that is, the cost of the initial tour capped
+/// at [`finite_upper_bound`].
+fn initial_upper_bound_cost(
+    distances: &Matrix<Distance>,
+    initial_tour: Option<&UnTour>,
+) -> Distance {
+    let finite_upper_bound = finite_upper_bound(distances);
+    initial_tour.map_or(finite_upper_bound, |tour| tour.cost.min(finite_upper_bound))
+}
 
-        explore_node(
-            distances,
-            scaled_distances,
-            edge_states,
-            node_penalties,
-            fixed_degrees,
-            upper_bound,
-            best_tour,
-            bb_counter,
-            bb_limit,
-            depth + 1,
-        );
+/// Returns one more than the largest cost of a tour using only finite edges (see
+/// [`finite_cost_bound`]), capped at [`Distance::MAX`].
+///
+/// This is the upper bound as long as only tours using missing edges are known, e.g. for graphs
+/// with missing edges. Using [`Distance::MAX`] instead would make the step sizes of the
+/// subgradient optimization, which grow with the gap between the bounds, overflow the penalties.
+fn finite_upper_bound(distances: &Matrix<Distance>) -> Distance {
+    let bound = (finite_cost_bound(distances) + 1).min(i64::from(Distance::MAX.0));
+    Distance(bound as i32)
+}
 
-        // Backtrack
-        edge_states.set_data_symmetric(
-            branching_edge.from,
-            branching_edge.to,
-            EdgeState::Available,
-        );
-        fixed_degrees[branching_edge.from.0] -= 1;
-        fixed_degrees[branching_edge.to.0] -= 1;
+/// Returns the initial tour with its cost recomputed from the distances, or `None` if its edges
+/// do not form a single tour through all nodes.
+///
+/// The reported cost is not trusted, as a cost which is too low would prune the search too much.
+/// The initial tour might use missing edges, so the cost is capped to avoid overflows.
+fn checked_initial_tour(distances: &Matrix<Distance>, tour: UnTour) -> Option<UnTour> {
+    tour.to_order(distances.dimension()).ok()?;
+    let cost = tour.compute_cost(distances).min(Distance::MAX);
+    Some(UnTour { cost, ..tour })
+}
+
+/// Stores the given tour in the shared incumbent of the config, if there is one.
+fn publish_incumbent(config: &HeldKarpConfig, tour: &UnTour) {
+    if let Some(incumbent) = &config.incumbent {
+        // Clone outside of the lock to keep the critical section short
+        let tour = tour.clone();
+        let mut incumbent = incumbent.lock().unwrap_or_else(PoisonError::into_inner);
+        *incumbent = Some(tour);
     }
 }
 
@@ -311,9 +1277,29 @@ fn held_karp_lower_bound(
     edge_states: &Matrix<EdgeState>,
     node_penalties: &mut [ScaledDistance],
     upper_bound: Distance,
-    max_iterations: usize,
-    beta: f64,
+    params: &SubgradientParams,
 ) -> Option<LowerBoundOutput> {
+    held_karp_lower_bound_with_termination(
+        distances,
+        scaled_distances,
+        edge_states,
+        node_penalties,
+        upper_bound,
+        params,
+    )
+    .0
+}
+
+/// Like [`held_karp_lower_bound`], but additionally returns the number of computed 1-trees and why
+/// the subgradient optimization stopped.
+fn held_karp_lower_bound_with_termination(
+    distances: &Matrix<Distance>,
+    scaled_distances: &Matrix<ScaledDistance>,
+    edge_states: &Matrix<EdgeState>,
+    node_penalties: &mut [ScaledDistance],
+    upper_bound: Distance,
+    params: &SubgradientParams,
+) -> (Option<LowerBoundOutput>, usize, SubgradientTermination) {
     let scaled_upper_bound = ScaledDistance::from_distance(upper_bound);
 
     // Tracks the current best lower bound found
@@ -321,17 +1307,27 @@ fn held_karp_lower_bound(
 
     let mut iter_count = 0;
 
-    let mut alpha = INITIAL_ALPHA;
+    let mut alpha = params.initial_alpha;
 
-    let node_penalty_sum: ScaledDistance = node_penalties.iter().sum();
+    let mut one_trees_computed = 0;
 
-    let one_tree = loop {
-        let one_tree = min_one_tree(scaled_distances, edge_states, node_penalties)?;
+    let (one_tree, termination) = loop {
+        let Some(one_tree) = min_one_tree_parallel(
+            scaled_distances,
+            edge_states,
+            node_penalties,
+            params.one_tree_threads,
+        ) else {
+            return (None, one_trees_computed, SubgradientTermination::Converged);
+        };
+        one_trees_computed += 1;
 
         // Compute the cost of the 1-tree with penalties. This is simultaneously the value of
         // the lagrangian relaxation and thus a lower bound (possibly an upper bound too, if it is a
         // tour).
         let one_tree_cost = {
+            // The penalties change in every iteration, so their sum has to be recomputed
+            let node_penalty_sum: ScaledDistance = node_penalties.iter().sum();
             let mut base_cost = 2 * node_penalty_sum;
 
             for edge in &one_tree {
@@ -353,44 +1349,44 @@ fn held_karp_lower_bound(
                 "Pruning in held_karp_lower_bound due to lower bound {} >= upper bound {}",
                 one_tree_cost.0, scaled_upper_bound.0
             );
-            break one_tree;
+            break (one_tree, SubgradientTermination::Converged);
         }
 
         // Next we check the degrees of the nodes in the 1-tree
-        // Deg[node] can be interpreted as follows:
-        //  Deg[node] < 0: Node has degree > 2 -> we need to decrease its penalty. This makes edges
+        // The deviation (degree - 2) of a node can be interpreted as follows:
+        //  deviation > 0: Node has degree > 2 -> we need to decrease its penalty. This makes edges
         //                 incident to node more expensive, that is, less likely to be selected.
-        //  Deg[node] > 0: Node has degree < 2 -> we need to increase its penalty. This makes edges
+        //  deviation < 0: Node has degree < 2 -> we need to increase its penalty. This makes edges
         //                 incident to node cheaper, that is, more likely to be selected.
-        //  Deg[node] == 0: Node has degree == 2 -> no change to penalty.
-        let mut deg = vec![2i32; distances.dimension()];
+        //  deviation == 0: Node has degree == 2 -> no change to penalty.
+        let deviations = one_tree_degrees(&one_tree, distances.dimension());
 
-        for edge in &one_tree {
-            deg[edge.from.0] -= 1;
-            deg[edge.to.0] -= 1;
-        }
-
-        let square_sum = deg.iter().map(|&d| d * d).sum::<i32>();
+        let square_sum = deviations.iter().map(|&d| d * d).sum::<i32>();
 
         if square_sum == 0 {
             // Found a tour
-            let cost: Distance = one_tree
-                .iter()
-                .map(|edge| distances.get_data(edge.from, edge.to))
-                .sum();
+            let cost = Distance::saturating_sum(
+                one_tree
+                    .iter()
+                    .map(|edge| distances.get_data(edge.from, edge.to)),
+            );
 
-            return Some(LowerBoundOutput::Tour(UnTour {
-                edges: one_tree,
-                cost,
-            }));
+            return (
+                Some(LowerBoundOutput::Tour(UnTour {
+                    edges: one_tree,
+                    cost,
+                })),
+                one_trees_computed,
+                SubgradientTermination::Converged,
+            );
         }
 
         // We have not found a tour yet, so we want to update the penalties
         iter_count += 1;
 
-        if iter_count >= max_iterations {
+        if iter_count >= params.max_iterations {
             // Reached maximum iterations
-            break one_tree;
+            break (one_tree, SubgradientTermination::MaxIterations);
         }
 
         // TODO: Research on subgradient method for non-smooth optimization to find out more about
@@ -401,50 +1397,199 @@ fn held_karp_lower_bound(
 
         if step_size <= 3 {
             // Step size is very small (<= 3 in scaled), we probably won't be making much progress
-            break one_tree;
+            break (one_tree, SubgradientTermination::SmallStep);
         }
 
-        alpha *= beta;
+        alpha *= params.beta;
 
         // Update penalties based on degree deviations and step size
         // TODO: Handle overflows
-        for (node_penalty, &d) in node_penalties.iter_mut().zip(deg.iter()) {
-            let adjustment = ScaledDistance(step_size * d);
-            *node_penalty += adjustment;
+        for (node_penalty, &deviation) in node_penalties.iter_mut().zip(deviations.iter()) {
+            let adjustment = ScaledDistance(step_size * deviation);
+            *node_penalty -= adjustment;
         }
     };
 
     let best_lower_bound = scaled_best_lower_bound.to_distance_rounded_up();
 
-    Some(LowerBoundOutput::LowerBound(best_lower_bound, one_tree))
+    (
+        Some(LowerBoundOutput::LowerBound(best_lower_bound, one_tree)),
+        one_trees_computed,
+        termination,
+    )
 }
 
-/// Select an edge from the 1-tree to branch on.
+/// Select an edge from the 1-tree to branch on according to the given strategy.
 ///
-/// The edge with the minimum reduced cost (edge_cost - node_penalties[from] - node_penalties[to])
-/// among available edges is selected for branching.
+/// See [`BranchStrategy`] for the available strategies. Only available edges are considered.
 fn edge_to_branch_on(
-    scaled_distances: &Matrix<ScaledDistance>,
-    edge_states: &Matrix<EdgeState>,
+    scaled_distances: &impl ReadDistance<Data = ScaledDistance>,
+    edge_states: &impl ReadDistance<Data = EdgeState>,
     node_penalties: &[ScaledDistance],
     one_tree: &[UnEdge],
+    branch_strategy: BranchStrategy,
 ) -> Option<UnEdge> {
-    let mut minimum_edge = None;
-    let mut minimum_edge_distance = ScaledDistance::MAX;
+    let reduced_distance = |edge: &UnEdge| {
+        scaled_distances.get(edge.from, edge.to)
+            - node_penalties[edge.from.0]
+            - node_penalties[edge.to.0]
+    };
+    let available_edges = one_tree
+        .iter()
+        .filter(|edge| edge_states.get(edge.from, edge.to) == EdgeState::Available);
+
+    match branch_strategy {
+        BranchStrategy::CheapestReducedCost => {
+            let mut minimum_edge = None;
+            let mut minimum_edge_distance = ScaledDistance::MAX;
+
+            for edge in available_edges {
+                let reduced_distance = reduced_distance(edge);
+                if reduced_distance < minimum_edge_distance {
+                    minimum_edge_distance = reduced_distance;
+                    minimum_edge = Some(*edge);
+                }
+            }
+
+            minimum_edge
+        }
+        BranchStrategy::MostConstrainedNode => {
+            let deviations = one_tree_degrees(one_tree, node_penalties.len());
+            let deviation = |node: Node| deviations[node.0].unsigned_abs();
+
+            // Maximize the deviation, then minimize the reduced distance
+            available_edges
+                .map(|edge| {
+                    let edge_deviation = deviation(edge.from).max(deviation(edge.to));
+                    (edge, edge_deviation, reduced_distance(edge))
+                })
+                .min_by(
+                    |(_, deviation_a, distance_a), (_, deviation_b, distance_b)| {
+                        deviation_b
+                            .cmp(deviation_a)
+                            .then(distance_a.cmp(distance_b))
+                    },
+                )
+                .map(|(edge, ..)| *edge)
+        }
+    }
+}
 
+/// Permanently excludes edges which cannot be part of a tour cheaper than the upper bound and
+/// returns the number of excluded edges.
+///
+/// The given 1-tree has to be a minimum 1-tree with respect to the edge states and the given node
+/// penalties. Then, the cheapest 1-tree containing an additional available edge is obtained by
+/// adding the edge and removing the most expensive non-fixed edge (in terms of reduced costs) of
+/// the resulting cycle, or, for edges incident to node 0, the more expensive non-fixed edge of
+/// node 0. If the cost of that 1-tree is at least the upper bound, no tour cheaper than the upper
+/// bound contains the edge, so it is set to [`EdgeState::Excluded`]. Edges whose cycle only
+/// consists of fixed edges are excluded as well, as they would close a subtour.
+///
+/// Intended to be called with the penalties of the bound computation at the root of the search,
+/// as the excluded edges are never made available again.
+fn eliminate_edges(
+    scaled_distances: &Matrix<ScaledDistance>,
+    edge_states: &mut Matrix<EdgeState>,
+    node_penalties: &[ScaledDistance],
+    one_tree: &[UnEdge],
+    upper_bound: Distance,
+) -> usize {
+    let dimension = scaled_distances.dimension();
+    // Costs are summed up as i64, as edges of missing distances would overflow the sums
+    let reduced_cost = |from: Node, to: Node| {
+        i64::from(scaled_distances.get_data(from, to).0)
+            - i64::from(node_penalties[from.0].0)
+            - i64::from(node_penalties[to.0].0)
+    };
+    // The most expensive edge which may be replaced, `None` if all edges are fixed
+    let removable_cost = |edges: &mut dyn Iterator<Item = &UnEdge>| {
+        edges
+            .filter(|edge| edge_states.get_data(edge.from, edge.to) != EdgeState::Fixed)
+            .map(|edge| reduced_cost(edge.from, edge.to))
+            .max()
+    };
+
+    let penalty_sum: i64 = node_penalties
+        .iter()
+        .map(|penalty| i64::from(penalty.0))
+        .sum();
+    let one_tree_cost = 2 * penalty_sum
+        + one_tree
+            .iter()
+            .map(|edge| reduced_cost(edge.from, edge.to))
+            .sum::<i64>();
+    let upper_bound = i64::from(ScaledDistance::from_distance(upper_bound).0);
+    let one_tree_edges = FixedEdgeSet::from_edges(one_tree);
+
+    let mut tree_neighbors = vec![Vec::new(); dimension];
     for edge in one_tree {
-        if edge_states.get_data(edge.from, edge.to) == EdgeState::Available {
-            let reduced_distance = scaled_distances.get_data(edge.from, edge.to)
-                - node_penalties[edge.from.0]
-                - node_penalties[edge.to.0];
-            if reduced_distance < minimum_edge_distance {
-                minimum_edge_distance = reduced_distance;
-                minimum_edge = Some(*edge);
+        if edge.from != Node(0) && edge.to != Node(0) {
+            tree_neighbors[edge.from.0].push(edge.to);
+            tree_neighbors[edge.to.0].push(edge.from);
+        }
+    }
+
+    let mut to_exclude = Vec::new();
+    let mut is_excluded = |from: Node, to: Node, replaced_cost: Option<i64>| {
+        if edge_states.get_data(from, to) != EdgeState::Available
+            || one_tree_edges.contains_nodes(from, to)
+        {
+            return;
+        }
+        let excluded = match replaced_cost {
+            Some(replaced_cost) => {
+                one_tree_cost + reduced_cost(from, to) - replaced_cost >= upper_bound
+            }
+            None => true,
+        };
+        if excluded {
+            to_exclude.push(UnEdge::new(from, to));
+        }
+    };
+
+    // Edges incident to node 0 replace one of the two edges of node 0 in the 1-tree
+    let zero_replaced_cost = removable_cost(
+        &mut one_tree
+            .iter()
+            .filter(|edge| edge.from == Node(0) || edge.to == Node(0)),
+    );
+    for to in 1..dimension {
+        is_excluded(Node(0), Node(to), zero_replaced_cost);
+    }
+
+    // Other edges replace the most expensive edge on the path between their endpoints in the tree
+    let mut path_cost: Vec<Option<i64>> = vec![None; dimension];
+    let mut visited = vec![false; dimension];
+    let mut stack = Vec::new();
+    for root in 1..dimension {
+        visited.fill(false);
+        visited[root] = true;
+        path_cost[root] = None;
+        stack.push(Node(root));
+        while let Some(node) = stack.pop() {
+            for &neighbor in &tree_neighbors[node.0] {
+                if visited[neighbor.0] {
+                    continue;
+                }
+                visited[neighbor.0] = true;
+                path_cost[neighbor.0] = if edge_states.get_data(node, neighbor) == EdgeState::Fixed
+                {
+                    path_cost[node.0]
+                } else {
+                    let cost = reduced_cost(node, neighbor);
+                    Some(path_cost[node.0].map_or(cost, |path_cost| path_cost.max(cost)))
+                };
+                stack.push(neighbor);
             }
         }
+        for (to, &replaced_cost) in path_cost.iter().enumerate().skip(root + 1) {
+            is_excluded(Node(root), Node(to), replaced_cost);
+        }
     }
 
-    minimum_edge
+    edge_states.set_edges(&to_exclude, EdgeState::Excluded);
+    to_exclude.len()
 }
 
 /// Initializes node penalties for Lagrangian relaxation.
@@ -474,3 +1619,142 @@ fn initial_penalties(
 
     penalties
 }
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::instance::TSPSymInstance;
+
+    use super::*;
+
+    /// Run the branch-and-bound search with the given explorer from the same initial state as
+    /// [`held_karp_with_config`] and return the best tour and the number of explored nodes.
+    fn run_search(
+        distances: &Matrix<Distance>,
+        config: &HeldKarpConfig,
+        explore: impl FnOnce(&mut SearchState),
+    ) -> (UnTour, usize) {
+        let dimension = distances.dimension();
+        let scaled_distances = Matrix::new(
+            distances
+                .data()
+                .iter()
+                .map(|&d| ScaledDistance::from_distance(d))
+                .collect(),
+            dimension,
+        );
+        let edge_states = Matrix::new(
+            vec![EdgeState::Available; distances.data().len()],
+            dimension,
+        );
+        let node_penalties = initial_penalties(&scaled_distances, dimension);
+
+        let initial_tour = nearest_neighbor(distances, Node(0)).to_un_tour(distances);
+        let mut state = SearchState {
+            distances,
+            scaled_distances,
+            config,
+            bb_limit: None,
+            edge_states,
+            node_penalties,
+            fixed_degrees: vec![0; dimension],
+            upper_bound: initial_tour.cost,
+            best_tour: Some(initial_tour),
+            bb_counter: 0,
+        };
+
+        explore(&mut state);
+
+        // The search has to leave the state as it found it
+        assert!(
+            state
+                .edge_states
+                .data()
+                .iter()
+                .all(|&state| state == EdgeState::Available)
+        );
+        assert!(state.fixed_degrees.iter().all(|&degree| degree == 0));
+
+        (state.best_tour.unwrap(), state.bb_counter)
+    }
+
+    #[test]
+    fn test_iterative_search_matches_recursive_search() {
+        let instance: TSPSymInstance<Matrix<Distance>> =
+            tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap();
+        let distances = instance.distance_matrix();
+        let config = HeldKarpConfig::default();
+
+        let (iterative_tour, iterative_nodes) = run_search(distances, &config, |state| {
+            explore_nodes(state, vec![SearchStep::Explore { depth: 0 }]);
+        });
+        let (recursive_tour, recursive_nodes) =
+            run_search(distances, &config, |state| explore_node_recursive(state, 0));
+
+        assert_eq!(iterative_tour.cost, Distance(10628));
+        assert_eq!(iterative_tour.cost, recursive_tour.cost);
+        assert_eq!(iterative_tour.edges, recursive_tour.edges);
+        assert_eq!(iterative_nodes, recursive_nodes);
+    }
+
+    #[test]
+    fn test_eliminate_edges_keeps_optimal_tour() {
+        let instance: TSPSymInstance<Matrix<Distance>> =
+            tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap();
+        let distances = instance.distance_matrix();
+        let optimal_tour =
+            held_karp_with_initial_tour(distances, &HeldKarpConfig::default(), |distances| {
+                nearest_neighbor(distances, Node(0)).to_un_tour(distances)
+            })
+            .unwrap()
+            .tour;
+
+        let dimension = distances.dimension();
+        let scaled_distances = Matrix::new(
+            distances
+                .data()
+                .iter()
+                .map(|&d| ScaledDistance::from_distance(d))
+                .collect(),
+            dimension,
+        );
+        let mut edge_states = Matrix::new(
+            vec![EdgeState::Available; distances.data().len()],
+            dimension,
+        );
+        let mut node_penalties = initial_penalties(&scaled_distances, dimension);
+        // Any tour cheaper than this upper bound has to survive the elimination
+        let upper_bound = optimal_tour.cost + Distance(1);
+        let Some(LowerBoundOutput::LowerBound(_, one_tree)) = held_karp_lower_bound(
+            distances,
+            &scaled_distances,
+            &edge_states,
+            &mut node_penalties,
+            upper_bound,
+            &SubgradientParams::default(),
+        ) else {
+            panic!("The root bound of att48 should not be a tour");
+        };
+
+        let eliminated = eliminate_edges(
+            &scaled_distances,
+            &mut edge_states,
+            &node_penalties,
+            &one_tree,
+            upper_bound,
+        );
+
+        let excluded = edge_states
+            .data()
+            .iter()
+            .filter(|&&state| state == EdgeState::Excluded)
+            .count();
+        assert!(eliminated > 0);
+        assert_eq!(excluded, 2 * eliminated);
+        for edge in &optimal_tour.edges {
+            assert_eq!(
+                edge_states.get_data(edge.from, edge.to),
+                EdgeState::Available
+            );
+        }
+    }
+}
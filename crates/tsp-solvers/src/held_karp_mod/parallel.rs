@@ -9,12 +9,16 @@ use tsp_core::instance::{
     distance::{Distance, ScaledDistance},
     edge::UnEdge,
     matrix::Matrix,
-    node::Node,
 };
 
-use crate::held_karp_mod::{
-    BETA, EdgeState, INITIAL_ALPHA, INITIAL_BETA, INITIAL_MAX_ITERATIONS, MAX_ITERATIONS,
-    edge_to_branch_on, initial_penalties, min_one_tree,
+use crate::{
+    SolverError,
+    feasibility::{check_cost_headroom, check_finite_graph},
+    held_karp_mod::{
+        BETA, BranchStrategy, EdgeState, INITIAL_ALPHA, INITIAL_BETA, INITIAL_MAX_ITERATIONS,
+        MAX_ITERATIONS, checked_initial_tour, edge_to_branch_on, identity_tour, initial_penalties,
+        min_one_tree, one_tree_degrees,
+    },
 };
 
 ///  TODO: Adapt documentation
@@ -23,9 +27,84 @@ use crate::held_karp_mod::{
 ///
 /// For a detailed explanation of the algorithm, see the [module-level
 /// documentation][crate::held_karp_mod].
-pub fn held_karp_parallel(distances: &Matrix<Distance>) -> Option<UnTour> {
+///
+/// Uses the default [`ParallelConfig`], see [`held_karp_parallel_with_config`] for more control.
+pub fn held_karp_parallel(distances: &Matrix<Distance>) -> Result<UnTour, SolverError> {
+    held_karp_parallel_with_config(distances, &ParallelConfig::default())
+}
+
+/// Configuration of the parallel Held-Karp solver, see [`held_karp_parallel_with_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelConfig {
+    /// Maximum number of threads exploring the search tree at the same time. With a single
+    /// thread, the search is sequential.
+    ///
+    /// Defaults to 9.
+    pub threads: usize,
+    /// Whether the returned tour only depends on the instance and the initial tour, but neither on
+    /// the number of threads nor on their scheduling.
+    ///
+    /// Otherwise, the threads share the cost of the best tour found so far, which changes the
+    /// bounds and thus the explored nodes of other threads depending on when it is found. Then,
+    /// runs may return different tours of the same optimal cost. In the deterministic mode:
+    /// - The subgradient optimization uses the cost of the initial tour as its upper bound, and
+    ///   each child of a node starts from the node penalties of its parent. This makes the search
+    ///   tree independent of the order in which nodes are explored.
+    /// - Of several tours with the same cost, the one found first by a sequential depth-first
+    ///   search is returned. Nodes whose lower bound equals the cost of the best tour are only
+    ///   pruned if that tour precedes them in the depth-first order.
+    ///
+    /// This explores more nodes, as the bounds profit less from good tours found in the meantime.
+    ///
+    /// Defaults to `false`.
+    pub deterministic: bool,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self {
+            threads: 9,
+            deterministic: false,
+        }
+    }
+}
+
+/// Solve the Traveling Salesman Problem using the parallel Held-Karp algorithm with the given
+/// configuration.
+///
+/// The search is warm started with the [`identity_tour`], see
+/// [`held_karp_parallel_with_initial_tour`] to use a different heuristic.
+pub fn held_karp_parallel_with_config(
+    distances: &Matrix<Distance>,
+    config: &ParallelConfig,
+) -> Result<UnTour, SolverError> {
+    held_karp_parallel_with_initial_tour(distances, config, identity_tour)
+}
+
+/// Solve the Traveling Salesman Problem using the parallel Held-Karp algorithm with the given
+/// configuration, warm starting the search with the tour computed by `initial_upper_bound`.
+///
+/// The cost reported with the tour is ignored and recomputed from the distances. If the edges do
+/// not form a single tour through all nodes, the [`identity_tour`] is used instead. See
+/// [`held_karp_with_initial_tour`](super::held_karp_with_initial_tour) for the sequential solver.
+pub fn held_karp_parallel_with_initial_tour(
+    distances: &Matrix<Distance>,
+    config: &ParallelConfig,
+    initial_upper_bound: impl FnOnce(&Matrix<Distance>) -> UnTour,
+) -> Result<UnTour, SolverError> {
+    check_finite_graph(distances)?;
+    check_cost_headroom(distances)?;
+
+    // Instances with at most one node only have the empty tour, which 1-trees cannot represent
+    if distances.dimension() <= 1 {
+        return Ok(UnTour {
+            edges: Vec::new(),
+            cost: Distance(0),
+        });
+    }
+
     info!("Starting Held-Karp parallel solver for instance");
-    let mut edge_states = Matrix::new(
+    let edge_states = Matrix::new(
         vec![EdgeState::Available; distances.data().len()],
         distances.dimension(),
     );
@@ -39,66 +118,155 @@ pub fn held_karp_parallel(distances: &Matrix<Distance>) -> Option<UnTour> {
         distances.dimension(),
     );
 
-    let mut node_penalties = initial_penalties(&scaled_distances, distances.dimension());
-    let mut fixed_degrees = vec![0u32; distances.dimension()];
-    let mut bb_counter = 0;
+    let initial_tour = checked_initial_tour(distances, initial_upper_bound(distances))
+        .or_else(|| checked_initial_tour(distances, identity_tour(distances)))
+        .expect("The identity tour is a valid tour");
+    let search = ParallelSearch {
+        distances,
+        scaled_distances: &scaled_distances,
+        config,
+        bb_limit: None,
+        fixed_upper_bound: config.deterministic.then_some(initial_tour.cost),
+        best_tour: Mutex::new(Incumbent {
+            tour: initial_tour,
+            path: Vec::new(),
+        }),
+        threads_spawned: Mutex::new(1),
+    };
+    let mut thread = ThreadState {
+        edge_states,
+        node_penalties: initial_penalties(&scaled_distances, distances.dimension()),
+        fixed_degrees: vec![0; distances.dimension()],
+        bb_counter: 0,
+        path: Vec::new(),
+    };
 
-    let mut initial_upper_bound = Distance(0);
-    let mut initial_tour = Vec::with_capacity(distances.dimension());
-    for i in 0..distances.dimension() {
-        initial_tour.push(UnEdge {
-            from: Node(i),
-            to: Node((i + 1) % distances.dimension()),
-        });
-        initial_upper_bound += distances.get_data(Node(i), Node((i + 1) % distances.dimension()));
-    }
-    let best_tour = Arc::new(Mutex::new(UnTour {
-        edges: initial_tour,
-        cost: initial_upper_bound,
-    }));
+    explore_node_new_thread(&search, &mut thread, 0);
 
-    let threads_spawned = Arc::new(Mutex::new(1usize));
+    Ok(search.best_tour.into_inner().unwrap().tour)
+}
 
-    explore_node_new_thread(
-        distances,
-        &scaled_distances,
-        &mut edge_states,
-        node_penalties.as_mut_slice(),
-        fixed_degrees.as_mut_slice(),
-        best_tour.clone(),
-        &mut bb_counter,
-        None,
-        0,
-        threads_spawned,
-    );
+/// The best tour found so far, shared by all threads.
+#[derive(Debug)]
+struct Incumbent {
+    tour: UnTour,
+    /// The branching decisions leading to the node of the search tree at which the tour has been
+    /// found, where `false` excludes and `true` includes the branching edge. Empty for the initial
+    /// tour, which thus precedes all other tours in the depth-first order.
+    path: Vec<bool>,
+}
 
-    best_tour.lock().unwrap().clone().into()
+impl Incumbent {
+    /// Replaces the incumbent by the given tour if it is cheaper, or, in the deterministic mode,
+    /// if it costs the same but precedes the incumbent in the depth-first order.
+    fn offer(&mut self, tour: UnTour, path: &[bool], deterministic: bool) {
+        if tour.cost < self.tour.cost
+            || (deterministic && tour.cost == self.tour.cost && path < self.path.as_slice())
+        {
+            debug!("Found a new best tour with cost {}", tour.cost.0);
+            self.tour = tour;
+            self.path = path.to_vec();
+        }
+    }
+
+    /// Returns whether a node with the given lower bound and path can be pruned, as it cannot
+    /// lead to a tour replacing the incumbent.
+    fn prunes(&self, lower_bound: Distance, path: &[bool], deterministic: bool) -> bool {
+        if deterministic {
+            lower_bound > self.tour.cost
+                || (lower_bound == self.tour.cost && self.path.as_slice() < path)
+        } else {
+            lower_bound >= self.tour.cost
+        }
+    }
 }
 
-/// TODO: Adapt documentation
+/// Parameters of a parallel branch-and-bound search which are shared by all of its threads.
+struct ParallelSearch<'a> {
+    distances: &'a Matrix<Distance>,
+    scaled_distances: &'a Matrix<ScaledDistance>,
+    config: &'a ParallelConfig,
+    /// Maximum number of nodes to explore per thread, if any.
+    bb_limit: Option<usize>,
+    /// Upper bound used by the subgradient optimization instead of the cost of the best tour found
+    /// so far, see [`ParallelConfig::deterministic`].
+    fixed_upper_bound: Option<Distance>,
+    best_tour: Mutex<Incumbent>,
+    /// Number of threads currently exploring the search tree.
+    threads_spawned: Mutex<usize>,
+}
+
+/// State of a single thread of a parallel branch-and-bound search, which describes the node it
+/// currently explores.
 ///
+/// A spawned thread starts from a copy of the state of its parent thread.
+#[derive(Debug, Clone)]
+struct ThreadState {
+    edge_states: Matrix<EdgeState>,
+    node_penalties: Vec<ScaledDistance>,
+    /// Number of fixed edges incident to each node.
+    fixed_degrees: Vec<u32>,
+    /// Number of nodes explored by the thread, including the ones explored by its parent before
+    /// it was spawned.
+    bb_counter: usize,
+    /// The branching decisions leading to the current node, see [`Incumbent::path`].
+    path: Vec<bool>,
+}
+
+impl ThreadState {
+    /// Sets the state of the edge in both directions.
+    fn set_edge_state(&mut self, edge: UnEdge, state: EdgeState) {
+        self.edge_states
+            .set_data_symmetric(edge.from, edge.to, state);
+    }
+
+    /// Fixes the edge and records the decision in the path.
+    fn fix_edge(&mut self, edge: UnEdge) {
+        self.set_edge_state(edge, EdgeState::Fixed);
+        self.fixed_degrees[edge.from.0] += 1;
+        self.fixed_degrees[edge.to.0] += 1;
+        self.path.push(true);
+    }
+
+    /// Makes the fixed edge available again and removes the decision from the path.
+    fn unfix_edge(&mut self, edge: UnEdge) {
+        self.path.pop();
+        self.set_edge_state(edge, EdgeState::Available);
+        self.fixed_degrees[edge.from.0] -= 1;
+        self.fixed_degrees[edge.to.0] -= 1;
+    }
+
+    /// Excludes the edge and records the decision in the path.
+    fn exclude_edge(&mut self, edge: UnEdge) {
+        self.set_edge_state(edge, EdgeState::Excluded);
+        self.path.push(false);
+    }
+
+    /// Makes the excluded edge available again and removes the decision from the path.
+    fn unexclude_edge(&mut self, edge: UnEdge) {
+        self.path.pop();
+        self.set_edge_state(edge, EdgeState::Available);
+    }
+
+    /// Returns whether the edge can be fixed without exceeding a degree of 2 at its nodes.
+    fn can_fix(&self, edge: UnEdge) -> bool {
+        self.fixed_degrees[edge.from.0] < 2 && self.fixed_degrees[edge.to.0] < 2
+    }
+}
+
 /// Depth-first branch-and-bound search exploring nodes recursively.
 /// Computes a lower bound at each node using Held-Karp lower bound computation and then branches
 /// on an edge from the resulting 1-tree.
 ///
-/// TODO: Summarize arguments in Held-Karp State Struct or Smth
-fn explore_node_new_thread(
-    distances: &Matrix<Distance>,
-    scaled_distances: &Matrix<ScaledDistance>,
-    edge_states: &mut Matrix<EdgeState>,
-    node_penalties: &mut [ScaledDistance],
-    fixed_degrees: &mut [u32],
-    best_tour: Arc<Mutex<UnTour>>,
-    bb_counter: &mut usize,
-    bb_limit: Option<usize>,
-    depth: usize,
-    threads_spawned: Arc<Mutex<usize>>,
-) {
+/// The excluding branch of a node is explored by a new thread as long as less than
+/// [`ParallelConfig::threads`] threads are running. The new thread starts from a copy of the
+/// [`ThreadState`] of the current one.
+fn explore_node_new_thread(search: &ParallelSearch, thread: &mut ThreadState, depth: usize) {
     // Increment the branch count
-    *bb_counter += 1;
+    thread.bb_counter += 1;
 
-    if let Some(limit) = bb_limit {
-        if *bb_counter >= limit {
+    if let Some(limit) = search.bb_limit {
+        if thread.bb_counter >= limit {
             return;
         }
     }
@@ -109,31 +277,39 @@ fn explore_node_new_thread(
         (MAX_ITERATIONS, BETA)
     };
 
+    let deterministic = search.config.deterministic;
     let one_tree = match held_karp_lower_bound_parallel(
-        distances,
-        scaled_distances,
-        edge_states,
-        node_penalties,
-        // Possibly pass Arc<Mutex<UnTour>> instead of copying the best tour cost each time
-        best_tour.clone(),
+        search.distances,
+        search.scaled_distances,
+        &thread.edge_states,
+        &mut thread.node_penalties,
+        || {
+            search
+                .fixed_upper_bound
+                .unwrap_or_else(|| search.best_tour.lock().unwrap().tour.cost)
+        },
         max_iterations,
         beta,
     ) {
         Some(LowerBoundOutput::Tour(tour)) => {
             // Found a new tour, that is, an upper bound
-            debug!("Found a new best tour with cost {}", tour.cost.0);
-            *best_tour.lock().unwrap() = tour;
+            search
+                .best_tour
+                .lock()
+                .unwrap()
+                .offer(tour, &thread.path, deterministic);
             return;
         }
         Some(LowerBoundOutput::LowerBound(lower_bound, one_tree)) => {
-            let current_upper_bound = best_tour.lock().unwrap().cost;
             // Check if the lower bound is better than the current best cost
-            if lower_bound >= current_upper_bound {
+            if search
+                .best_tour
+                .lock()
+                .unwrap()
+                .prunes(lower_bound, &thread.path, deterministic)
+            {
                 // Prune this node, as we have already found a better tour than the lower bound
-                trace!(
-                    "Pruning node with lower bound {} >= upper bound {}",
-                    lower_bound.0, current_upper_bound.0
-                );
+                trace!("Pruning node with lower bound {}", lower_bound.0);
                 return;
             } else {
                 one_tree
@@ -145,170 +321,71 @@ fn explore_node_new_thread(
         }
     };
 
-    let Some(branching_edge) =
-        edge_to_branch_on(scaled_distances, edge_states, node_penalties, &one_tree)
-    else {
+    let Some(branching_edge) = edge_to_branch_on(
+        search.scaled_distances,
+        &thread.edge_states,
+        &thread.node_penalties,
+        &one_tree,
+        BranchStrategy::default(),
+    ) else {
         // No edge to branch on, so we prune
         return;
     };
 
-    if (fixed_degrees[branching_edge.from.0] < 2) && (fixed_degrees[branching_edge.to.0] < 2) {
-        if *threads_spawned.lock().unwrap() <= 8 {
+    // In the deterministic mode, both children start from the penalties of this node, instead of
+    // the including child starting from the ones the excluding branch ended with
+    let parent_penalties = deterministic.then(|| thread.node_penalties.clone());
+
+    if thread.can_fix(branching_edge) {
+        let spawn_thread = {
+            let mut threads_spawned = search.threads_spawned.lock().unwrap();
+            let spawn_thread = *threads_spawned < search.config.threads;
+            if spawn_thread {
+                *threads_spawned += 1;
+            }
+            spawn_thread
+        };
+        if spawn_thread {
             // We can spawn a new thread which explores the branch excluding the edge
-            *threads_spawned.lock().unwrap() += 1;
             thread::scope(|s| {
                 // Explore the branch excluding the edge
-                let thread_handle = {
-                    let mut edge_states_clone = edge_states.clone();
-                    let mut node_penalties_clone = node_penalties.to_vec();
-                    let mut fixed_degrees_clone = fixed_degrees.to_vec();
-                    let best_tour_handle = best_tour.clone();
-                    let mut bb_counter_clone = bb_counter.clone();
-                    let threads_spawned_handle = threads_spawned.clone();
-
-                    let thread_handle = s.spawn(move || {
-                        edge_states_clone.set_data_symmetric(
-                            branching_edge.from,
-                            branching_edge.to,
-                            EdgeState::Excluded,
-                        );
-
-                        explore_node_new_thread(
-                            distances,
-                            scaled_distances,
-                            &mut edge_states_clone,
-                            &mut node_penalties_clone,
-                            &mut fixed_degrees_clone,
-                            best_tour_handle,
-                            &mut bb_counter_clone,
-                            bb_limit,
-                            depth + 1,
-                            threads_spawned_handle,
-                        );
-                    });
-
-                    thread_handle
-                };
-
-                // Try exploring the branch including the edge.
-                // That is, we might not be able to explore this branch, if we the edge inclusion
-                // would violate the already fixed degrees / edges.
-                // println!("Exploring inclusion at depth {}", depth);
-                edge_states.set_data_symmetric(
-                    branching_edge.from,
-                    branching_edge.to,
-                    EdgeState::Fixed,
-                );
-                fixed_degrees[branching_edge.from.0] += 1;
-                fixed_degrees[branching_edge.to.0] += 1;
-
-                explore_node_new_thread(
-                    distances,
-                    scaled_distances,
-                    edge_states,
-                    node_penalties,
-                    fixed_degrees,
-                    best_tour,
-                    bb_counter,
-                    bb_limit,
-                    depth + 1,
-                    threads_spawned.clone(),
-                );
-
-                // Backtrack
-                edge_states.set_data_symmetric(
-                    branching_edge.from,
-                    branching_edge.to,
-                    EdgeState::Available,
-                );
-                fixed_degrees[branching_edge.from.0] -= 1;
-                fixed_degrees[branching_edge.to.0] -= 1;
+                let mut excluding_thread = thread.clone();
+                s.spawn(move || {
+                    excluding_thread.exclude_edge(branching_edge);
+                    explore_node_new_thread(search, &mut excluding_thread, depth + 1);
+                });
+
+                // Explore the branch including the edge
+                thread.fix_edge(branching_edge);
+                explore_node_new_thread(search, thread, depth + 1);
+                thread.unfix_edge(branching_edge);
             });
-            
+
             // Decrement the thread count
-            *threads_spawned.lock().unwrap() -= 1;
+            *search.threads_spawned.lock().unwrap() -= 1;
         } else {
             // We cannot spawn a new thread, so we explore both branches in the current thread
-            {
-                edge_states.set_data_symmetric(
-                    branching_edge.from,
-                    branching_edge.to,
-                    EdgeState::Excluded,
-                );
-
-                explore_node_new_thread(
-                    distances,
-                    scaled_distances,
-                    edge_states,
-                    node_penalties,
-                    fixed_degrees,
-                    best_tour.clone(),
-                    bb_counter,
-                    bb_limit,
-                    depth + 1,
-                    threads_spawned.clone(),
-                );
-            }
+            thread.exclude_edge(branching_edge);
+            explore_node_new_thread(search, thread, depth + 1);
+            thread.unexclude_edge(branching_edge);
 
             // Try exploring the branch including the edge.
             // That is, we might not be able to explore this branch, if we the edge inclusion would
             // violate the already fixed degrees / edges.
-            if (fixed_degrees[branching_edge.from.0] < 2)
-                && (fixed_degrees[branching_edge.to.0] < 2)
-            {
-                edge_states.set_data_symmetric(
-                    branching_edge.from,
-                    branching_edge.to,
-                    EdgeState::Fixed,
-                );
-                fixed_degrees[branching_edge.from.0] += 1;
-                fixed_degrees[branching_edge.to.0] += 1;
-
-                explore_node_new_thread(
-                    distances,
-                    scaled_distances,
-                    edge_states,
-                    node_penalties,
-                    fixed_degrees,
-                    best_tour,
-                    bb_counter,
-                    bb_limit,
-                    depth + 1,
-                    threads_spawned.clone(),
-                );
-
-                // Backtrack
-                edge_states.set_data_symmetric(
-                    branching_edge.from,
-                    branching_edge.to,
-                    EdgeState::Available,
-                );
-                fixed_degrees[branching_edge.from.0] -= 1;
-                fixed_degrees[branching_edge.to.0] -= 1;
+            if thread.can_fix(branching_edge) {
+                if let Some(parent_penalties) = &parent_penalties {
+                    thread.node_penalties.copy_from_slice(parent_penalties);
+                }
+                thread.fix_edge(branching_edge);
+                explore_node_new_thread(search, thread, depth + 1);
+                thread.unfix_edge(branching_edge);
             }
         }
     } else {
         // We can only explore the branch excluding the edge.
-        {
-            edge_states.set_data_symmetric(
-                branching_edge.from,
-                branching_edge.to,
-                EdgeState::Excluded,
-            );
-
-            explore_node_new_thread(
-                distances,
-                scaled_distances,
-                edge_states,
-                node_penalties,
-                fixed_degrees,
-                best_tour.clone(),
-                bb_counter,
-                bb_limit,
-                depth + 1,
-                threads_spawned,
-            );
-        }
+        thread.exclude_edge(branching_edge);
+        explore_node_new_thread(search, thread, depth + 1);
+        thread.unexclude_edge(branching_edge);
     }
 }
 
@@ -325,7 +402,7 @@ fn explore_node_parallel(
     edge_states: &mut Matrix<EdgeState>,
     node_penalties: &mut [ScaledDistance],
     fixed_degrees: &mut [u32],
-    best_tour: Arc<Mutex<UnTour>>,
+    best_tour: Arc<Mutex<Incumbent>>,
     bb_counter: &mut usize,
     bb_limit: Option<usize>,
     depth: usize,
@@ -345,21 +422,19 @@ fn explore_node_parallel(
         (MAX_ITERATIONS, BETA)
     };
 
-    let current_upper_bound = best_tour.lock().unwrap().cost;
+    let current_upper_bound = best_tour.lock().unwrap().tour.cost;
     let one_tree = match held_karp_lower_bound_parallel(
         distances,
         scaled_distances,
         edge_states,
         node_penalties,
-        // Possibly pass Arc<Mutex<UnTour>> instead of copying the best tour cost each time
-        best_tour.clone(),
+        || best_tour.lock().unwrap().tour.cost,
         max_iterations,
         beta,
     ) {
         Some(LowerBoundOutput::Tour(tour)) => {
             // Found a new tour, that is, an upper bound
-            debug!("Found a new best tour with cost {}", tour.cost.0);
-            *best_tour.lock().unwrap() = tour;
+            best_tour.lock().unwrap().offer(tour, &[], false);
             return;
         }
         Some(LowerBoundOutput::LowerBound(lower_bound, one_tree)) => {
@@ -381,9 +456,13 @@ fn explore_node_parallel(
         }
     };
 
-    let Some(branching_edge) =
-        edge_to_branch_on(scaled_distances, edge_states, node_penalties, &one_tree)
-    else {
+    let Some(branching_edge) = edge_to_branch_on(
+        scaled_distances,
+        edge_states,
+        node_penalties,
+        &one_tree,
+        BranchStrategy::default(),
+    ) else {
         // No edge to branch on, so we prune
         return;
     };
@@ -448,12 +527,15 @@ enum LowerBoundOutput {
 }
 
 /// Compute Held-Karp lower bound using 1-trees and Lagrangian relaxation
+///
+/// The upper bound of the subgradient optimization is queried in every iteration, such that it
+/// can follow the best tour found so far.
 fn held_karp_lower_bound_parallel(
     distances: &Matrix<Distance>,
     scaled_distances: &Matrix<ScaledDistance>,
     edge_states: &Matrix<EdgeState>,
     node_penalties: &mut [ScaledDistance],
-    best_tour: Arc<Mutex<UnTour>>,
+    upper_bound: impl Fn() -> Distance,
     max_iterations: usize,
     beta: f64,
 ) -> Option<LowerBoundOutput> {
@@ -464,17 +546,17 @@ fn held_karp_lower_bound_parallel(
 
     let mut alpha = INITIAL_ALPHA;
 
-    let node_penalty_sum: ScaledDistance = node_penalties.iter().sum();
-
     let one_tree = loop {
         let one_tree = min_one_tree(scaled_distances, edge_states, node_penalties)?;
 
-        let scaled_upper_bound = ScaledDistance::from_distance(best_tour.lock().unwrap().cost);
+        let scaled_upper_bound = ScaledDistance::from_distance(upper_bound());
 
         // Compute the cost of the 1-tree with penalties. This is simultaneously the value of
         // the lagrangian relaxation and thus a lower bound (possibly an upper bound too, if it is a
         // tour).
         let one_tree_cost = {
+            // The penalties change in every iteration, so their sum has to be recomputed
+            let node_penalty_sum: ScaledDistance = node_penalties.iter().sum();
             let mut base_cost = 2 * node_penalty_sum;
 
             for edge in &one_tree {
@@ -499,27 +581,23 @@ fn held_karp_lower_bound_parallel(
         }
 
         // Next we check the degrees of the nodes in the 1-tree
-        // Deg[node] can be interpreted as follows:
-        //  Deg[node] < 0: Node has degree > 2 -> we need to decrease its penalty. This makes edges
+        // The deviation (degree - 2) of a node can be interpreted as follows:
+        //  deviation > 0: Node has degree > 2 -> we need to decrease its penalty. This makes edges
         //                 incident to node more expensive, that is, less likely to be selected.
-        //  Deg[node] > 0: Node has degree < 2 -> we need to increase its penalty. This makes edges
+        //  deviation < 0: Node has degree < 2 -> we need to increase its penalty. This makes edges
         //                 incident to node cheaper, that is, more likely to be selected.
-        //  Deg[node] == 0: Node has degree == 2 -> no change to penalty.
-        let mut deg = vec![2i32; distances.dimension()];
+        //  deviation == 0: Node has degree == 2 -> no change to penalty.
+        let deviations = one_tree_degrees(&one_tree, distances.dimension());
 
-        for edge in &one_tree {
-            deg[edge.from.0] -= 1;
-            deg[edge.to.0] -= 1;
-        }
-
-        let square_sum = deg.iter().map(|&d| d * d).sum::<i32>();
+        let square_sum = deviations.iter().map(|&d| d * d).sum::<i32>();
 
         if square_sum == 0 {
             // Found a tour
-            let cost: Distance = one_tree
-                .iter()
-                .map(|edge| distances.get_data(edge.from, edge.to))
-                .sum();
+            let cost = Distance::saturating_sum(
+                one_tree
+                    .iter()
+                    .map(|edge| distances.get_data(edge.from, edge.to)),
+            );
 
             return Some(LowerBoundOutput::Tour(UnTour {
                 edges: one_tree,
@@ -550,9 +628,9 @@ fn held_karp_lower_bound_parallel(
 
         // Update penalties based on degree deviations and step size
         // TODO: Handle overflows
-        for (node_penalty, &d) in node_penalties.iter_mut().zip(deg.iter()) {
-            let adjustment = ScaledDistance(step_size * d);
-            *node_penalty += adjustment;
+        for (node_penalty, &deviation) in node_penalties.iter_mut().zip(deviations.iter()) {
+            let adjustment = ScaledDistance(step_size * deviation);
+            *node_penalty -= adjustment;
         }
     };
 
@@ -1,3 +1,11 @@
+use std::{
+    sync::{
+        Barrier, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
 use tsp_core::instance::{
     distance::ScaledDistance,
     edge::UnEdge,
@@ -7,17 +15,58 @@ use tsp_core::instance::{
 
 use crate::held_karp_mod::EdgeState;
 
+/// Minimum number of nodes per thread of [`min_spanning_tree_parallel`].
+const MIN_NODES_PER_THREAD: usize = 256;
+
 /// Compute a minimum 1-tree with given node penalties and edge states.
+///
+/// The special node of the 1-tree is always node 0 (with 0-based indexing). That is, the returned
+/// edges consist of a minimum spanning tree on the nodes `1..n` plus the two cheapest edges
+/// connecting node 0 to that tree. Edge costs are reduced by the penalties of both endpoints.
+///
+/// Returns `None` if no 1-tree respecting the edge states exists, e.g. if too many edges are
+/// excluded or node 0 has more than two fixed edges.
+///
+/// The spanning tree is computed on the current thread, see [`min_one_tree_parallel`] to use
+/// multiple threads instead.
 pub fn min_one_tree(
     distances_scaled: &Matrix<ScaledDistance>,
     edge_states: &Matrix<EdgeState>,
     penalties: &[ScaledDistance],
+) -> Option<Vec<UnEdge>> {
+    min_one_tree_parallel(distances_scaled, edge_states, penalties, 1)
+}
+
+/// Compute a minimum 1-tree like [`min_one_tree`], computing the spanning tree with the given
+/// number of threads.
+///
+/// The nodes are partitioned into one block per thread. In each step of Prim's algorithm, every
+/// thread updates the cheapest connections of the nodes in its block to the node added last and
+/// reports the cheapest of them, from which the next node of the tree is chosen. As the threads
+/// have to be synchronized in every step, this only pays off for large instances. A `threads`
+/// value of 0 is treated as 1. If the instance is too small to give each thread a reasonable
+/// number of nodes, fewer threads are used.
+///
+/// The resulting tree has the same cost as the one computed serially, but may differ from it if
+/// there are multiple minimum 1-trees.
+pub fn min_one_tree_parallel(
+    distances_scaled: &Matrix<ScaledDistance>,
+    edge_states: &Matrix<EdgeState>,
+    penalties: &[ScaledDistance],
+    threads: usize,
 ) -> Option<Vec<UnEdge>> {
     let (distances_scaled_zero, distances_scaled_rest) = distances_scaled.split_first_row();
     let (edge_states_zero, edge_states_rest) = edge_states.split_first_row();
 
     // First, compute the minimum spanning tree on all nodes except the first one (node 0)
-    let tree = min_spanning_tree(distances_scaled_rest, edge_states_rest, penalties)?;
+    let threads = threads
+        .max(1)
+        .min(distances_scaled.dimension() / MIN_NODES_PER_THREAD);
+    let tree = if threads > 1 {
+        min_spanning_tree_parallel(distances_scaled_rest, edge_states_rest, penalties, threads)?
+    } else {
+        min_spanning_tree(distances_scaled_rest, edge_states_rest, penalties)?
+    };
 
     // Next, find the two cheapest edges connecting the first node (node 0) to the rest of the tree
     let node_zero = Node(0);
@@ -36,6 +85,9 @@ pub fn min_one_tree(
         match edge_states_zero[node_index] {
             EdgeState::Excluded => continue,
             EdgeState::Available => {
+                // The penalty of node 0 is the same for all of these edges, so only the penalty
+                // of the neighbor matters for choosing the cheapest ones
+                let distance = distance - penalties[node_index];
                 if distance < dist_cheapest_edge_a {
                     // Assign new value to cheapest edge a, and move previous a to b
                     // (because of the invariant)
@@ -80,9 +132,40 @@ pub fn min_one_tree(
     }
 }
 
+/// Returns the deviation of each node's degree in the 1-tree from 2, that is, `degree - 2`.
+///
+/// A 1-tree is a tour if and only if all deviations are 0. Positive values indicate nodes with
+/// too many incident edges, negative values nodes which are leaves of the 1-tree. This is the
+/// subgradient used to update the node penalties in the Lagrangian relaxation, so nodes whose
+/// deviation persists over many iterations hint at why the lower bound does not converge.
+pub fn one_tree_degrees(one_tree: &[UnEdge], dimension: usize) -> Vec<i32> {
+    let mut deviations = vec![-2; dimension];
+    for edge in one_tree {
+        deviations[edge.from.0] += 1;
+        deviations[edge.to.0] += 1;
+    }
+    deviations
+}
+
+/// Returns the largest absolute deviation of a node's degree from 2, given the deviations
+/// computed by [`one_tree_degrees`].
+///
+/// This is 0 if and only if the 1-tree is a tour.
+pub fn max_degree_violation(degree_deviations: &[i32]) -> u32 {
+    degree_deviations
+        .iter()
+        .map(|deviation| deviation.unsigned_abs())
+        .max()
+        .unwrap_or(0)
+}
+
 /// Compute a minimum spanning tree with given edge states and node penalties. Implements a
 /// variation of Prim's algorithm to abide the edge states.
 ///
+/// Works on full matrices, as scanning the contiguous rows given by `get_adjacency_list` is
+/// considerably faster than gathering the rows of a symmetric matrix (see the `prim_row_access`
+/// benchmark).
+///
 /// Returns a vector of edges representing the minimum spanning tree.
 fn min_spanning_tree(
     distances_scaled: MatrixViewZeroRemoved<ScaledDistance>,
@@ -119,7 +202,8 @@ fn min_spanning_tree(
 
         for (index, next) in remaining_nodes.iter().enumerate() {
             match edge_states_curr[next.0] {
-                EdgeState::Excluded => continue,
+                // The node might still be reachable from another node of the tree
+                EdgeState::Excluded => {}
                 EdgeState::Available => {
                     let distance = distances_scaled_curr[next.0];
                     let adjusted_distance = distance - current_penalty - penalties[next.0];
@@ -169,13 +253,274 @@ fn min_spanning_tree(
     Some(tree)
 }
 
+/// Cheapest connection of a block of nodes to the tree, as reported by a thread of
+/// [`min_spanning_tree_parallel`] in each step.
+#[derive(Debug, Clone, Copy)]
+enum BlockStep {
+    /// The cheapest node of the block which is not yet in the tree, with its cost and predecessor.
+    Cheapest(ScaledDistance, Node, Node),
+    /// All nodes of the block are either in the tree or unreachable so far.
+    Unreachable,
+    /// A node of the block would be added twice via fixed edges, i.e. there is no spanning tree.
+    Infeasible,
+}
+
+/// Compute a minimum spanning tree with given edge states and node penalties like
+/// [`min_spanning_tree`], using the given number of threads (at least 2).
+///
+/// Each thread owns the cheapest connections to the tree of a contiguous block of nodes. The
+/// threads are spawned once and synchronized with a barrier twice per step: Once the main thread
+/// has published the node added last, and once all threads have reported the cheapest node of
+/// their block.
+fn min_spanning_tree_parallel(
+    distances_scaled: MatrixViewZeroRemoved<ScaledDistance>,
+    edge_states: MatrixViewZeroRemoved<EdgeState>,
+    penalties: &[ScaledDistance],
+    threads: usize,
+) -> Option<Vec<UnEdge>> {
+    let number_of_nodes_in_tree = distances_scaled.dimension_adjusted();
+    // The nodes of the tree are 1..=number_of_nodes_in_tree, we use NO_NODE to signal the threads
+    // to stop
+    const NO_NODE: usize = 0;
+    let block_length = number_of_nodes_in_tree.div_ceil(threads);
+
+    let mut in_tree = vec![false; number_of_nodes_in_tree];
+    let mut best_cost_to_node = vec![ScaledDistance::MAX; number_of_nodes_in_tree];
+    let mut best_pred_to_node = vec![Node(NO_NODE); number_of_nodes_in_tree];
+
+    let added_last = AtomicUsize::new(NO_NODE);
+    let block_steps: Vec<Mutex<BlockStep>> = (0..threads)
+        .map(|_| Mutex::new(BlockStep::Unreachable))
+        .collect();
+    let barrier = Barrier::new(threads + 1);
+
+    thread::scope(|scope| {
+        for (block, ((in_tree, best_cost_to_node), best_pred_to_node)) in in_tree
+            .chunks_mut(block_length)
+            .zip(best_cost_to_node.chunks_mut(block_length))
+            .zip(best_pred_to_node.chunks_mut(block_length))
+            .enumerate()
+        {
+            let (distances_scaled, edge_states, penalties) =
+                (&distances_scaled, &edge_states, penalties);
+            let (added_last, block_step, barrier) = (&added_last, &block_steps[block], &barrier);
+            // Node index of the first node of the block
+            let first_node = 1 + block * block_length;
+
+            scope.spawn(move || {
+                loop {
+                    barrier.wait();
+                    let curr = Node(added_last.load(Ordering::Acquire));
+                    if curr.0 == NO_NODE {
+                        break;
+                    }
+                    if (first_node..first_node + in_tree.len()).contains(&curr.0) {
+                        in_tree[curr.0 - first_node] = true;
+                    }
+
+                    let current_penalty = penalties[curr.0];
+                    let distances_scaled_curr = distances_scaled.get_adjacency_list(curr);
+                    let edge_states_curr = edge_states.get_adjacency_list(curr);
+
+                    let mut step = BlockStep::Unreachable;
+                    for offset in 0..in_tree.len() {
+                        if in_tree[offset] {
+                            continue;
+                        }
+                        let next = first_node + offset;
+                        match edge_states_curr[next] {
+                            EdgeState::Excluded => {}
+                            EdgeState::Available => {
+                                let adjusted_distance =
+                                    distances_scaled_curr[next] - current_penalty - penalties[next];
+                                if adjusted_distance < best_cost_to_node[offset] {
+                                    best_cost_to_node[offset] = adjusted_distance;
+                                    best_pred_to_node[offset] = curr;
+                                }
+                            }
+                            EdgeState::Fixed => {
+                                if best_cost_to_node[offset] == ScaledDistance::MIN {
+                                    // See min_spanning_tree, the fixed edges would contain a cycle
+                                    step = BlockStep::Infeasible;
+                                    break;
+                                }
+                                best_cost_to_node[offset] = ScaledDistance::MIN;
+                                best_pred_to_node[offset] = curr;
+                            }
+                        }
+
+                        let cost = best_cost_to_node[offset];
+                        let is_cheaper = match step {
+                            BlockStep::Cheapest(cheapest, ..) => cost < cheapest,
+                            _ => cost < ScaledDistance::MAX,
+                        };
+                        if is_cheaper {
+                            step = BlockStep::Cheapest(cost, Node(next), best_pred_to_node[offset]);
+                        }
+                    }
+
+                    *block_step.lock().expect("MST worker thread panicked") = step;
+                    barrier.wait();
+                }
+            });
+        }
+
+        // The resulting tree edges in no particular order
+        let mut tree = Vec::with_capacity(number_of_nodes_in_tree - 1);
+        // Start from node 1
+        let mut curr = Node(1);
+        let mut feasible = true;
+
+        // Tree contains n - 1 edges
+        for _ in 0..(number_of_nodes_in_tree - 1) {
+            added_last.store(curr.0, Ordering::Release);
+            barrier.wait();
+            barrier.wait();
+
+            let mut cheapest: Option<(ScaledDistance, Node, Node)> = None;
+            for block_step in &block_steps {
+                match *block_step.lock().expect("MST worker thread panicked") {
+                    BlockStep::Cheapest(cost, node, pred) => {
+                        if cheapest.is_none_or(|(cheapest_cost, ..)| cost < cheapest_cost) {
+                            cheapest = Some((cost, node, pred));
+                        }
+                    }
+                    BlockStep::Unreachable => {}
+                    BlockStep::Infeasible => feasible = false,
+                }
+            }
+
+            match cheapest {
+                Some((_, node, pred)) if feasible => {
+                    tree.push(UnEdge::new(pred, node));
+                    curr = node;
+                }
+                _ => {
+                    // Either the fixed edges contain a cycle or the remaining nodes are
+                    // unreachable
+                    feasible = false;
+                    break;
+                }
+            }
+        }
+
+        added_last.store(NO_NODE, Ordering::Release);
+        barrier.wait();
+
+        feasible.then_some(tree)
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
+    use rand::{RngExt, SeedableRng, rngs::SmallRng};
     use tsp_core::instance::matrix::Matrix;
 
     use super::*;
 
+    #[test]
+    fn test_one_tree_degrees() {
+        // Node 1 is the center of a star, node 0 is connected to nodes 1 and 2
+        let one_tree = [
+            UnEdge::new(Node(1), Node(2)),
+            UnEdge::new(Node(1), Node(3)),
+            UnEdge::new(Node(1), Node(4)),
+            UnEdge::new(Node(0), Node(1)),
+            UnEdge::new(Node(0), Node(2)),
+        ];
+        let deviations = one_tree_degrees(&one_tree, 5);
+        assert_eq!(deviations, vec![0, 2, 0, -1, -1]);
+        assert_eq!(max_degree_violation(&deviations), 2);
+    }
+
+    #[test]
+    fn test_one_tree_degrees_of_tour() {
+        let tour: Vec<_> = (0..5)
+            .map(|node| UnEdge::new(Node(node), Node((node + 1) % 5)))
+            .collect();
+        let deviations = one_tree_degrees(&tour, 5);
+        assert!(deviations.iter().all(|&deviation| deviation == 0));
+        assert_eq!(max_degree_violation(&deviations), 0);
+    }
+
+    fn assert_same_edges(actual: &[UnEdge], expected: &[UnEdge]) {
+        assert_eq!(actual.len(), expected.len());
+        actual.iter().for_each(|edge| {
+            assert!(
+                expected.contains(edge),
+                "Edge {:?} not in expected 1-tree",
+                edge
+            );
+        });
+    }
+
+    #[test]
+    fn test_min_one_tree_special_node_is_zero() {
+        // Node 0 is far away from all other nodes, which lie on a path of cheap edges
+        let dimension = 6;
+        let distance_matrix = Matrix::new_from_distance_function(dimension, |from, to| {
+            if from.0 == 0 || to.0 == 0 {
+                ScaledDistance(100 + from.0 as i32 + to.0 as i32)
+            } else if from.0.abs_diff(to.0) == 1 {
+                ScaledDistance(1)
+            } else {
+                ScaledDistance(10)
+            }
+        });
+        let penalties = vec![ScaledDistance(0); dimension];
+        let edge_states = Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+
+        let one_tree = min_one_tree(&distance_matrix, &edge_states, &penalties).unwrap();
+        let mut expected = (1..dimension - 1)
+            .map(|i| UnEdge::new(Node(i), Node(i + 1)))
+            .collect::<Vec<_>>();
+        expected.push(UnEdge::new(Node(0), Node(1)));
+        expected.push(UnEdge::new(Node(0), Node(2)));
+        assert_same_edges(&one_tree, &expected);
+    }
+
+    #[test]
+    fn test_min_one_tree_respects_penalties_of_neighbors() {
+        let dimension = 5;
+        let distance_matrix = Matrix::new_from_dimension_with_value(dimension, ScaledDistance(10));
+        // Edges to node 4 and 3 get cheaper than all others
+        let mut penalties = vec![ScaledDistance(0); dimension];
+        penalties[3] = ScaledDistance(2);
+        penalties[4] = ScaledDistance(5);
+        let edge_states = Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+
+        let one_tree = min_one_tree(&distance_matrix, &edge_states, &penalties).unwrap();
+        assert_eq!(one_tree.len(), dimension);
+        assert!(one_tree.contains(&UnEdge::new(Node(0), Node(4))));
+        assert!(one_tree.contains(&UnEdge::new(Node(0), Node(3))));
+    }
+
+    #[test]
+    fn test_min_one_tree_fixed_edges_at_special_node() {
+        let dimension = 5;
+        let distance_matrix = Matrix::new_from_distance_function(dimension, |from, to| {
+            ScaledDistance((from.0 + to.0) as i32)
+        });
+        let penalties = vec![ScaledDistance(0); dimension];
+        let mut edge_states =
+            Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+        edge_states.set_data_symmetric(Node(0), Node(3), EdgeState::Fixed);
+        edge_states.set_data_symmetric(Node(0), Node(4), EdgeState::Fixed);
+
+        let one_tree = min_one_tree(&distance_matrix, &edge_states, &penalties).unwrap();
+        assert!(one_tree.contains(&UnEdge::new(Node(0), Node(3))));
+        assert!(one_tree.contains(&UnEdge::new(Node(0), Node(4))));
+        assert!(!one_tree.contains(&UnEdge::new(Node(0), Node(1))));
+
+        // A third fixed edge at the special node makes the 1-tree infeasible
+        edge_states.set_data_symmetric(Node(0), Node(1), EdgeState::Fixed);
+        assert_eq!(
+            min_one_tree(&distance_matrix, &edge_states, &penalties),
+            None
+        );
+    }
+
     #[test]
     fn test_min_spanning_tree_simple_tree() {
         let dimension = 11;
@@ -224,6 +569,30 @@ mod tests {
         assert_eq!(mst, None);
     }
 
+    #[test]
+    fn test_min_spanning_tree_excluded_edge_of_node_added_last() {
+        // Node 3 is only reachable from node 1, as the edge to node 2, which is added to the tree
+        // right before, is excluded
+        let distance_matrix = Matrix::new_from_distance_function(4, |from, to| {
+            match (from.0.min(to.0), from.0.max(to.0)) {
+                (1, 2) => ScaledDistance(1),
+                (1, 3) => ScaledDistance(5),
+                _ => ScaledDistance(10),
+            }
+        });
+        let penalties = vec![ScaledDistance(0); 4];
+        let mut edge_states = Matrix::new_from_dimension_with_value(4, EdgeState::Available);
+        edge_states.set_data_symmetric(Node(2), Node(3), EdgeState::Excluded);
+        let (_, distance_matrix_rest) = distance_matrix.split_first_row();
+        let (_, edge_states_rest) = edge_states.split_first_row();
+
+        let mst = min_spanning_tree(distance_matrix_rest, edge_states_rest, &penalties).unwrap();
+        assert_same_edges(
+            &mst,
+            &[UnEdge::new(Node(1), Node(2)), UnEdge::new(Node(1), Node(3))],
+        );
+    }
+
     #[test]
     fn test_min_spanning_tree_infeasible_node_isolated() {
         let dimension = 6;
@@ -286,4 +655,88 @@ mod tests {
             );
         });
     }
+
+    fn one_tree_cost(one_tree: &[UnEdge], distance_matrix: &Matrix<ScaledDistance>) -> i32 {
+        one_tree
+            .iter()
+            .map(|edge| distance_matrix.get_data(edge.from, edge.to).0)
+            .sum()
+    }
+
+    fn random_instance(dimension: usize, seed: u64) -> Matrix<ScaledDistance> {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut distance_matrix =
+            Matrix::new_from_dimension_with_value(dimension, ScaledDistance(0));
+        for from in 0..dimension {
+            for to in (from + 1)..dimension {
+                // Few distinct values to get many ties between the threads
+                let distance = ScaledDistance(rng.random_range(1..20));
+                distance_matrix.set_data_symmetric(Node(from), Node(to), distance);
+            }
+        }
+        distance_matrix
+    }
+
+    #[test]
+    fn test_min_one_tree_parallel_same_cost_as_serial() {
+        for (dimension, seed) in [(600, 1), (777, 2), (1_030, 3)] {
+            let distance_matrix = random_instance(dimension, seed);
+            let penalties = (0..dimension)
+                .map(|node| ScaledDistance((node % 5) as i32))
+                .collect::<Vec<_>>();
+            let mut edge_states =
+                Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+            edge_states.set_data_symmetric(Node(1), Node(dimension - 1), EdgeState::Fixed);
+            edge_states.set_data_symmetric(Node(2), Node(3), EdgeState::Excluded);
+
+            let serial =
+                min_one_tree_parallel(&distance_matrix, &edge_states, &penalties, 1).unwrap();
+            for threads in [2, 3, 4] {
+                let parallel =
+                    min_one_tree_parallel(&distance_matrix, &edge_states, &penalties, threads)
+                        .unwrap();
+                assert_eq!(parallel.len(), dimension);
+                assert!(parallel.contains(&UnEdge::new(Node(1), Node(dimension - 1))));
+                assert!(!parallel.contains(&UnEdge::new(Node(2), Node(3))));
+                assert_eq!(
+                    one_tree_cost(&parallel, &distance_matrix),
+                    one_tree_cost(&serial, &distance_matrix)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_one_tree_parallel_infeasible() {
+        let dimension = 600;
+        let distance_matrix = random_instance(dimension, 4);
+        let penalties = vec![ScaledDistance(0); dimension];
+        let mut edge_states =
+            Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+        // Fixed edges forming a cycle
+        edge_states.set_data_symmetric(Node(1), Node(300), EdgeState::Fixed);
+        edge_states.set_data_symmetric(Node(300), Node(599), EdgeState::Fixed);
+        edge_states.set_data_symmetric(Node(599), Node(1), EdgeState::Fixed);
+        assert_eq!(
+            min_one_tree_parallel(&distance_matrix, &edge_states, &penalties, 1),
+            None
+        );
+        assert_eq!(
+            min_one_tree_parallel(&distance_matrix, &edge_states, &penalties, 2),
+            None
+        );
+
+        // Node 5 can not be reached at all
+        let mut edge_states =
+            Matrix::new_from_dimension_with_value(dimension, EdgeState::Available);
+        for node in 0..dimension {
+            if node != 5 {
+                edge_states.set_data_symmetric(Node(node), Node(5), EdgeState::Excluded);
+            }
+        }
+        assert_eq!(
+            min_one_tree_parallel(&distance_matrix, &edge_states, &penalties, 2),
+            None
+        );
+    }
 }
@@ -1,8 +1,42 @@
 /*!
 This crate provides implementations of various algorithms to solve the Traveling Salesman Problem (TSP).
 Explanations and references for the algorithms can be found in their respective modules.
+
+Without the default `std` feature, the crate is `no_std` and only requires `alloc`. Then, only
+the heuristics and helpers which need neither threads nor time measurements are available, e.g.
+[`construction`] and [`local_search::two_opt`]. The Held-Karp solvers, the lower
+[`bounds`] and [`solve`] require `std`.
  */
 #![warn(missing_debug_implementations, missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod bounds;
+pub mod christofides;
+pub mod construction;
+pub mod dynamic_programming;
+mod error;
+pub mod feasibility;
+#[cfg(feature = "std")]
 pub mod held_karp_mod;
-pub use held_karp_mod::held_karp;
+pub mod local_search;
+pub mod matching;
+pub mod neighbor_lists;
+#[cfg(feature = "std")]
+pub mod solve;
+pub mod spanning_tree;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+pub use error::SolverError;
+#[cfg(feature = "std")]
+pub use held_karp_mod::{
+    BoundInfo, BranchStrategy, HeldKarpConfig, HeldKarpSolution, ReducedCosts, SearchOrder,
+    SubgradientParams, SubgradientTermination, find_any_hamiltonian_cycle, held_karp,
+    held_karp_bound, held_karp_path, held_karp_restricted, held_karp_resume, held_karp_with_config,
+    held_karp_with_initial_tour, identity_tour,
+};
+#[cfg(feature = "std")]
+pub use solve::{Algorithm, SolveConfig, SolveResult, solve};
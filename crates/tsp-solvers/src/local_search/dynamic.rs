@@ -0,0 +1,204 @@
+use tsp_core::instance::{
+    distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node, tour_order::TourOrder,
+};
+
+use crate::local_search::two_opt::{cycle_cost, improve_segment};
+
+/// Number of positions on each side of the changed part of the tour which [`insert_city`] and
+/// [`remove_city`] re-optimize.
+const LOCAL_RADIUS: usize = 16;
+
+/// Inserts the new city into the tour at the position where it increases the cost the least and
+/// re-optimizes the tour around it with 2-opt. Returns the cost of the resulting tour, which
+/// also replaces the tracked cost of the tour, if any.
+///
+/// The new city has to be the node `Node(tour.len())`, and the distances have to be those of the
+/// instance including it, e.g. with `tour.len() + 1` nodes. Only 2-opt moves within a
+/// fixed number of positions around the inserted city are considered, so the update takes `O(n)`
+/// for the insertion plus a constant number of moves per pass, instead of a full
+/// [`two_opt`](crate::local_search::two_opt).
+///
+/// The distances are assumed to be symmetric. Panics if the new city or the dimension of the
+/// distances do not match the tour.
+pub fn insert_city(tour: &mut TourOrder, new: Node, distances: &Matrix<Distance>) -> Distance {
+    let length = tour.len();
+    assert_eq!(
+        new,
+        Node(length),
+        "The new city of a tour with {length} nodes has to be node {length}"
+    );
+    assert_eq!(
+        distances.dimension(),
+        length + 1,
+        "The distances have to include the new city"
+    );
+
+    let mut nodes = tour.nodes().to_vec();
+    // Insert between the nodes at positions position - 1 and position (cyclically)
+    let position = (0..length)
+        .min_by_key(|&position| {
+            let before = nodes[(position + length - 1) % length];
+            let after = nodes[position];
+            distances.get_data(before, new) + distances.get_data(new, after)
+                - distances.get_data(before, after)
+        })
+        .unwrap_or(0);
+    nodes.insert(position, new);
+
+    improve_around(distances, &mut nodes, position);
+    let cost = cycle_cost(distances, &nodes);
+    tour.set_nodes(nodes, cost);
+    cost
+}
+
+/// Removes the city from the tour, connecting its neighbors directly, and re-optimizes the tour
+/// around the gap with 2-opt. Returns the cost of the resulting tour, which also replaces the
+/// tracked cost of the tour, if any.
+///
+/// The distances have to be those of the instance including the removed city. Afterwards, the
+/// tour refers to the instance without it, in which all nodes after the removed city are shifted
+/// down by one. That is, its distances are the submatrix
+/// [induced](Matrix::induced_subgraph) by the remaining nodes in increasing order. Like
+/// [`insert_city`], only moves close to the gap are considered.
+///
+/// The distances are assumed to be symmetric. Panics if the city is not part of the tour or the
+/// dimension of the distances does not match the tour.
+pub fn remove_city(tour: &mut TourOrder, removed: Node, distances: &Matrix<Distance>) -> Distance {
+    let length = tour.len();
+    assert!(
+        removed.0 < length,
+        "Node {} is not part of a tour with {length} nodes",
+        removed.0
+    );
+    assert_eq!(
+        distances.dimension(),
+        length,
+        "The distances have to include the removed city"
+    );
+
+    let mut nodes = tour.nodes().to_vec();
+    let position = nodes
+        .iter()
+        .position(|&node| node == removed)
+        .expect("The tour contains all nodes");
+    nodes.remove(position);
+    if !nodes.is_empty() {
+        // The neighbors of the removed city are now at positions position - 1 and position
+        let position = position % nodes.len();
+        improve_around(distances, &mut nodes, position);
+    }
+
+    // The cost has to be computed before renumbering, while the nodes still match the distances
+    let cost = cycle_cost(distances, &nodes);
+    for node in &mut nodes {
+        if node.0 > removed.0 {
+            node.0 -= 1;
+        }
+    }
+    tour.set_nodes(nodes, cost);
+    cost
+}
+
+/// Applies improving 2-opt moves to the nodes within [`LOCAL_RADIUS`] positions of the given
+/// position until there are none left. The nodes at the boundary of this window stay in place.
+///
+/// If the window covers the whole tour, the whole tour is improved like in
+/// [`two_opt`](crate::local_search::two_opt) instead.
+/// The nodes do not need to be a permutation of `0..n`, as long as they match the distances.
+fn improve_around(distances: &Matrix<Distance>, nodes: &mut [Node], position: usize) {
+    let length = nodes.len();
+    let window = 2 * LOCAL_RADIUS + 1;
+    if window >= length {
+        while improve_segment(distances, &FixedEdgeSet::new(), nodes, true) {}
+        return;
+    }
+
+    // Rotating the order does not change the tour, but moves the window to the front
+    let offset = (position + length - LOCAL_RADIUS) % length;
+    nodes.rotate_left(offset);
+    while improve_segment(distances, &FixedEdgeSet::new(), &mut nodes[..window], false) {}
+    nodes.rotate_right(offset);
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::test_support::line_distances;
+
+    use super::*;
+    use crate::local_search::two_opt;
+
+    #[test]
+    fn test_insert_city_points_on_line() {
+        // Inserting a city between others keeps the tour along the line optimal
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        let (smaller, _) =
+            distances.induced_subgraph(&(0..positions.len() - 1).map(Node).collect::<Vec<_>>());
+        let mut tour = TourOrder::identity(positions.len() - 1);
+        two_opt(&smaller, &mut tour, &FixedEdgeSet::new());
+
+        let cost = insert_city(&mut tour, Node(positions.len() - 1), &distances);
+        assert_eq!(tour.len(), positions.len());
+        assert_eq!(cost, tour.cost(&distances));
+        assert_eq!(cost, Distance(18));
+    }
+
+    #[test]
+    fn test_remove_city_renumbers_nodes() {
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        let mut tour = TourOrder::identity(positions.len());
+        two_opt(&distances, &mut tour, &FixedEdgeSet::new());
+
+        // Removing the city at coordinate 9 leaves the cities from 0 to 8 on the line
+        let removed = Node(6);
+        let cost = remove_city(&mut tour, removed, &distances);
+        let remaining: Vec<Node> = (0..positions.len())
+            .filter(|&node| node != removed.0)
+            .map(Node)
+            .collect();
+        let (smaller, _) = distances.induced_subgraph(&remaining);
+        assert_eq!(tour.len(), positions.len() - 1);
+        assert_eq!(cost, tour.cost(&smaller));
+        assert_eq!(cost, Distance(16));
+    }
+
+    #[test]
+    fn test_insert_and_remove_large_tour() {
+        // Positions shuffled deterministically, such that the window does not cover the tour
+        let dimension = 200;
+        let positions: Vec<i32> = (0..dimension).map(|i| (i * 7919) % dimension).collect();
+        let distances = line_distances(&positions);
+        let mut tour = TourOrder::identity(dimension as usize).with_cost(&distances);
+        let optimum = two_opt(&distances, &mut tour, &FixedEdgeSet::new());
+
+        let last = Node(dimension as usize - 1);
+        let removed_cost = remove_city(&mut tour, last, &distances);
+        assert!(removed_cost <= optimum);
+        assert_eq!(tour.cached_cost(), Some(removed_cost));
+        let inserted_cost = insert_city(&mut tour, last, &distances);
+        assert_eq!(inserted_cost, tour.cost(&distances));
+        assert_eq!(inserted_cost, optimum);
+        assert!(tour.verify(&distances));
+    }
+
+    #[test]
+    fn test_insert_and_remove_small_tours() {
+        let distances = line_distances(&[0, 4, 1]);
+        let mut tour = TourOrder::identity(0);
+        for dimension in 1..=3 {
+            let (distances, _) =
+                distances.induced_subgraph(&(0..dimension).map(Node).collect::<Vec<_>>());
+            insert_city(&mut tour, Node(dimension - 1), &distances);
+            assert_eq!(tour.len(), dimension);
+        }
+        assert_eq!(tour.cost(&distances), Distance(8));
+
+        for dimension in (1..=3).rev() {
+            let (distances, _) =
+                distances.induced_subgraph(&(0..dimension).map(Node).collect::<Vec<_>>());
+            remove_city(&mut tour, Node(0), &distances);
+            assert_eq!(tour.len(), dimension - 1);
+        }
+    }
+}
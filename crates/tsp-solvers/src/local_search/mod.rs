@@ -0,0 +1,49 @@
+/*!
+Building blocks for local search on tours given as a [`TourOrder`].
+
+## Improvement Moves
+
+Improvement moves change a tour locally and are applied as long as they decrease its cost.
+
+- [`two_opt`]: Replaces two edges of the tour by reversing the path between them.
+- [`two_opt_parallel`]: Experimental multi-threaded variant of [`two_opt`] for large instances.
+  Requires the `std` feature.
+- [`multi_start_nearest_neighbor`]: Improves the nearest neighbor tours of several start nodes
+  with [`two_opt`] in parallel and keeps the best one. Requires the `std` feature.
+- [`or_opt`]: Moves a segment of up to three consecutive nodes to another position of the tour.
+- [`variable_neighborhood_descent`]: Alternates between [`two_opt`] and [`or_opt`] until neither
+  improves the tour.
+
+## Perturbations
+
+Perturbations (also called kicks) change a tour randomly in a way that local search moves cannot
+easily undo. They are used by metaheuristics like iterated local search to escape local optima.
+
+- [`double_bridge`]: The double-bridge move, which swaps two consecutive segments of the tour.
+
+## Changing the Instance
+
+When a city is added to or removed from the instance, a good tour of the new instance can be
+obtained from the old tour by a local update instead of solving from scratch.
+
+- [`insert_city`]: Inserts a new city at the cheapest position and re-optimizes around it.
+- [`remove_city`]: Removes a city, bridges the gap and re-optimizes around it.
+
+[`TourOrder`]: tsp_core::instance::tour_order::TourOrder
+*/
+
+mod dynamic;
+#[cfg(feature = "std")]
+mod multi_start;
+mod or_opt;
+mod perturbation;
+mod two_opt;
+
+pub use dynamic::{insert_city, remove_city};
+#[cfg(feature = "std")]
+pub use multi_start::multi_start_nearest_neighbor;
+pub use or_opt::{or_opt, variable_neighborhood_descent};
+pub use perturbation::double_bridge;
+pub use two_opt::two_opt;
+#[cfg(feature = "std")]
+pub use two_opt::two_opt_parallel;
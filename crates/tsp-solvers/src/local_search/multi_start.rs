@@ -0,0 +1,164 @@
+#[cfg(not(feature = "rayon"))]
+use std::thread;
+
+use tsp_core::instance::{
+    distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node, tour_order::TourOrder,
+};
+
+use super::two_opt::two_opt;
+use crate::construction::nearest_neighbor;
+
+/// Constructs a [`nearest_neighbor`] tour from each of the start nodes, improves each with
+/// [`two_opt`] using the given number of threads and returns the cheapest resulting tour. The
+/// returned tour tracks its cost, see [`TourOrder::cached_cost`].
+///
+/// Different start nodes lead to different 2-opt local optima, so the best of several starts is
+/// usually better than a single one. If several tours are equally cheap, the one of the earliest
+/// start node in `starts` is returned, independently of the number of threads. If `starts` is
+/// empty, node 0 is used as the only start node. A `threads` value of 0 is treated as 1. Like
+/// [`two_opt`], moves removing an edge contained in `fixed` are skipped.
+///
+/// The number of 2-opt moves, and with it the running time, can differ a lot between start nodes.
+/// Without the `rayon` feature, the start nodes are split into one chunk of consecutive start
+/// nodes per thread. With the `rayon` feature, the start nodes are processed on the global rayon
+/// thread pool, whose work stealing balances the uneven costs and which ignores `threads`.
+///
+/// The distances are assumed to be symmetric.
+pub fn multi_start_nearest_neighbor(
+    distances: &Matrix<Distance>,
+    starts: &[Node],
+    threads: usize,
+    fixed: &FixedEdgeSet,
+) -> TourOrder {
+    let starts = if starts.is_empty() {
+        &[Node(0)]
+    } else {
+        starts
+    };
+    best_tour(distances, starts, threads, fixed)
+}
+
+/// Returns the cheapest improved nearest neighbor tour of the start nodes, one thread per chunk
+/// of start nodes.
+#[cfg(not(feature = "rayon"))]
+fn best_tour(
+    distances: &Matrix<Distance>,
+    starts: &[Node],
+    threads: usize,
+    fixed: &FixedEdgeSet,
+) -> TourOrder {
+    let chunk_length = starts.len().div_ceil(threads.max(1));
+    thread::scope(|scope| {
+        let handles: Vec<_> = starts
+            .chunks(chunk_length)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&start| improved_tour(distances, start, fixed))
+                        .reduce(cheaper)
+                        .expect("chunks are not empty")
+                })
+            })
+            .collect();
+        // Joining in order keeps the earliest of equally cheap tours
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("multi-start worker thread panicked"))
+            .reduce(cheaper)
+            .expect("there is at least one start node")
+    })
+    .1
+}
+
+/// Returns the cheapest improved nearest neighbor tour of the start nodes, on the rayon thread
+/// pool.
+#[cfg(feature = "rayon")]
+fn best_tour(
+    distances: &Matrix<Distance>,
+    starts: &[Node],
+    _threads: usize,
+    fixed: &FixedEdgeSet,
+) -> TourOrder {
+    use rayon::prelude::*;
+
+    // `reduce` combines neighboring results only, so `cheaper` keeps the earliest of equally cheap
+    // tours
+    starts
+        .par_iter()
+        .map(|&start| improved_tour(distances, start, fixed))
+        .reduce_with(cheaper)
+        .expect("there is at least one start node")
+        .1
+}
+
+/// Constructs the nearest neighbor tour from the start node and improves it with [`two_opt`].
+fn improved_tour(
+    distances: &Matrix<Distance>,
+    start: Node,
+    fixed: &FixedEdgeSet,
+) -> (Distance, TourOrder) {
+    let mut tour = nearest_neighbor(distances, start).with_cost(distances);
+    let cost = two_opt(distances, &mut tour, fixed);
+    (cost, tour)
+}
+
+/// Returns the cheaper of the tours, preferring the first one if both are equally cheap.
+fn cheaper(first: (Distance, TourOrder), second: (Distance, TourOrder)) -> (Distance, TourOrder) {
+    if second.0 < first.0 { second } else { first }
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::test_support::line_distances;
+
+    use super::*;
+
+    #[test]
+    fn test_multi_start_is_best_of_single_starts() {
+        let distances = Matrix::new_from_distance_function(40, |from, to| {
+            let (from, to) = (from.0 as i32, to.0 as i32);
+            Distance((from * 7 % 13 - to * 7 % 13).abs() + (from * 3 % 11 - to * 3 % 11).abs())
+        });
+        let starts: Vec<Node> = (0..40).map(Node).collect();
+        let fixed = FixedEdgeSet::new();
+        let best_single = starts
+            .iter()
+            .map(|&start| improved_tour(&distances, start, &fixed).0)
+            .min()
+            .unwrap();
+
+        for threads in [0, 1, 3, 8] {
+            let tour = multi_start_nearest_neighbor(&distances, &starts, threads, &fixed);
+            assert_eq!(tour.cached_cost(), Some(best_single));
+            assert!(tour.verify(&distances));
+        }
+    }
+
+    #[test]
+    fn test_multi_start_prefers_earliest_start_on_ties() {
+        // Every nearest neighbor tour on a line is improved to the optimal cost 18
+        let distances = line_distances(&[0, 1, 9, 2, 3, 4, 5, 6, 7, 8]);
+        let starts = [Node(4), Node(2), Node(7)];
+        let fixed = FixedEdgeSet::new();
+        let earliest = improved_tour(&distances, Node(4), &fixed).1;
+        assert_ne!(earliest, improved_tour(&distances, Node(7), &fixed).1);
+        for threads in [1, 2, 3] {
+            let tour = multi_start_nearest_neighbor(&distances, &starts, threads, &fixed);
+            assert_eq!(tour.cached_cost(), Some(Distance(18)));
+            assert_eq!(tour, earliest);
+        }
+    }
+
+    #[test]
+    fn test_multi_start_without_start_nodes() {
+        let distances = line_distances(&[3, 0, 2, 1]);
+        let tour = multi_start_nearest_neighbor(&distances, &[], 2, &FixedEdgeSet::new());
+        assert_eq!(tour.len(), 4);
+        assert_eq!(tour.cached_cost(), Some(Distance(6)));
+
+        let empty = line_distances(&[]);
+        let tour = multi_start_nearest_neighbor(&empty, &[], 2, &FixedEdgeSet::new());
+        assert!(tour.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use tsp_core::instance::{
+    distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node, tour_order::TourOrder,
+};
+
+use super::two_opt::{cycle_cost, two_opt};
+
+/// Maximum number of consecutive nodes moved by a single [`or_opt`] move.
+const MAX_SEGMENT_LENGTH: usize = 3;
+
+/// Improves the tour with Or-opt moves until no improving move is left and returns the cost of the
+/// resulting tour.
+///
+/// An Or-opt move removes a segment of one to three consecutive nodes from the tour and inserts it
+/// between two other consecutive nodes, in its original or in reversed orientation. Improving
+/// moves are applied as soon as they are found (first improvement). Each pass over all segments
+/// and insertion points takes `O(n^2)`.
+///
+/// Moves which would remove an edge contained in `fixed` are skipped, so fixed edges of the
+/// initial tour are kept. Pass an empty [`FixedEdgeSet`] if there are no fixed edges.
+///
+/// The distances are assumed to be symmetric.
+pub fn or_opt(
+    distances: &Matrix<Distance>,
+    tour: &mut TourOrder,
+    fixed: &FixedEdgeSet,
+) -> Distance {
+    let mut nodes = tour.nodes().to_vec();
+    while improve_or_opt(distances, fixed, &mut nodes) {}
+    let cost = cycle_cost(distances, &nodes);
+    tour.set_nodes(nodes, cost);
+    cost
+}
+
+/// Improves the tour with variable neighborhood descent and returns the cost of the resulting tour.
+///
+/// The tour is improved by [`two_opt`] until no improving 2-opt move is left, then by [`or_opt`].
+/// If Or-opt improves the tour, the descent starts over with 2-opt, otherwise the tour is a local
+/// optimum with respect to both neighborhoods. Like both moves, moves removing an edge contained
+/// in `fixed` are skipped.
+///
+/// The distances are assumed to be symmetric.
+pub fn variable_neighborhood_descent(
+    distances: &Matrix<Distance>,
+    tour: &mut TourOrder,
+    fixed: &FixedEdgeSet,
+) -> Distance {
+    loop {
+        let two_opt_cost = two_opt(distances, tour, fixed);
+        let or_opt_cost = or_opt(distances, tour, fixed);
+        if or_opt_cost >= two_opt_cost {
+            return or_opt_cost;
+        }
+    }
+}
+
+/// Performs one pass of first-improvement Or-opt over all segments of the tour and returns whether
+/// an improving move has been applied.
+fn improve_or_opt(
+    distances: &Matrix<Distance>,
+    fixed: &FixedEdgeSet,
+    nodes: &mut Vec<Node>,
+) -> bool {
+    let length = nodes.len();
+    let mut improved = false;
+
+    for segment_length in 1..=MAX_SEGMENT_LENGTH {
+        // The remaining nodes need at least three edges, one of which is newly created, such that
+        // the segment can be inserted somewhere else
+        if length < segment_length + 3 {
+            break;
+        }
+        for start in 0..length {
+            let end = (start + segment_length - 1) % length;
+            let before = nodes[(start + length - 1) % length];
+            let after = nodes[(end + 1) % length];
+            let (first, last) = (nodes[start], nodes[end]);
+            if fixed.contains_nodes(before, first) || fixed.contains_nodes(last, after) {
+                continue;
+            }
+            let removal_gain = distances.get_data(before, first) + distances.get_data(last, after)
+                - distances.get_data(before, after);
+
+            // The edges of the tour which remain after removing the segment, except for the new
+            // edge from `before` to `after`
+            for offset in segment_length..(length - 1) {
+                let position = (start + offset) % length;
+                let (from, to) = (nodes[position], nodes[(position + 1) % length]);
+                if fixed.contains_nodes(from, to) {
+                    continue;
+                }
+                let removed = distances.get_data(from, to);
+                let forward =
+                    distances.get_data(from, first) + distances.get_data(last, to) - removed;
+                let reversed =
+                    distances.get_data(from, last) + distances.get_data(first, to) - removed;
+                let insertion_cost = forward.min(reversed);
+                if insertion_cost < removal_gain {
+                    move_segment(nodes, start, segment_length, offset, reversed < forward);
+                    improved = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    improved
+}
+
+/// Moves the segment of the given length starting at position `start` behind the node at `offset`
+/// positions after `start`, reversing it if requested.
+fn move_segment(
+    nodes: &mut Vec<Node>,
+    start: usize,
+    segment_length: usize,
+    offset: usize,
+    reverse: bool,
+) {
+    nodes.rotate_left(start);
+    let mut segment: Vec<Node> = nodes.drain(..segment_length).collect();
+    if reverse {
+        segment.reverse();
+    }
+    // The node at `offset` is now at position `offset - segment_length` of the remaining nodes
+    let insert_position = offset - segment_length + 1;
+    nodes.splice(insert_position..insert_position, segment);
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::test_support::line_distances;
+
+    use super::*;
+
+    #[test]
+    fn test_or_opt_moves_misplaced_node() {
+        // The node at position 9 is visited between the nodes at 1 and 2
+        let distances = line_distances(&[0, 1, 9, 2, 3, 4, 5, 6, 7, 8]);
+        let mut tour = TourOrder::identity(10).with_cost(&distances);
+        let cost = or_opt(&distances, &mut tour, &FixedEdgeSet::new());
+        assert_eq!(cost, Distance(18));
+        assert_eq!(tour.cached_cost(), Some(cost));
+        assert!(tour.verify(&distances));
+    }
+
+    #[test]
+    fn test_or_opt_keeps_fixed_edges() {
+        let distances = line_distances(&[0, 1, 9, 2, 3, 4, 5, 6, 7, 8]);
+        let fixed = FixedEdgeSet::from_edges(&[(1, 2).into(), (2, 3).into()]);
+        let mut tour = TourOrder::identity(10);
+        let cost = or_opt(&distances, &mut tour, &fixed);
+        assert_eq!(cost, tour.cost(&distances));
+        let tour = tour.to_un_tour(&distances);
+        assert!(tour.contains_edge(&(1, 2).into()));
+        assert!(tour.contains_edge(&(2, 3).into()));
+    }
+
+    #[test]
+    fn test_or_opt_small_tours() {
+        for dimension in 0..5 {
+            let positions: Vec<i32> = (0..dimension).rev().collect();
+            let distances = line_distances(&positions);
+            let mut tour = TourOrder::identity(dimension as usize);
+            let cost = or_opt(&distances, &mut tour, &FixedEdgeSet::new());
+            assert_eq!(cost, tour.cost(&distances));
+            assert_eq!(tour.len(), dimension as usize);
+        }
+    }
+
+    #[test]
+    fn test_variable_neighborhood_descent_reaches_both_local_optima() {
+        let positions = [7, 3, 9, 0, 4, 8, 1, 6, 2, 5, 14, 11, 13, 10, 12];
+        let distances = line_distances(&positions);
+        let mut tour = TourOrder::identity(positions.len());
+        let fixed = FixedEdgeSet::new();
+        let cost = variable_neighborhood_descent(&distances, &mut tour, &fixed);
+
+        assert_eq!(two_opt(&distances, &mut tour.clone(), &fixed), cost);
+        assert_eq!(or_opt(&distances, &mut tour.clone(), &fixed), cost);
+        // Any tour visiting the nodes on a line in order and returning is optimal
+        assert_eq!(cost, Distance(28));
+    }
+}
@@ -0,0 +1,84 @@
+use rand::{Rng, seq::index};
+use tsp_core::instance::{distance::Distance, matrix::Matrix, tour_order::TourOrder};
+
+/// Applies a random double-bridge move to the tour and returns the change in cost, see
+/// [`TourOrder::swap_segments`].
+///
+/// The tour is cut at three random positions into four non-empty segments `A B C D`, which are
+/// then reconnected as `A C B D`. That is, the edges between consecutive segments are replaced,
+/// which always yields a single cycle again. As the move is non-sequential, it cannot easily be
+/// undone by 2-opt or 3-opt moves, which makes it a popular perturbation for iterated local
+/// search. If the cost of the tour is tracked, it is updated with respect to the given distances.
+///
+/// Tours with less than four nodes are left unchanged, as they cannot be split into four non-empty
+/// segments.
+pub fn double_bridge(
+    tour: &mut TourOrder,
+    distances: &Matrix<Distance>,
+    rng: &mut impl Rng,
+) -> Distance {
+    let dimension = tour.len();
+    if dimension < 4 {
+        return Distance(0);
+    }
+
+    // Cut positions in 1..dimension, such that all segments are non-empty
+    let mut cuts = index::sample(rng, dimension - 1, 3).into_vec();
+    cuts.sort_unstable();
+    let [first, second, third] = [cuts[0] + 1, cuts[1] + 1, cuts[2] + 1];
+    tour.swap_segments(first, second, third, distances)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand::{SeedableRng, rngs::SmallRng};
+    use tsp_core::{
+        instance::{edge::UnEdge, node::Node},
+        test_support::line_distances,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_double_bridge_keeps_valid_tour() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        for dimension in 0..20 {
+            let positions: Vec<i32> = (0..dimension as i32).collect();
+            let distances = line_distances(&positions);
+            let mut tour = TourOrder::identity(dimension).with_cost(&distances);
+            for _ in 0..50 {
+                let cost_before = tour.cached_cost().unwrap();
+                let delta = double_bridge(&mut tour, &distances, &mut rng);
+                let mut nodes = tour.nodes().to_vec();
+                nodes.sort_unstable();
+                assert_eq!(nodes, TourOrder::identity(dimension).nodes());
+                assert_eq!(tour.cached_cost(), Some(cost_before + delta));
+                assert!(tour.verify(&distances));
+            }
+        }
+    }
+
+    #[test]
+    fn test_double_bridge_replaces_edges_between_segments() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let dimension = 12;
+        let positions: Vec<i32> = (0..dimension as i32).collect();
+        for _ in 0..50 {
+            let original = TourOrder::identity(dimension);
+            let mut tour = original.clone();
+            double_bridge(&mut tour, &line_distances(&positions), &mut rng);
+
+            let original_edges: Vec<UnEdge> = original.edges().collect();
+            let removed = tour
+                .edges()
+                .filter(|edge| !original_edges.contains(edge))
+                .count();
+            // Three edges are replaced, but if B and C consist of single nodes, the edge between
+            // them is kept
+            assert!((2..=3).contains(&removed), "Replaced {removed} edges");
+            assert_eq!(tour.nodes()[0], Node(0));
+        }
+    }
+}
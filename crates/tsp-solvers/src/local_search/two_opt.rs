@@ -0,0 +1,300 @@
+#[cfg(all(feature = "std", not(feature = "rayon")))]
+use std::thread;
+
+use tsp_core::instance::{
+    distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node, tour_order::TourOrder,
+};
+
+/// Minimum number of nodes per region of [`two_opt_parallel`]. Smaller regions contain too few
+/// moves to make up for the overhead of spawning threads.
+#[cfg(feature = "std")]
+const MIN_REGION_LENGTH: usize = 64;
+
+/// Number of regions per thread of [`two_opt_parallel`]. With rayon, several regions per thread
+/// leave work stealing something to balance, without rayon each region gets its own thread.
+#[cfg(feature = "rayon")]
+const REGIONS_PER_THREAD: usize = 4;
+#[cfg(all(feature = "std", not(feature = "rayon")))]
+const REGIONS_PER_THREAD: usize = 1;
+
+/// Improves the tour with 2-opt moves until no improving move is left and returns the cost of the
+/// resulting tour.
+///
+/// A 2-opt move removes two edges `(a, b)` and `(c, d)` of the tour and reconnects it via
+/// `(a, c)` and `(b, d)`, which corresponds to reversing the path from `b` to `c`. Improving
+/// moves are applied as soon as they are found (first improvement). Each pass over all pairs of
+/// edges takes `O(n^2)`.
+///
+/// Moves which would remove an edge contained in `fixed` are skipped, so fixed edges of the
+/// initial tour are kept. Pass an empty [`FixedEdgeSet`] if there are no fixed edges.
+///
+/// The distances are assumed to be symmetric.
+pub fn two_opt(
+    distances: &Matrix<Distance>,
+    tour: &mut TourOrder,
+    fixed: &FixedEdgeSet,
+) -> Distance {
+    let mut nodes = tour.nodes().to_vec();
+    while improve_segment(distances, fixed, &mut nodes, true) {}
+    let cost = cycle_cost(distances, &nodes);
+    tour.set_nodes(nodes, cost);
+    cost
+}
+
+/// Improves the tour with 2-opt moves using the given number of threads and returns the cost of
+/// the resulting tour.
+///
+/// This is experimental. The tour is partitioned into one region of consecutive nodes per thread
+/// and each thread applies improving 2-opt moves whose removed edges both lie within its region.
+/// Reversing a path inside a region does not touch the other regions, so the moves of different
+/// threads never conflict and can be applied in parallel. After each round, the region boundaries
+/// are shifted by half a region, such that moves across the previous boundaries can be found in
+/// the next round. Once a round with either offset finds no improvement, a final serial
+/// [`two_opt`] removes the remaining improving moves, which span multiple regions.
+///
+/// The result is a 2-opt local optimum, but not necessarily the same one [`two_opt`] finds. A
+/// `threads` value of 0 is treated as 1. If the tour is too small to give each thread a region
+/// of reasonable size, fewer threads are used. Like [`two_opt`], moves removing an edge contained
+/// in `fixed` are skipped.
+///
+/// With the `rayon` feature, the tour is split into several regions per thread, which are
+/// processed on the global rayon thread pool instead of one thread each.
+///
+/// The distances are assumed to be symmetric.
+#[cfg(feature = "std")]
+pub fn two_opt_parallel(
+    distances: &Matrix<Distance>,
+    tour: &mut TourOrder,
+    threads: usize,
+    fixed: &FixedEdgeSet,
+) -> Distance {
+    let mut nodes = tour.nodes().to_vec();
+    let threads = threads.max(1).min(nodes.len() / MIN_REGION_LENGTH);
+
+    if threads > 1 {
+        let region_length = nodes
+            .len()
+            .div_ceil(threads * REGIONS_PER_THREAD)
+            .max(MIN_REGION_LENGTH);
+        let offsets = [0, region_length / 2];
+        // Number of consecutive rounds without any improvement
+        let mut rounds_without_improvement = 0;
+        let mut round = 0;
+        while rounds_without_improvement < offsets.len() {
+            // Rotating the order does not change the tour, but moves the region boundaries
+            let offset = offsets[round % offsets.len()];
+            nodes.rotate_left(offset);
+
+            let improved = improve_regions(distances, fixed, &mut nodes, region_length);
+
+            nodes.rotate_right(offset);
+            if improved {
+                rounds_without_improvement = 0;
+            } else {
+                rounds_without_improvement += 1;
+            }
+            round += 1;
+        }
+    }
+
+    let cost = cycle_cost(distances, &nodes);
+    tour.set_nodes(nodes, cost);
+    two_opt(distances, tour, fixed)
+}
+
+/// Applies improving 2-opt moves within each region of the given length until none is left, one
+/// thread per region. Returns whether any move has been applied.
+#[cfg(all(feature = "std", not(feature = "rayon")))]
+fn improve_regions(
+    distances: &Matrix<Distance>,
+    fixed: &FixedEdgeSet,
+    nodes: &mut [Node],
+    region_length: usize,
+) -> bool {
+    thread::scope(|scope| {
+        let handles: Vec<_> = nodes
+            .chunks_mut(region_length)
+            .map(|region| scope.spawn(move || improve_region(distances, fixed, region)))
+            .collect();
+        let mut improved = false;
+        for handle in handles {
+            improved |= handle.join().expect("2-opt worker thread panicked");
+        }
+        improved
+    })
+}
+
+/// Applies improving 2-opt moves within each region of the given length until none is left, on
+/// the rayon thread pool. Returns whether any move has been applied.
+///
+/// Regions with many improving moves take longer, which work stealing balances across the
+/// several regions per thread.
+#[cfg(feature = "rayon")]
+fn improve_regions(
+    distances: &Matrix<Distance>,
+    fixed: &FixedEdgeSet,
+    nodes: &mut [Node],
+    region_length: usize,
+) -> bool {
+    use rayon::prelude::*;
+
+    // Not `any`, which would stop improving the other regions after the first improvement
+    nodes
+        .par_chunks_mut(region_length)
+        .map(|region| improve_region(distances, fixed, region))
+        .reduce(|| false, |improved, other| improved | other)
+}
+
+/// Applies improving 2-opt moves within the region until none is left and returns whether any
+/// move has been applied.
+#[cfg(feature = "std")]
+fn improve_region(distances: &Matrix<Distance>, fixed: &FixedEdgeSet, region: &mut [Node]) -> bool {
+    let mut improved = false;
+    while improve_segment(distances, fixed, region, false) {
+        improved = true;
+    }
+    improved
+}
+
+/// Performs one pass of first-improvement 2-opt over the given segment of nodes and returns
+/// whether an improving move has been applied.
+///
+/// If `is_cycle` is set, the segment is the whole tour and the edge from the last to the first
+/// node is considered as well. Otherwise, only edges between consecutive nodes of the segment are
+/// considered, such that the segment is only rearranged internally and its first and last node
+/// stay in place. Moves removing an edge contained in `fixed` are skipped.
+pub(super) fn improve_segment(
+    distances: &Matrix<Distance>,
+    fixed: &FixedEdgeSet,
+    segment: &mut [Node],
+    is_cycle: bool,
+) -> bool {
+    let length = segment.len();
+    if length < 4 {
+        return false;
+    }
+    let number_of_edges = if is_cycle { length } else { length - 1 };
+
+    let mut improved = false;
+    for i in 0..number_of_edges - 2 {
+        for j in i + 2..number_of_edges {
+            // In a cycle, the first and the last edge are adjacent
+            if is_cycle && i == 0 && j == length - 1 {
+                continue;
+            }
+            let (a, b) = (segment[i], segment[i + 1]);
+            let (c, d) = (segment[j], segment[(j + 1) % length]);
+            if fixed.contains_nodes(a, b) || fixed.contains_nodes(c, d) {
+                continue;
+            }
+
+            let removed = distances.get_data(a, b) + distances.get_data(c, d);
+            let added = distances.get_data(a, c) + distances.get_data(b, d);
+            if added < removed {
+                segment[i + 1..=j].reverse();
+                improved = true;
+            }
+        }
+    }
+
+    improved
+}
+
+/// Computes the cost of the tour visiting the nodes in the given order, like [`TourOrder::cost`].
+///
+/// The nodes do not need to be a permutation of `0..n`, as long as they match the distances.
+pub(super) fn cycle_cost(distances: &Matrix<Distance>, nodes: &[Node]) -> Distance {
+    let number_of_edges = if nodes.len() < 2 { 0 } else { nodes.len() };
+    Distance::saturating_sum(
+        (0..number_of_edges)
+            .map(|index| distances.get_data(nodes[index], nodes[(index + 1) % nodes.len()])),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::test_support::line_distances;
+
+    use super::*;
+
+    #[test]
+    fn test_two_opt_points_on_line() {
+        // The optimal tour goes there and back again and has length 2 * (max - min)
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        let mut tour = TourOrder::identity(positions.len()).with_cost(&distances);
+        assert_eq!(
+            two_opt(&distances, &mut tour, &FixedEdgeSet::new()),
+            Distance(18)
+        );
+        assert_eq!(tour.cost(&distances), Distance(18));
+        assert_eq!(tour.cached_cost(), Some(Distance(18)));
+    }
+
+    #[test]
+    fn test_two_opt_small_tours_unchanged() {
+        for dimension in 0..4 {
+            let positions: Vec<i32> = (0..dimension as i32).rev().collect();
+            let distances = line_distances(&positions);
+            let mut tour = TourOrder::identity(dimension);
+            two_opt(&distances, &mut tour, &FixedEdgeSet::new());
+            assert_eq!(tour, TourOrder::identity(dimension));
+        }
+    }
+
+    #[test]
+    fn test_two_opt_parallel_points_on_line() {
+        // Shuffle the positions deterministically, such that there is a lot to improve
+        let dimension = 500;
+        let positions: Vec<i32> = (0..dimension).map(|i| (i * 7919) % dimension).collect();
+        let distances = line_distances(&positions);
+
+        for threads in [0, 1, 2, 4, 16] {
+            let mut tour = TourOrder::identity(dimension as usize);
+            let cost = two_opt_parallel(&distances, &mut tour, threads, &FixedEdgeSet::new());
+            assert_eq!(cost, tour.cost(&distances));
+            assert_eq!(cost, Distance(2 * (dimension - 1)));
+        }
+    }
+
+    #[test]
+    fn test_two_opt_keeps_fixed_edges() {
+        let positions = [3, 0, 5, 1, 4, 2, 9, 7, 8, 6];
+        let distances = line_distances(&positions);
+        let initial = TourOrder::identity(positions.len());
+        let fixed: FixedEdgeSet = initial.edges().step_by(3).collect();
+
+        let mut tour = initial.clone();
+        let cost = two_opt(&distances, &mut tour, &fixed);
+        assert_eq!(cost, tour.cost(&distances));
+        assert!(cost <= initial.cost(&distances));
+        let edges: Vec<_> = tour.edges().collect();
+        assert!(initial.edges().step_by(3).all(|edge| edges.contains(&edge)));
+
+        // With all edges fixed, no move is possible
+        let all_fixed: FixedEdgeSet = initial.edges().collect();
+        let mut tour = initial.clone();
+        two_opt(&distances, &mut tour, &all_fixed);
+        assert_eq!(tour, initial);
+    }
+
+    #[test]
+    fn test_two_opt_parallel_keeps_fixed_edges() {
+        let dimension = 500;
+        let positions: Vec<i32> = (0..dimension).map(|i| (i * 7919) % dimension).collect();
+        let distances = line_distances(&positions);
+        let initial = TourOrder::identity(dimension as usize);
+        let fixed: FixedEdgeSet = initial.edges().step_by(10).collect();
+
+        let mut tour = initial.clone();
+        let cost = two_opt_parallel(&distances, &mut tour, 4, &fixed);
+        assert_eq!(cost, tour.cost(&distances));
+        let edges: Vec<_> = tour.edges().collect();
+        assert!(
+            initial
+                .edges()
+                .step_by(10)
+                .all(|edge| edges.contains(&edge))
+        );
+    }
+}
@@ -0,0 +1,110 @@
+/*!
+Perfect matchings, i.e. sets of edges such that each of the given nodes is incident to exactly one
+of them.
+
+Minimum weight perfect matchings are needed e.g. by the [Christofides algorithm][crate::christofides]
+and for matching-based lower bounds. Computing them exactly requires the blossom algorithm, which
+is not implemented yet. Instead, [`greedy_perfect_matching`] computes a greedy approximation.
+*/
+
+use alloc::{vec, vec::Vec};
+
+use tsp_core::instance::{
+    distance::{Distance, DistanceSource},
+    edge::UnEdge,
+    node::Node,
+};
+
+/// Compute a perfect matching of small weight on the given nodes.
+///
+/// This is a greedy approximation which repeatedly matches the two closest unmatched
+/// nodes in `O(k^2 log k)` for `k` nodes. The result is not necessarily of minimum weight: Even if
+/// the distances satisfy the triangle inequality, greedy matchings can be up to a factor of
+/// `O(k^0.59)` heavier than minimum weight ones, although they are usually much closer in
+/// practice.
+///
+/// The distances are assumed to be symmetric. Panics if the number of nodes is odd.
+// TODO: Compute a minimum weight perfect matching with the blossom algorithm
+pub fn greedy_perfect_matching(nodes: &[Node], distances: &impl DistanceSource) -> Vec<UnEdge> {
+    assert!(
+        nodes.len().is_multiple_of(2),
+        "A perfect matching requires an even number of nodes, got {}",
+        nodes.len()
+    );
+
+    let mut pairs: Vec<(Distance, Node, Node)> =
+        Vec::with_capacity(nodes.len() * nodes.len().saturating_sub(1) / 2);
+    for (index, &from) in nodes.iter().enumerate() {
+        for &to in &nodes[index + 1..] {
+            pairs.push((distances.distance(from, to), from, to));
+        }
+    }
+    pairs.sort_unstable();
+
+    let mut matched = vec![false; distances.dimension()];
+    let mut matching = Vec::with_capacity(nodes.len() / 2);
+    for (_, from, to) in pairs {
+        if !matched[from.0] && !matched[to.0] {
+            matched[from.0] = true;
+            matched[to.0] = true;
+            matching.push(UnEdge::new(from, to));
+        }
+    }
+
+    matching
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::instance::matrix::{Matrix, MatrixSym};
+
+    use super::*;
+
+    #[test]
+    fn test_matching_points_on_line() {
+        let positions: [i32; 6] = [0, 10, 1, 11, 5, 6];
+        let distance_function =
+            |from: Node, to: Node| Distance((positions[from.0] - positions[to.0]).abs());
+        let full = Matrix::new_from_distance_function(positions.len(), distance_function);
+        let symmetric = MatrixSym::new_from_distance_function(positions.len(), distance_function);
+        let nodes: Vec<Node> = (0..positions.len()).map(Node).collect();
+
+        for matching in [
+            greedy_perfect_matching(&nodes, &full),
+            greedy_perfect_matching(&nodes, &symmetric),
+        ] {
+            let mut matching = matching;
+            matching.sort();
+            assert_eq!(
+                matching,
+                vec![
+                    UnEdge::new(Node(0), Node(2)),
+                    UnEdge::new(Node(1), Node(3)),
+                    UnEdge::new(Node(4), Node(5)),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_matching_subset_of_nodes() {
+        let distances =
+            Matrix::new_from_distance_function(
+                6,
+                |from, to| Distance(from.0.abs_diff(to.0) as i32),
+            );
+        let matching = greedy_perfect_matching(&[Node(5), Node(1), Node(4), Node(0)], &distances);
+        assert_eq!(matching.len(), 2);
+        assert!(matching.contains(&UnEdge::new(Node(4), Node(5))));
+        assert!(matching.contains(&UnEdge::new(Node(0), Node(1))));
+
+        assert!(greedy_perfect_matching(&[], &distances).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_matching_odd_number_of_nodes() {
+        let distances = Matrix::new_from_dimension_with_value(3, Distance(1));
+        greedy_perfect_matching(&[Node(0), Node(1), Node(2)], &distances);
+    }
+}
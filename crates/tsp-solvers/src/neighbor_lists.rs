@@ -0,0 +1,89 @@
+/*!
+Lists of the nearest neighbors of each node, which let heuristics consider the few promising
+nodes first instead of scanning all nodes, e.g. in
+[`nearest_neighbor_knn`](crate::construction::nearest_neighbor_knn).
+*/
+
+use alloc::vec::Vec;
+
+use tsp_core::instance::{distance::DistanceSource, node::Node};
+
+/// The `k` nearest neighbors of each node, sorted by increasing distance.
+///
+/// Ties are broken by the index of the neighbor, such that the first neighbor in a list is the
+/// same node a scan of all nodes for the closest one (preferring smaller indices) would find.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeighborLists {
+    k: usize,
+    /// The lists of all nodes, stored contiguously with `k` neighbors per node.
+    neighbors: Vec<Node>,
+}
+
+impl NeighborLists {
+    /// Computes the `k` nearest neighbors of each node in `O(n^2 + n * k log k)`.
+    ///
+    /// The neighbors are the nodes closest by the distance from the node to them. A node is not
+    /// its own neighbor, so `k` is capped at `n - 1`.
+    pub fn new(distances: &impl DistanceSource, k: usize) -> Self {
+        let dimension = distances.dimension();
+        let k = k.min(dimension.saturating_sub(1));
+        let mut neighbors = Vec::with_capacity(dimension * k);
+        let mut candidates = Vec::with_capacity(dimension.saturating_sub(1));
+
+        for from in (0..dimension).map(Node) {
+            candidates.clear();
+            candidates.extend(
+                (0..dimension)
+                    .map(Node)
+                    .filter(|&to| to != from)
+                    .map(|to| (distances.distance(from, to), to)),
+            );
+            if k < candidates.len() {
+                candidates.select_nth_unstable(k);
+                candidates.truncate(k);
+            }
+            candidates.sort_unstable();
+            neighbors.extend(candidates.iter().map(|&(_, to)| to));
+        }
+
+        NeighborLists { k, neighbors }
+    }
+
+    /// Returns the number of neighbors of each node.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the neighbors of the node, sorted by increasing distance.
+    pub fn neighbors(&self, node: Node) -> &[Node] {
+        &self.neighbors[node.0 * self.k..(node.0 + 1) * self.k]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::instance::{distance::Distance, matrix::Matrix};
+
+    use super::*;
+
+    #[test]
+    fn test_neighbor_lists_points_on_line() {
+        let positions: [i32; 5] = [0, 3, 1, 2, 7];
+        let distances = Matrix::new_from_distance_function(positions.len(), |from, to| {
+            Distance((positions[from.0] - positions[to.0]).abs())
+        });
+
+        let neighbor_lists = NeighborLists::new(&distances, 2);
+        assert_eq!(neighbor_lists.k(), 2);
+        assert_eq!(neighbor_lists.neighbors(Node(0)), &[Node(2), Node(3)]);
+        // Nodes 2 and 1 are both at distance 1 from node 3, the smaller index comes first
+        assert_eq!(neighbor_lists.neighbors(Node(3)), &[Node(1), Node(2)]);
+        assert_eq!(neighbor_lists.neighbors(Node(4)), &[Node(1), Node(3)]);
+
+        assert_eq!(NeighborLists::new(&distances, 10).k(), 4);
+        assert_eq!(
+            NeighborLists::new(&Matrix::<Distance>::new(vec![], 0), 3).k(),
+            0
+        );
+    }
+}
@@ -0,0 +1,173 @@
+/*!
+A single entry point which chooses a solver based on the size of the instance.
+
+- Instances with at most [`MAX_DYNAMIC_PROGRAMMING_DIMENSION`] nodes are solved exactly by
+  [`dynamic_programming`].
+- Instances with at most [`MAX_BRANCH_AND_BOUND_DIMENSION`] nodes are solved exactly by the
+  [Held-Karp branch-and-bound search](crate::held_karp_mod), warm started with the heuristic tour.
+- Larger instances are solved heuristically by a [greedy edge](greedy_edge) tour improved by
+  [variable neighborhood descent](variable_neighborhood_descent), which alternates between 2-opt
+  and Or-opt moves. If there is a time limit, the remaining time is spent on iterated local
+  search, that is, on perturbing the best tour with a [`double_bridge`] and improving it again.
+
+See [`SolveConfig`] to override the choice or to set a time limit.
+*/
+
+use std::time::{Duration, Instant};
+
+use log::debug;
+use rand::{SeedableRng, rngs::SmallRng};
+use tsp_core::instance::{
+    TSPSymInstance, UnTour, distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix,
+};
+
+use crate::{
+    HeldKarpConfig, SolverError,
+    construction::greedy_edge,
+    dynamic_programming::{MAX_DIMENSION, dynamic_programming},
+    feasibility::check_finite_graph,
+    held_karp_with_initial_tour,
+    local_search::{double_bridge, variable_neighborhood_descent},
+    neighbor_lists::NeighborLists,
+};
+
+/// Largest dimension for which [`solve`] uses [`Algorithm::DynamicProgramming`] by default.
+pub const MAX_DYNAMIC_PROGRAMMING_DIMENSION: usize = 16;
+
+/// Largest dimension for which [`solve`] uses [`Algorithm::BranchAndBound`] by default.
+pub const MAX_BRANCH_AND_BOUND_DIMENSION: usize = 60;
+
+/// Number of nearest neighbors of each node whose edges are candidates of the greedy edge
+/// construction of [`Algorithm::Heuristic`].
+const GREEDY_NEIGHBORS: usize = 10;
+
+/// Algorithm used by [`solve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// The exact [`dynamic_programming`] algorithm, which takes exponential memory. Only supports
+    /// instances with up to [`MAX_DIMENSION`](crate::dynamic_programming::MAX_DIMENSION) nodes
+    /// and ignores fixed edges.
+    DynamicProgramming,
+    /// The exact [Held-Karp branch-and-bound search](crate::held_karp_mod), which respects the
+    /// fixed edges of the instance.
+    BranchAndBound,
+    /// Greedy edge construction followed by (iterated) variable neighborhood descent with 2-opt
+    /// and Or-opt moves. Fixed edges are not guaranteed to be part of the tour.
+    Heuristic,
+}
+
+/// Configuration of [`solve`].
+#[derive(Debug, Clone, Default)]
+pub struct SolveConfig {
+    /// Algorithm to use.
+    ///
+    /// Defaults to `None`, that is, the algorithm is chosen by the size of the instance, see the
+    /// [module-level documentation][crate::solve].
+    pub algorithm: Option<Algorithm>,
+    /// Time budget of the solver.
+    ///
+    /// The branch-and-bound search returns the best tour found so far once the time is up, while
+    /// the heuristic spends the time on iterated local search. Dynamic programming ignores the
+    /// time limit, as it runs in a predictable amount of time.
+    ///
+    /// Defaults to `None`, that is, no time limit.
+    pub time_limit: Option<Duration>,
+}
+
+/// Result of [`solve`].
+#[derive(Debug, Clone)]
+pub struct SolveResult {
+    /// The best tour found.
+    pub tour: UnTour,
+    /// The algorithm which found the tour.
+    pub algorithm: Algorithm,
+    /// Whether the tour is proven to be optimal.
+    pub is_optimal: bool,
+}
+
+/// Solve the instance with the configured algorithm or the one suited best for its size.
+///
+/// See the [module-level documentation][crate::solve] for details. Returns the errors of the
+/// chosen algorithm, e.g. if the graph of finite edges cannot contain a tour or if
+/// [`Algorithm::DynamicProgramming`] is chosen for an instance with more than
+/// [`MAX_DIMENSION`](crate::dynamic_programming::MAX_DIMENSION) nodes.
+pub fn solve(
+    instance: &TSPSymInstance<Matrix<Distance>>,
+    config: &SolveConfig,
+) -> Result<SolveResult, SolverError> {
+    let start = Instant::now();
+    let distances = instance.distance_matrix();
+    let dimension = distances.dimension();
+    let algorithm = config.algorithm.unwrap_or(
+        if dimension <= MAX_DYNAMIC_PROGRAMMING_DIMENSION && instance.fixed_edges().is_empty() {
+            Algorithm::DynamicProgramming
+        } else if dimension <= MAX_BRANCH_AND_BOUND_DIMENSION {
+            Algorithm::BranchAndBound
+        } else {
+            Algorithm::Heuristic
+        },
+    );
+    debug!("Solving instance with {dimension} nodes using {algorithm:?}");
+
+    let (tour, is_optimal) = match algorithm {
+        Algorithm::DynamicProgramming => {
+            if dimension > MAX_DIMENSION {
+                return Err(SolverError::DimensionTooLarge(dimension, MAX_DIMENSION));
+            }
+            (dynamic_programming(distances)?, true)
+        }
+        Algorithm::BranchAndBound => {
+            let held_karp_config = HeldKarpConfig {
+                fixed_edges: instance.fixed_edges().to_vec(),
+                time_limit: config.time_limit,
+                ..HeldKarpConfig::default()
+            };
+            let fixed = instance.fixed_edge_set();
+            let solution =
+                held_karp_with_initial_tour(distances, &held_karp_config, |distances| {
+                    heuristic_tour(distances, &fixed, None)
+                })?;
+            (solution.tour, solution.is_optimal)
+        }
+        Algorithm::Heuristic => {
+            check_finite_graph(distances)?;
+            let deadline = config.time_limit.map(|time_limit| start + time_limit);
+            let tour = heuristic_tour(distances, &instance.fixed_edge_set(), deadline);
+            (tour, false)
+        }
+    };
+
+    Ok(SolveResult {
+        tour,
+        algorithm,
+        is_optimal,
+    })
+}
+
+/// Computes a greedy edge tour improved by variable neighborhood descent. Until the deadline (if
+/// any), the best tour is repeatedly perturbed by a double-bridge move and improved again.
+fn heuristic_tour(
+    distances: &Matrix<Distance>,
+    fixed: &FixedEdgeSet,
+    deadline: Option<Instant>,
+) -> UnTour {
+    let neighbors = NeighborLists::new(distances, GREEDY_NEIGHBORS);
+    let mut best_tour = greedy_edge(distances, &neighbors);
+    let mut best_cost = variable_neighborhood_descent(distances, &mut best_tour, fixed);
+
+    if let Some(deadline) = deadline {
+        let mut rng = SmallRng::seed_from_u64(0);
+        while Instant::now() < deadline {
+            let mut tour = best_tour.clone();
+            double_bridge(&mut tour, distances, &mut rng);
+            let cost = variable_neighborhood_descent(distances, &mut tour, fixed);
+            if cost < best_cost {
+                debug!("Iterated local search improved the tour to {}", cost.0);
+                best_tour = tour;
+                best_cost = cost;
+            }
+        }
+    }
+
+    best_tour.to_un_tour(distances)
+}
@@ -0,0 +1,116 @@
+/*!
+Minimum spanning trees, the basis of the [Christofides algorithm][crate::christofides] and of
+spanning tree lower bounds.
+
+The Held-Karp solver uses its own variant of Prim's algorithm, which additionally respects edge
+states and node penalties, see [`min_one_tree`](crate::held_karp_mod::min_one_tree).
+*/
+
+use alloc::{vec, vec::Vec};
+
+use tsp_core::instance::{
+    distance::{Distance, DistanceSource},
+    edge::UnEdge,
+    node::Node,
+};
+
+/// Compute a minimum spanning tree of all nodes using Prim's algorithm in `O(n^2)`.
+///
+/// Returns the `n - 1` edges of the tree, each pointing from the node already in the tree to the
+/// node it connects, in the order they were added. Edges with distance [`Distance::MAX`] are not
+/// treated specially, so the tree only uses them if the remaining edges do not connect all nodes.
+///
+/// The distances are assumed to be symmetric.
+pub fn minimum_spanning_tree(distances: &impl DistanceSource) -> Vec<UnEdge> {
+    let dimension = distances.dimension();
+    let mut tree = Vec::with_capacity(dimension.saturating_sub(1));
+    if dimension == 0 {
+        return tree;
+    }
+
+    let mut in_tree = vec![false; dimension];
+    // For each node, track the cheapest connection to the tree and the node in the tree it leads to
+    let mut best_cost_to_node = vec![Distance::MAX; dimension];
+    let mut best_pred_to_node = vec![Node(0); dimension];
+
+    let mut current = Node(0);
+    in_tree[0] = true;
+
+    for _ in 1..dimension {
+        let mut cheapest_node = None;
+        let mut cheapest_cost = Distance::MAX;
+
+        for next in 0..dimension {
+            if in_tree[next] {
+                continue;
+            }
+            let distance = distances.distance(current, Node(next));
+            if distance < best_cost_to_node[next] {
+                best_cost_to_node[next] = distance;
+                best_pred_to_node[next] = current;
+            }
+            if cheapest_node.is_none() || best_cost_to_node[next] < cheapest_cost {
+                cheapest_cost = best_cost_to_node[next];
+                cheapest_node = Some(Node(next));
+            }
+        }
+
+        let cheapest_node =
+            cheapest_node.expect("There is always a node left to add to the spanning tree");
+        tree.push(UnEdge::new(
+            best_pred_to_node[cheapest_node.0],
+            cheapest_node,
+        ));
+        in_tree[cheapest_node.0] = true;
+        current = cheapest_node;
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use tsp_core::instance::matrix::{Matrix, MatrixSym};
+
+    use super::*;
+
+    #[test]
+    fn test_spanning_tree_points_on_line() {
+        let positions: [i32; 5] = [0, 10, 3, 11, 5];
+        let distance_function =
+            |from: Node, to: Node| Distance((positions[from.0] - positions[to.0]).abs());
+        let full = Matrix::new_from_distance_function(positions.len(), distance_function);
+        let symmetric = MatrixSym::new_from_distance_function(positions.len(), distance_function);
+
+        for tree in [
+            minimum_spanning_tree(&full),
+            minimum_spanning_tree(&symmetric),
+        ] {
+            let mut tree = tree;
+            tree.sort();
+            assert_eq!(
+                tree,
+                vec![
+                    UnEdge::new(Node(0), Node(2)),
+                    UnEdge::new(Node(1), Node(3)),
+                    UnEdge::new(Node(1), Node(4)),
+                    UnEdge::new(Node(2), Node(4)),
+                ]
+            );
+            let cost: Distance = tree
+                .iter()
+                .map(|edge| full.distance(edge.from, edge.to))
+                .sum();
+            assert_eq!(cost, Distance(11));
+        }
+    }
+
+    #[test]
+    fn test_spanning_tree_trivial_instances() {
+        let distances = Matrix::new_from_dimension_with_value(1, Distance(0));
+        assert!(minimum_spanning_tree(&distances).is_empty());
+
+        let distances = Matrix::new_from_dimension_with_value(0, Distance(0));
+        assert!(minimum_spanning_tree(&distances).is_empty());
+    }
+}
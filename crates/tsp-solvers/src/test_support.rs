@@ -0,0 +1,37 @@
+/*!
+Helpers for testing the solvers on instance files, e.g. in the test suites of crates building on
+the solvers. Requires the `test-support` feature, which pulls in the parser.
+*/
+
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix};
+
+use crate::held_karp;
+
+/// Solves the instance with [`held_karp`] and asserts that the cost of the found tour equals the
+/// cost of the optimal tour in the given `.opt.tour` file.
+///
+/// Panics if either file cannot be parsed, if the optimal tour does not visit all nodes of the
+/// instance or if the costs differ.
+pub fn assert_tour_optimal(instance_path: &str, opt_tour_path: &str) {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance(instance_path).unwrap();
+    let distances = tsp_instance.distance_matrix();
+    let opt_tour = tsp_parser::parse_tsp_tour(opt_tour_path).unwrap();
+    assert_eq!(
+        opt_tour.len(),
+        distances.dimension(),
+        "Optimal tour {opt_tour_path} does not visit all nodes of {instance_path}"
+    );
+    let opt_cost: Distance = opt_tour
+        .iter()
+        .zip(opt_tour.iter().cycle().skip(1))
+        .map(|(&from, &to)| distances.get_data(from, to))
+        .sum();
+
+    let best_tour = held_karp(distances).unwrap();
+    assert_eq!(
+        best_tour.cost, opt_cost,
+        "Held-Karp computed tour length {} does not match optimal tour length {} for instance {}",
+        best_tour.cost.0, opt_cost.0, instance_path
+    );
+}
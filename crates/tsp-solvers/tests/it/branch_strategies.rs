@@ -0,0 +1,56 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix};
+use tsp_solvers::{BranchStrategy, HeldKarpConfig, held_karp_with_config};
+
+/// Solves the instance with all branch strategies and checks that they agree on the tour length.
+///
+/// Returns the number of search nodes explored by the cheapest reduced cost and the most
+/// constrained node strategy, in that order.
+fn compare_branch_strategies(instance_path: &str) -> (usize, usize) {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance(instance_path).unwrap();
+
+    let mut results = Vec::new();
+    for branch_strategy in [
+        BranchStrategy::CheapestReducedCost,
+        BranchStrategy::MostConstrainedNode,
+    ] {
+        let config = HeldKarpConfig {
+            branch_strategy,
+            ..HeldKarpConfig::default()
+        };
+        let solution = held_karp_with_config(tsp_instance.distance_matrix(), &config).unwrap();
+        assert!(solution.nodes_explored > 0);
+        results.push(solution);
+    }
+
+    // All strategies are exact, so they have to agree on the optimal tour length
+    assert!(
+        results
+            .windows(2)
+            .all(|pair| pair[0].tour.cost == pair[1].tour.cost)
+    );
+
+    (results[0].nodes_explored, results[1].nodes_explored)
+}
+
+#[test]
+fn test_branch_strategies_search_tree_size_gr24() {
+    let (cheapest_reduced_cost, most_constrained_node) =
+        compare_branch_strategies("../../instances/tsplib_symmetric/gr24.tsp");
+    // Branching at the most constrained node fixes the tour structure sooner, which results in a
+    // smaller search tree on this instance
+    assert!(most_constrained_node < cheapest_reduced_cost);
+}
+
+#[test]
+fn test_branch_strategies_search_tree_size_eil51() {
+    let (cheapest_reduced_cost, most_constrained_node) =
+        compare_branch_strategies("../../instances/tsplib_symmetric/eil51.tsp");
+    assert!(most_constrained_node < cheapest_reduced_cost);
+}
+
+#[test]
+#[ignore = "Solving gr96 takes several minutes"]
+fn test_branch_strategies_search_tree_size_gr96() {
+    compare_branch_strategies("../../instances/tsplib_symmetric/gr96.tsp");
+}
@@ -0,0 +1,88 @@
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge::UnEdge, matrix::Matrix, node::Node,
+};
+use tsp_solvers::{HeldKarpConfig, SolverError, held_karp_restricted, held_karp_with_config};
+
+fn instance_12() -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap()
+}
+
+/// The edges from each node to its `k` nearest neighbors.
+fn nearest_neighbor_edges(distances: &Matrix<Distance>, k: usize) -> Vec<UnEdge> {
+    let dimension = distances.dimension();
+    let mut edges = Vec::new();
+    for from in 0..dimension {
+        let mut neighbors: Vec<usize> = (0..dimension).filter(|&to| to != from).collect();
+        neighbors.sort_by_key(|&to| distances.get_data(Node(from), Node(to)));
+        edges.extend(
+            neighbors
+                .into_iter()
+                .take(k)
+                .map(|to| UnEdge::new(Node(from), Node(to))),
+        );
+    }
+    edges
+}
+
+#[test]
+fn test_held_karp_restricted_nearest_neighbors() {
+    let tsp_instance = instance_12();
+    let distances = tsp_instance.distance_matrix();
+    let candidates = nearest_neighbor_edges(distances, 5);
+
+    let tour = held_karp_restricted(distances, &candidates).unwrap();
+    assert_eq!(tour.len(), 12);
+    assert!(tour.cost >= Distance(1200));
+    for edge in &tour.edges {
+        assert!(
+            candidates.contains(edge) || candidates.contains(&UnEdge::new(edge.to, edge.from)),
+            "Edge {edge:?} is not a candidate"
+        );
+    }
+}
+
+#[test]
+fn test_held_karp_restricted_without_optimal_edge() {
+    let tsp_instance = instance_12();
+    let distances = tsp_instance.distance_matrix();
+    // All edges except for the edge between nodes 0 and 5 of the unique optimal tour
+    let candidates: Vec<UnEdge> = (0..12)
+        .flat_map(|from| (0..from).map(move |to| UnEdge::new(Node(from), Node(to))))
+        .filter(|&edge| edge != UnEdge::new(Node(5), Node(0)))
+        .collect();
+
+    let tour = held_karp_restricted(distances, &candidates).unwrap();
+    assert!(!tour.contains_edge(&UnEdge::new(Node(0), Node(5))));
+    assert!(tour.cost > Distance(1200));
+
+    // Fixed edges take precedence over the candidate edges
+    let config = HeldKarpConfig {
+        candidate_edges: Some(candidates),
+        fixed_edges: vec![UnEdge::new(Node(0), Node(5))],
+        ..HeldKarpConfig::default()
+    };
+    let solution = held_karp_with_config(distances, &config).unwrap();
+    assert_eq!(solution.tour.cost, Distance(1200));
+}
+
+#[test]
+fn test_held_karp_restricted_rejects_invalid_candidates() {
+    let tsp_instance = instance_12();
+    let distances = tsp_instance.distance_matrix();
+
+    let result = held_karp_restricted(distances, &[UnEdge::new(Node(0), Node(12))]);
+    assert_eq!(
+        result,
+        Err(SolverError::InvalidCandidateEdge(UnEdge::new(
+            Node(0),
+            Node(12)
+        )))
+    );
+
+    // Node 11 only has a single candidate edge
+    let mut candidates = nearest_neighbor_edges(distances, 5);
+    candidates.retain(|edge| edge.from != Node(11) && edge.to != Node(11));
+    candidates.push(UnEdge::new(Node(11), Node(0)));
+    let result = held_karp_restricted(distances, &candidates);
+    assert_eq!(result, Err(SolverError::CandidateDegreeTooSmall(Node(11))));
+}
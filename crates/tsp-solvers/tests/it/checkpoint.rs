@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use tsp_core::instance::{TSPSymInstance, UnTour, distance::Distance, matrix::Matrix, node::Node};
+use tsp_solvers::{
+    HeldKarpConfig, HeldKarpSolution, construction::nearest_neighbor, held_karp_resume,
+    held_karp_with_initial_tour,
+};
+
+fn nearest_neighbor_tour(distances: &Matrix<Distance>) -> UnTour {
+    nearest_neighbor(distances, Node(0)).to_un_tour(distances)
+}
+
+fn parse_att48() -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap()
+}
+
+/// Resumes the search in slices of the given duration until it is completed.
+fn resume_until_optimal(
+    distances: &Matrix<Distance>,
+    mut solution: HeldKarpSolution,
+    time_slice: Duration,
+) -> HeldKarpSolution {
+    let config = HeldKarpConfig {
+        time_limit: Some(time_slice),
+        ..HeldKarpConfig::default()
+    };
+    while let Some(checkpoint) = solution.checkpoint {
+        assert!(!solution.is_optimal);
+        solution = held_karp_resume(distances, &config, checkpoint).unwrap();
+    }
+    solution
+}
+
+#[test]
+fn test_resumed_search_matches_uninterrupted_search() {
+    let instance = parse_att48();
+    let distances = instance.distance_matrix();
+
+    let uninterrupted =
+        held_karp_with_initial_tour(distances, &HeldKarpConfig::default(), nearest_neighbor_tour)
+            .unwrap();
+    assert!(uninterrupted.is_optimal);
+    assert!(uninterrupted.checkpoint.is_none());
+
+    let config = HeldKarpConfig {
+        time_limit: Some(Duration::from_millis(20)),
+        ..HeldKarpConfig::default()
+    };
+    let interrupted =
+        held_karp_with_initial_tour(distances, &config, nearest_neighbor_tour).unwrap();
+    let resumed = resume_until_optimal(distances, interrupted, Duration::from_millis(20));
+
+    assert!(resumed.is_optimal);
+    assert_eq!(resumed.tour.cost, Distance(10628));
+    // The resumed search explores exactly the nodes the uninterrupted search explores
+    assert_eq!(resumed.nodes_explored, uninterrupted.nodes_explored);
+}
+
+#[test]
+fn test_resume_rejects_checkpoint_of_other_instance() {
+    let instance = parse_att48();
+    let config = HeldKarpConfig {
+        time_limit: Some(Duration::ZERO),
+        ..HeldKarpConfig::default()
+    };
+    let solution =
+        held_karp_with_initial_tour(instance.distance_matrix(), &config, nearest_neighbor_tour)
+            .unwrap();
+    let checkpoint = solution.checkpoint.unwrap();
+    assert_eq!(checkpoint.dimension(), 48);
+    assert_eq!(checkpoint.nodes_explored(), 0);
+    assert_eq!(checkpoint.best_tour(), Some(&solution.tour));
+
+    let other: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/gr24.tsp").unwrap();
+    assert_eq!(
+        held_karp_resume(other.distance_matrix(), &config, checkpoint.clone()).unwrap_err(),
+        tsp_solvers::SolverError::InvalidCheckpoint(24)
+    );
+
+    // An instance of the same dimension with a single different distance is rejected as well
+    let mut other = instance.distance_matrix().clone();
+    let distance = other.get_data(Node(3), Node(7));
+    other.set_data_symmetric(Node(3), Node(7), distance + Distance(1));
+    assert_eq!(
+        held_karp_resume(&other, &config, checkpoint).unwrap_err(),
+        tsp_solvers::SolverError::InvalidCheckpoint(48)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_resume_from_checkpoint_file() {
+    use tsp_solvers::held_karp_mod::{Checkpoint, CheckpointConfig, held_karp_resume_from};
+
+    let instance = parse_att48();
+    let distances = instance.distance_matrix();
+    let path =
+        std::env::temp_dir().join(format!("tsp_rust_checkpoint_{}.json", std::process::id()));
+
+    // Write a checkpoint before every node and stop the search early
+    let config = HeldKarpConfig {
+        time_limit: Some(Duration::from_millis(20)),
+        checkpoint: Some(CheckpointConfig {
+            path: path.clone(),
+            interval: Duration::ZERO,
+        }),
+        ..HeldKarpConfig::default()
+    };
+    let interrupted =
+        held_karp_with_initial_tour(distances, &config, nearest_neighbor_tour).unwrap();
+    let checkpoint = Checkpoint::load(&path).unwrap();
+    assert_eq!(
+        checkpoint.nodes_explored(),
+        interrupted.checkpoint.unwrap().nodes_explored()
+    );
+
+    let resumed = held_karp_resume_from(distances, &HeldKarpConfig::default(), &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(resumed.is_optimal);
+    assert_eq!(resumed.tour.cost, Distance(10628));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_resume_rejects_corrupted_checkpoint_file() {
+    use tsp_solvers::held_karp_mod::{Checkpoint, held_karp_resume_from};
+
+    let instance = parse_att48();
+    let distances = instance.distance_matrix();
+    let config = HeldKarpConfig {
+        time_limit: Some(Duration::from_millis(20)),
+        ..HeldKarpConfig::default()
+    };
+    let checkpoint = held_karp_with_initial_tour(distances, &config, nearest_neighbor_tour)
+        .unwrap()
+        .checkpoint
+        .unwrap();
+    let path = std::env::temp_dir().join(format!(
+        "tsp_rust_corrupted_checkpoint_{}.json",
+        std::process::id()
+    ));
+    checkpoint.save(&path).unwrap();
+    let json = std::fs::read_to_string(&path).unwrap();
+
+    // Undo records which do not match the edge states, a node outside of the instance and a
+    // best tour which does not visit all nodes
+    let corruptions = [
+        json.replacen("\"Fixed\"", "\"Excluded\"", 1),
+        json.replacen("\"from\":", "\"from\":100", 1),
+        json.replacen("\"edges\":[{", "\"edges\":[{\"from\":0,\"to\":1},{", 1),
+    ];
+    for corrupted in corruptions {
+        assert_ne!(corrupted, json);
+        std::fs::write(&path, corrupted).unwrap();
+        assert!(Checkpoint::load(&path).is_ok());
+        assert!(held_karp_resume_from(distances, &HeldKarpConfig::default(), &path).is_err());
+    }
+    std::fs::remove_file(&path).unwrap();
+}
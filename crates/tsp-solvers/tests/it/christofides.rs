@@ -0,0 +1,21 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix, node::Node};
+use tsp_solvers::christofides::christofides_greedy;
+
+#[test]
+fn test_christofides_greedy_valid_tour_eil76() {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/eil76.tsp").unwrap();
+    let distances = tsp_instance.distance_matrix();
+
+    // TourOrder guarantees that each node is visited exactly once, i.e. a single Hamiltonian cycle
+    let tour = christofides_greedy(distances);
+    assert_eq!(tour.len(), distances.dimension());
+    assert_eq!(tour.edges().count(), distances.dimension());
+
+    let un_tour = tour.to_un_tour(distances);
+    assert_eq!(un_tour.dimension(), distances.dimension());
+    assert!((0..distances.dimension()).all(|node| un_tour.degree(Node(node)) == 2));
+
+    // The optimal tour has length 538
+    assert!(un_tour.cost >= Distance(538));
+}
@@ -0,0 +1,78 @@
+use rand::{RngExt, SeedableRng, rngs::SmallRng};
+use tsp_core::{
+    instance::{TSPSymInstance, distance::Distance, matrix::Matrix, node::Node},
+    tsp_lib_spec::EdgeWeightType,
+};
+use tsp_parser::data_section::{Point2D, distance_function::distance_function_for};
+use tsp_solvers::{
+    construction::{cheapest_insertion, double_tree, nearest_neighbor},
+    held_karp,
+};
+
+/// Generates a random Euclidean instance with the given number of nodes in a 1000 x 1000 square.
+fn random_euclidean_instance(dimension: usize, seed: u64) -> Matrix<Distance> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let points: Vec<Point2D> = (0..dimension)
+        .map(|_| Point2D {
+            x: rng.random_range(0.0..1000.0),
+            y: rng.random_range(0.0..1000.0),
+        })
+        .collect();
+    let distance_function = distance_function_for(EdgeWeightType::EUC_2D).unwrap();
+    Matrix::new_from_distance_function(dimension, |from, to| {
+        distance_function(&points[from.0], &points[to.0])
+    })
+}
+
+/// Seed of the random instance. The ratios below hold for this seed, other seeds may give slightly
+/// worse tours, as the ratios are only typical values and no guarantees.
+const SEED: u64 = 13;
+const DIMENSION: usize = 25;
+
+/// Returns the ratio of the cost of the tour to the optimal cost.
+fn ratio(cost: Distance, optimum: Distance) -> f64 {
+    cost.0 as f64 / optimum.0 as f64
+}
+
+#[test]
+fn test_nearest_neighbor_ratio() {
+    let distances = random_euclidean_instance(DIMENSION, SEED);
+    let optimum = held_karp(&distances).unwrap().cost;
+
+    let tour = nearest_neighbor(&distances, Node(0));
+    let ratio = ratio(tour.cost(&distances), optimum);
+    assert!(
+        ratio <= 1.25,
+        "Nearest neighbor tour is {ratio:.3} times the optimum"
+    );
+}
+
+#[test]
+fn test_cheapest_insertion_ratio() {
+    let distances = random_euclidean_instance(DIMENSION, SEED);
+    let optimum = held_karp(&distances).unwrap().cost;
+
+    let tour = cheapest_insertion(&distances);
+    let ratio = ratio(tour.cost(&distances), optimum);
+    assert!(
+        ratio <= 1.15,
+        "Cheapest insertion tour is {ratio:.3} times the optimum"
+    );
+}
+
+#[test]
+fn test_double_tree_ratio_eil51() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/eil51.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let optimum = Distance(426);
+
+    let tour = double_tree(distances);
+    assert_eq!(tour.len(), distances.dimension());
+    assert!((0..distances.dimension()).all(|node| tour.degree(Node(node)) == 2));
+    let ratio = ratio(tour.cost, optimum);
+    assert!(
+        ratio <= 2.0,
+        "Double-tree tour is {ratio:.3} times the optimum"
+    );
+}
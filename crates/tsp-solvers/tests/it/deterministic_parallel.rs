@@ -0,0 +1,73 @@
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node,
+};
+use tsp_solvers::{
+    construction::nearest_neighbor,
+    held_karp,
+    held_karp_mod::{ParallelConfig, held_karp_parallel_with_initial_tour},
+    identity_tour,
+    local_search::two_opt,
+};
+
+#[test]
+fn test_deterministic_parallel_independent_of_threads_att48() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap();
+    let distances = instance.distance_matrix();
+
+    let tours: Vec<_> = [1, 2, 4]
+        .into_iter()
+        .map(|threads| {
+            let config = ParallelConfig {
+                threads,
+                deterministic: true,
+            };
+            // The deterministic mode bounds with the initial tour only, so it needs a good one
+            held_karp_parallel_with_initial_tour(distances, &config, |distances| {
+                let mut tour = nearest_neighbor(distances, Node(0));
+                two_opt(distances, &mut tour, &FixedEdgeSet::new());
+                tour.to_un_tour(distances)
+            })
+            .unwrap()
+        })
+        .collect();
+
+    assert_eq!(tours[0].cost, Distance(10628));
+    // The tours have to be identical, including the order of their edges
+    assert_eq!(tours[0], tours[1]);
+    assert_eq!(tours[0], tours[2]);
+}
+
+#[test]
+fn test_parallel_initial_tour_cost_is_recomputed() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let optimal_cost = held_karp(distances).unwrap().cost;
+
+    for deterministic in [false, true] {
+        let config = ParallelConfig {
+            deterministic,
+            ..ParallelConfig::default()
+        };
+        // A bogus cost of 0 would prune every node if it were trusted, and a malformed tour
+        // would be returned as the result
+        let tour = held_karp_parallel_with_initial_tour(distances, &config, |distances| {
+            let mut tour = identity_tour(distances);
+            tour.cost = Distance(0);
+            tour
+        })
+        .unwrap();
+        assert_eq!(tour.cost, optimal_cost);
+
+        let tour = held_karp_parallel_with_initial_tour(distances, &config, |distances| {
+            let mut tour = identity_tour(distances);
+            tour.edges.pop();
+            tour.cost = Distance(0);
+            tour
+        })
+        .unwrap();
+        assert_eq!(tour.cost, optimal_cost);
+        assert!(tour.to_order(distances.dimension()).is_ok());
+    }
+}
@@ -0,0 +1,46 @@
+use tsp_core::instance::{
+    TSPSymInstance, UnTour, distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix, node::Node,
+};
+use tsp_solvers::{
+    HeldKarpConfig, HeldKarpSolution, construction::nearest_neighbor, held_karp_with_initial_tour,
+    local_search::two_opt,
+};
+
+fn warm_start_tour(distances: &Matrix<Distance>) -> UnTour {
+    let mut tour = nearest_neighbor(distances, Node(0));
+    two_opt(distances, &mut tour, &FixedEdgeSet::new());
+    tour.to_un_tour(distances)
+}
+
+fn solve(distances: &Matrix<Distance>, eliminate_edges: bool) -> HeldKarpSolution {
+    let config = HeldKarpConfig {
+        eliminate_edges,
+        ..HeldKarpConfig::default()
+    };
+    held_karp_with_initial_tour(distances, &config, warm_start_tour).unwrap()
+}
+
+fn check_edge_elimination(instance_name: &str, optimal_length: i32) {
+    let instance: TSPSymInstance<Matrix<Distance>> = tsp_parser::parse_tsp_instance(format!(
+        "../../instances/tsplib_symmetric/{instance_name}.tsp"
+    ))
+    .unwrap();
+    let distances = instance.distance_matrix();
+
+    let without_elimination = solve(distances, false);
+    let with_elimination = solve(distances, true);
+
+    assert_eq!(without_elimination.tour.cost, Distance(optimal_length));
+    assert_eq!(with_elimination.tour.cost, Distance(optimal_length));
+    assert!(with_elimination.nodes_explored <= without_elimination.nodes_explored);
+}
+
+#[test]
+fn test_edge_elimination_att48() {
+    check_edge_elimination("att48", 10628);
+}
+
+#[test]
+fn test_edge_elimination_eil51() {
+    check_edge_elimination("eil51", 426);
+}
@@ -0,0 +1,60 @@
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge::UnEdge, matrix::Matrix, node::Node,
+};
+use tsp_solvers::{HeldKarpConfig, SolverError, held_karp_with_config};
+
+fn instance_12() -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap()
+}
+
+#[test]
+fn test_held_karp_fixed_edges_in_tour() {
+    let tsp_instance = instance_12();
+    // Neither edge is part of the optimal tour, which has length 1200
+    let fixed_edges = vec![UnEdge::new(Node(0), Node(1)), UnEdge::new(Node(2), Node(3))];
+    let config = HeldKarpConfig {
+        fixed_edges: fixed_edges.clone(),
+        ..HeldKarpConfig::default()
+    };
+
+    let solution = held_karp_with_config(tsp_instance.distance_matrix(), &config).unwrap();
+    for edge in &fixed_edges {
+        assert!(
+            solution.tour.contains_edge(edge),
+            "Missing fixed edge {edge:?}"
+        );
+    }
+    assert_eq!(solution.tour.len(), 12);
+    assert!(solution.tour.cost > Distance(1200));
+}
+
+#[test]
+fn test_held_karp_fixed_edges_of_optimal_tour() {
+    let tsp_instance = instance_12();
+    let config = HeldKarpConfig {
+        fixed_edges: vec![UnEdge::new(Node(0), Node(5)), UnEdge::new(Node(0), Node(8))],
+        ..HeldKarpConfig::default()
+    };
+
+    let solution = held_karp_with_config(tsp_instance.distance_matrix(), &config).unwrap();
+    assert_eq!(solution.tour.cost, Distance(1200));
+}
+
+#[test]
+fn test_held_karp_rejects_invalid_fixed_edges() {
+    let tsp_instance = instance_12();
+    let config = HeldKarpConfig {
+        fixed_edges: vec![
+            UnEdge::new(Node(0), Node(1)),
+            UnEdge::new(Node(0), Node(2)),
+            UnEdge::new(Node(0), Node(3)),
+        ],
+        ..HeldKarpConfig::default()
+    };
+
+    let result = held_karp_with_config(tsp_instance.distance_matrix(), &config);
+    assert!(matches!(
+        result,
+        Err(SolverError::FixedDegreeTooLarge(Node(0)))
+    ));
+}
@@ -0,0 +1,75 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix, node::Node};
+use tsp_solvers::find_any_hamiltonian_cycle;
+
+/// Distances of the graph with the given edges (with distance `1 + from + to`), where all other
+/// edges are missing.
+fn sparse_distances(dimension: usize, edges: &[(usize, usize)]) -> Matrix<Distance> {
+    let mut distances = Matrix::new_from_distance_function(dimension, |from, to| {
+        if from == to {
+            Distance(0)
+        } else {
+            Distance::MAX
+        }
+    });
+    for &(from, to) in edges {
+        distances.set_data_symmetric(Node(from), Node(to), Distance((1 + from + to) as i32));
+    }
+    distances
+}
+
+#[test]
+fn test_hamiltonian_cycle_of_prism() {
+    // Two triangles whose corners are connected pairwise
+    #[rustfmt::skip]
+    let edges = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (0, 3), (1, 4), (2, 5)];
+    let distances = sparse_distances(6, &edges);
+
+    let tour = find_any_hamiltonian_cycle(&distances).unwrap();
+    assert!(tour.to_order(6).is_ok(), "{tour:?} is not a tour");
+    assert!(
+        tour.edges
+            .iter()
+            .all(|edge| distances.get_data(edge.from, edge.to) < Distance::MAX)
+    );
+    assert_eq!(
+        tour.cost,
+        tour.edges
+            .iter()
+            .map(|edge| distances.get_data(edge.from, edge.to))
+            .sum()
+    );
+}
+
+#[test]
+fn test_no_hamiltonian_cycle_in_petersen_graph() {
+    // The outer 5-cycle, the inner pentagram and the spokes between them
+    #[rustfmt::skip]
+    let edges = [
+        (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+        (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+        (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+    ];
+    assert_eq!(
+        find_any_hamiltonian_cycle(&sparse_distances(10, &edges)),
+        None
+    );
+}
+
+#[test]
+fn test_no_hamiltonian_cycle_with_cut_node() {
+    // Two triangles sharing node 2
+    let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)];
+    assert_eq!(
+        find_any_hamiltonian_cycle(&sparse_distances(5, &edges)),
+        None
+    );
+}
+
+#[test]
+fn test_hamiltonian_cycle_of_complete_graph() {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let tour = find_any_hamiltonian_cycle(tsp_instance.distance_matrix()).unwrap();
+    assert!(tour.to_order(12).is_ok(), "{tour:?} is not a tour");
+    assert!(tour.cost >= Distance(1200));
+}
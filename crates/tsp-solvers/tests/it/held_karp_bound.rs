@@ -0,0 +1,182 @@
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::{Distance, ScaledDistance},
+    edge::UnEdge,
+    matrix::Matrix,
+    node::Node,
+};
+use tsp_solvers::held_karp_mod::{
+    BoundInfo, ReducedCosts, SubgradientParams, SubgradientTermination, held_karp_bound,
+    held_karp_bound_with_params, one_tree_degrees,
+};
+
+/// Instances with their optimal tour lengths.
+const INSTANCES: [(&str, i32); 3] = [("eil51", 426), ("berlin52", 7542), ("st70", 675)];
+
+fn load(name: &str) -> Matrix<Distance> {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance(format!("../../instances/tsplib_symmetric/{name}.tsp"))
+            .unwrap();
+    instance.distance_matrix().clone()
+}
+
+#[test]
+fn test_held_karp_bound_is_tight_lower_bound() {
+    for (name, optimum) in INSTANCES {
+        let distances = load(name);
+        let BoundInfo {
+            bound, one_tree, ..
+        } = held_karp_bound(&distances, 1_000).unwrap();
+
+        assert!(
+            bound.0 <= optimum,
+            "{name}: bound {} exceeds optimum {optimum}",
+            bound.0
+        );
+        // The Held-Karp bound is usually within 1% of the optimum on these instances
+        assert!(
+            bound.0 as f64 >= 0.98 * optimum as f64,
+            "{name}: bound {} is far below optimum {optimum}",
+            bound.0
+        );
+        // A 1-tree has as many edges as nodes, and node 0 has degree 2
+        assert_eq!(one_tree.len(), distances.dimension());
+        assert_eq!(one_tree_degrees(&one_tree, distances.dimension())[0], 0);
+    }
+}
+
+#[test]
+fn test_held_karp_bound_more_iterations_not_worse() {
+    let distances = load("eil51");
+    let mut previous = Distance(i32::MIN);
+    for iterations in [1, 10, 100, 1_000] {
+        let bound = held_karp_bound(&distances, iterations).unwrap().bound;
+        assert!(
+            bound >= previous,
+            "{iterations} iterations: {bound:?} < {previous:?}"
+        );
+        previous = bound;
+    }
+}
+
+#[test]
+fn test_held_karp_bound_with_params() {
+    let distances = load("berlin52");
+    let default_bound = held_karp_bound_with_params(&distances, &SubgradientParams::default())
+        .unwrap()
+        .bound;
+    assert_eq!(
+        default_bound,
+        held_karp_bound(&distances, 1_000).unwrap().bound
+    );
+
+    // Without any penalty updates, the bound is the one of the minimum 1-tree
+    let params = SubgradientParams {
+        max_iterations: 1,
+        ..SubgradientParams::default()
+    };
+    let info = held_karp_bound_with_params(&distances, &params).unwrap();
+    assert!(info.bound < default_bound);
+    assert_eq!(info.iterations_run, 1);
+    assert_eq!(info.termination, SubgradientTermination::MaxIterations);
+}
+
+#[test]
+fn test_held_karp_bound_small_instances() {
+    for dimension in 0..2 {
+        let distances = Matrix::new(vec![Distance(0); dimension * dimension], dimension);
+        assert_eq!(
+            held_karp_bound(&distances, 10).unwrap(),
+            BoundInfo {
+                bound: Distance(0),
+                one_tree: Vec::new(),
+                node_penalties: vec![ScaledDistance(0); dimension],
+                iterations_run: 0,
+                termination: SubgradientTermination::Converged,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_held_karp_bound_termination() {
+    let distances = load("eil51");
+    let params = SubgradientParams {
+        max_iterations: 1_000_000,
+        ..SubgradientParams::default()
+    };
+    let info = held_karp_bound_with_params(&distances, &params).unwrap();
+    assert!(info.iterations_run < params.max_iterations);
+    assert_ne!(info.termination, SubgradientTermination::MaxIterations);
+
+    // Without decay of alpha, the step sizes stay large
+    let params = SubgradientParams {
+        max_iterations: 10,
+        beta: 1.0,
+        ..SubgradientParams::default()
+    };
+    let info = held_karp_bound_with_params(&distances, &params).unwrap();
+    assert_eq!(info.iterations_run, 10);
+    assert_eq!(info.termination, SubgradientTermination::MaxIterations);
+}
+
+#[test]
+fn test_reduced_costs() {
+    let distances = load("berlin52");
+    let dimension = distances.dimension();
+
+    // With a single iteration, the bound is the cost of the only 1-tree, i.e. the sum of the
+    // reduced costs of its edges plus twice the penalties
+    let params = SubgradientParams {
+        max_iterations: 1,
+        ..SubgradientParams::default()
+    };
+    let info = held_karp_bound_with_params(&distances, &params).unwrap();
+    let reduced_costs = info.reduced_costs(&distances);
+    let reduced_cost_sum: i32 = info
+        .one_tree
+        .iter()
+        .map(|&edge| reduced_costs.reduced_cost(edge).0)
+        .sum();
+    let penalty_sum: i32 = info.node_penalties.iter().map(|penalty| penalty.0).sum();
+    let lower = reduced_cost_sum + ((2 * penalty_sum) >> 5);
+    assert!(
+        (lower..=lower + dimension as i32 + 1).contains(&info.bound.0),
+        "bound {:?} does not match the reduced costs {lower}",
+        info.bound
+    );
+
+    // Reduced costs are symmetric and consistent with the distances and penalties
+    let info = held_karp_bound(&distances, 1_000).unwrap();
+    let reduced_costs = info.reduced_costs(&distances);
+    for from in (0..dimension).map(Node) {
+        for to in (0..dimension).map(Node) {
+            let reduced_cost = reduced_costs.reduced_cost(UnEdge { from, to });
+            assert_eq!(
+                reduced_cost,
+                reduced_costs.reduced_cost(UnEdge { from: to, to: from })
+            );
+            let penalties = info.node_penalties[from.0].0 + info.node_penalties[to.0].0;
+            let expected = (distances.get_data(from, to).0 * 32 - penalties).div_euclid(32);
+            assert_eq!(reduced_cost.0, expected);
+        }
+    }
+}
+
+#[test]
+fn test_reduced_costs_missing_edge() {
+    let mut distances = load("eil51");
+    distances.set_data_symmetric(Node(3), Node(7), Distance::MAX);
+    let penalties = vec![ScaledDistance(-1_000); distances.dimension()];
+
+    let reduced_costs = ReducedCosts::new(&distances, &penalties);
+    let edge = |from, to| UnEdge {
+        from: Node(from),
+        to: Node(to),
+    };
+    assert_eq!(reduced_costs.reduced_cost(edge(3, 7)), Distance::MAX);
+    assert_eq!(
+        reduced_costs.reduced_cost(edge(0, 1)).0,
+        distances.get_data(Node(0), Node(1)).0 + 62
+    );
+}
@@ -0,0 +1,102 @@
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix, node::Node};
+use tsp_solvers::{SolverError, held_karp_path};
+
+fn load_12() -> Matrix<Distance> {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    tsp_instance.distance_matrix().clone()
+}
+
+/// Cost of the shortest Hamiltonian path with the given endpoints, computed by dynamic
+/// programming over the subsets of visited nodes.
+fn shortest_path_cost(distances: &Matrix<Distance>, start: Option<Node>, end: Option<Node>) -> i32 {
+    let dimension = distances.dimension();
+    let all = (1 << dimension) - 1;
+    // cost[subset][last]: cheapest path visiting the subset and ending at last
+    let mut cost = vec![vec![i32::MAX; dimension]; 1 << dimension];
+    for node in 0..dimension {
+        if start.is_none_or(|start| start.0 == node) {
+            cost[1 << node][node] = 0;
+        }
+    }
+    for subset in 1..=all {
+        for last in 0..dimension {
+            let current = cost[subset][last];
+            if current == i32::MAX {
+                continue;
+            }
+            for next in (0..dimension).filter(|&next| subset & (1 << next) == 0) {
+                let next_cost = current + distances.get_data(Node(last), Node(next)).0;
+                let entry = &mut cost[subset | (1 << next)][next];
+                *entry = (*entry).min(next_cost);
+            }
+        }
+    }
+    (0..dimension)
+        .filter(|&last| end.is_none_or(|end| end.0 == last))
+        .map(|last| cost[all][last])
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_held_karp_path_optimal_on_12() {
+    let distances = load_12();
+    for (start, end) in [
+        (None, None),
+        (Some(Node(0)), None),
+        (None, Some(Node(5))),
+        (Some(Node(3)), Some(Node(7))),
+        (Some(Node(11)), Some(Node(0))),
+    ] {
+        let path = held_karp_path(&distances, start, end).unwrap();
+        assert_eq!(path.len(), 12);
+        if let Some(start) = start {
+            assert_eq!(path.nodes().first(), Some(&start));
+        }
+        if let Some(end) = end {
+            assert_eq!(path.nodes().last(), Some(&end));
+        }
+        assert_eq!(
+            path.path_cost(&distances).0,
+            shortest_path_cost(&distances, start, end),
+            "start = {start:?}, end = {end:?}"
+        );
+    }
+}
+
+#[test]
+fn test_held_karp_path_with_missing_edges() {
+    // The nodes are only connected along a line, which has no tour but one path
+    let mut distances = Matrix::new_from_distance_function(5, |from, to| {
+        if from == to {
+            Distance(0)
+        } else {
+            Distance::MAX
+        }
+    });
+    for node in 1..5 {
+        distances.set_data_symmetric(Node(node - 1), Node(node), Distance(node as i32));
+    }
+
+    let path = held_karp_path(&distances, Some(Node(4)), None).unwrap();
+    assert_eq!(path.nodes(), [4, 3, 2, 1, 0].map(Node));
+    assert_eq!(path.path_cost(&distances), Distance(10));
+    assert_eq!(
+        held_karp_path(&distances, Some(Node(2)), None),
+        Err(SolverError::NoTourFound)
+    );
+}
+
+#[test]
+fn test_held_karp_path_small_instances() {
+    for dimension in 0..4 {
+        let distances = Matrix::new(vec![Distance(1); dimension * dimension], dimension);
+        let path = held_karp_path(&distances, None, None).unwrap();
+        assert_eq!(path.len(), dimension);
+        assert_eq!(
+            path.path_cost(&distances),
+            Distance(dimension.saturating_sub(1) as i32)
+        );
+    }
+}
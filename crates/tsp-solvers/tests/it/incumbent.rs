@@ -0,0 +1,29 @@
+use std::sync::{Arc, Mutex};
+
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix};
+use tsp_solvers::{HeldKarpConfig, held_karp_with_config};
+
+#[test]
+fn test_shared_incumbent_holds_optimal_tour_after_solve() {
+    let tsp_instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let incumbent = Arc::new(Mutex::new(None));
+    let config = HeldKarpConfig {
+        incumbent: Some(incumbent.clone()),
+        ..HeldKarpConfig::default()
+    };
+
+    // Poll the incumbent from another thread while solving
+    let solution = std::thread::scope(|scope| {
+        let solver = scope.spawn(|| held_karp_with_config(tsp_instance.distance_matrix(), &config));
+        while !solver.is_finished() {
+            if let Some(tour) = incumbent.lock().unwrap().as_ref() {
+                assert_eq!(tour.edges.len(), 12);
+            }
+        }
+        solver.join().unwrap().unwrap()
+    });
+
+    assert_eq!(incumbent.lock().unwrap().as_ref(), Some(&solution.tour));
+    assert_eq!(solution.tour.cost, Distance(1200));
+}
@@ -0,0 +1,114 @@
+use tsp_core::instance::{
+    TSPSymInstance, UnTour, distance::Distance, edge::UnEdge, matrix::Matrix, node::Node,
+};
+use tsp_solvers::{
+    HeldKarpConfig, construction::nearest_neighbor, held_karp_with_config,
+    held_karp_with_initial_tour, identity_tour,
+};
+
+fn att48() -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance("../../instances/tsplib_symmetric/att48.tsp").unwrap()
+}
+
+#[test]
+fn test_nearest_neighbor_initial_tour() {
+    let instance = att48();
+    let distances = instance.distance_matrix();
+    let config = HeldKarpConfig::default();
+
+    let mut called = false;
+    let solution = held_karp_with_initial_tour(distances, &config, |distances| {
+        called = true;
+        nearest_neighbor(distances, Node(0)).to_un_tour(distances)
+    })
+    .unwrap();
+
+    assert!(called);
+    assert_eq!(solution.tour.cost, Distance(10628));
+}
+
+#[test]
+fn test_optimal_initial_tour_is_kept() {
+    let instance = att48();
+    let distances = instance.distance_matrix();
+    let config = HeldKarpConfig::default();
+    let optimal_tour = held_karp_with_config(distances, &config).unwrap().tour;
+
+    // No better tour exists, so the search can only prove the initial tour optimal
+    let solution =
+        held_karp_with_initial_tour(distances, &config, |_| optimal_tour.clone()).unwrap();
+    assert_eq!(solution.tour, optimal_tour);
+}
+
+#[test]
+fn test_initial_tour_without_fixed_edges_is_ignored() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let unconstrained = held_karp_with_config(distances, &HeldKarpConfig::default())
+        .unwrap()
+        .tour;
+    let fixed_edge = unconstrained.edges[0];
+    let config = HeldKarpConfig {
+        fixed_edges: vec![fixed_edge],
+        ..HeldKarpConfig::default()
+    };
+
+    // The identity tour with a bogus cost of 0 would prune every node if it were used
+    let solution = held_karp_with_initial_tour(distances, &config, |distances| {
+        let mut tour = identity_tour(distances);
+        tour.edges.retain(|edge| *edge != fixed_edge);
+        tour.cost = Distance(0);
+        tour
+    })
+    .unwrap();
+    assert!(solution.tour.contains_edge(&fixed_edge));
+    assert_eq!(solution.tour.cost, unconstrained.cost);
+}
+
+#[test]
+fn test_initial_tour_cost_is_recomputed() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let config = HeldKarpConfig::default();
+    let optimal_cost = held_karp_with_config(distances, &config).unwrap().tour.cost;
+
+    // A valid tour with a bogus cost of 0 would prune every node if the cost were trusted
+    let solution = held_karp_with_initial_tour(distances, &config, |distances| {
+        let mut tour = identity_tour(distances);
+        tour.cost = Distance(0);
+        tour
+    })
+    .unwrap();
+    assert_eq!(solution.tour.cost, optimal_cost);
+    assert_eq!(solution.tour.compute_cost(distances), optimal_cost);
+}
+
+#[test]
+fn test_malformed_initial_tour_is_ignored() {
+    let instance: TSPSymInstance<Matrix<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let config = HeldKarpConfig::default();
+    let optimal_cost = held_karp_with_config(distances, &config).unwrap().tour.cost;
+
+    // Two subtours of six nodes each
+    let subtours = (0..12)
+        .map(|node| UnEdge::new(Node(node), Node(node / 6 * 6 + (node + 1) % 6)))
+        .collect();
+    for edges in [
+        subtours,
+        // Too few edges
+        identity_tour(distances).edges[1..].to_vec(),
+        Vec::new(),
+    ] {
+        let solution = held_karp_with_initial_tour(distances, &config, |_| UnTour {
+            edges: edges.clone(),
+            cost: Distance(0),
+        })
+        .unwrap();
+        assert_eq!(solution.tour.cost, optimal_cost);
+        assert!(solution.tour.to_order(distances.dimension()).is_ok());
+    }
+}
@@ -0,0 +1,60 @@
+use tsp_core::instance::{
+    TSPSymInstance,
+    distance::Distance,
+    matrix::{Matrix, MatrixSym},
+    node::Node,
+};
+use tsp_solvers::bounds::{compare_lower_bounds, compute_one_tree};
+
+#[test]
+fn test_compare_lower_bounds_ordered_below_optimum() {
+    // Instances with their optimal tour lengths
+    for (name, optimum) in [("eil51", 426), ("berlin52", 7542)] {
+        let instance: TSPSymInstance<Matrix<Distance>> =
+            tsp_parser::parse_tsp_instance(format!("../../instances/tsplib_symmetric/{name}.tsp"))
+                .unwrap();
+        let bounds = compare_lower_bounds(instance.distance_matrix(), 1_000).unwrap();
+
+        assert_eq!(bounds.assignment, None, "{name}: instance is symmetric");
+        assert!(
+            bounds.minimum_spanning_tree <= bounds.one_tree,
+            "{name}: {bounds:?}"
+        );
+        assert!(bounds.one_tree <= bounds.held_karp, "{name}: {bounds:?}");
+        assert!(bounds.held_karp <= Distance(optimum), "{name}: {bounds:?}");
+    }
+}
+
+#[test]
+fn test_compute_one_tree_below_optimum() {
+    let instance: TSPSymInstance<MatrixSym<Distance>> =
+        tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
+    let distances = instance.distance_matrix();
+    let dimension = distances.dimension();
+
+    let (one_tree, cost) = compute_one_tree(distances, &vec![Distance(0); dimension]).unwrap();
+    assert_eq!(one_tree.len(), dimension);
+    assert_eq!(
+        one_tree
+            .iter()
+            .filter(|edge| edge.from == Node(0) || edge.to == Node(0))
+            .count(),
+        2
+    );
+    assert!(cost <= Distance(1200), "{cost}");
+
+    // Each node has degree 2 on average, so equal penalties do not change the cost
+    let (_, cost_equal_penalties) =
+        compute_one_tree(distances, &vec![Distance(17); dimension]).unwrap();
+    assert_eq!(cost_equal_penalties, cost);
+
+    let mut penalties = vec![Distance(0); dimension];
+    penalties[3] = Distance(50);
+    let (_, cost_penalties) = compute_one_tree(distances, &penalties).unwrap();
+    assert!(cost_penalties <= Distance(1200), "{cost_penalties}");
+
+    assert_eq!(
+        compute_one_tree(&MatrixSym::new(vec![Distance(0); 3], 2), &[Distance(0); 2]),
+        None
+    );
+}
@@ -1,68 +1,37 @@
-use tsp_core::instance::{
-    TSPSymInstance, UnTour, distance::Distance, edge::UnEdge, matrix::Matrix, node::Node,
-};
+use tsp_core::instance::{TSPSymInstance, distance::Distance, matrix::Matrix, node::Node};
 use tsp_solvers::held_karp;
 
+mod branch_strategies;
+mod candidate_edges;
+mod checkpoint;
+mod christofides;
+mod construction_ratios;
+mod deterministic_parallel;
+mod edge_elimination;
+mod fixed_edges;
+mod hamiltonian_cycles;
+mod held_karp_bound;
 mod held_karp_correct_length;
+mod held_karp_path;
+mod incumbent;
+mod initial_tour;
+mod lower_bounds;
+mod optimal_tours;
+mod search_order;
+mod small_instances;
+mod solve;
+mod two_opt;
 
 #[test]
 fn test_held_karp_on_12() {
     let tsp_instance: TSPSymInstance<Matrix<Distance>> =
         tsp_parser::parse_tsp_instance("../../instances/tsp_rust/12.tsp").unwrap();
     let best_tour = held_karp(&tsp_instance.distance_matrix()).unwrap();
-    let edges = vec![
-        UnEdge {
-            from: Node(1),
-            to: Node(6),
-        },
-        UnEdge {
-            from: Node(6),
-            to: Node(2),
-        },
-        UnEdge {
-            from: Node(1),
-            to: Node(10),
-        },
-        UnEdge {
-            from: Node(10),
-            to: Node(3),
-        },
-        UnEdge {
-            from: Node(2),
-            to: Node(4),
-        },
-        UnEdge {
-            from: Node(4),
-            to: Node(9),
-        },
-        UnEdge {
-            from: Node(9),
-            to: Node(11),
-        },
-        UnEdge {
-            from: Node(11),
-            to: Node(5),
-        },
-        UnEdge {
-            from: Node(3),
-            to: Node(7),
-        },
-        UnEdge {
-            from: Node(7),
-            to: Node(8),
-        },
-        UnEdge {
-            from: Node(0),
-            to: Node(5),
-        },
-        UnEdge {
-            from: Node(0),
-            to: Node(8),
-        },
-    ];
-    let expected_tour = UnTour {
-        edges,
-        cost: Distance(1200),
-    };
-    assert_eq!(best_tour, expected_tour);
+    // Compare the visiting order, as the order of the returned edges is arbitrary
+    let expected_order = [0, 5, 11, 9, 4, 2, 6, 1, 10, 3, 7, 8].map(Node);
+    assert_eq!(
+        best_tour.to_canonical_order(12).unwrap().nodes(),
+        expected_order
+    );
+    assert_eq!(best_tour.cost, Distance(1200));
 }
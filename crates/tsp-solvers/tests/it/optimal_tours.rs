@@ -0,0 +1,9 @@
+use tsp_solvers::test_support::assert_tour_optimal;
+
+#[test]
+fn test_optimal_tour_12() {
+    assert_tour_optimal(
+        "../../instances/tsp_rust/12.tsp",
+        "../../instances/tsp_rust/12.opt.tour",
+    );
+}
@@ -0,0 +1,37 @@
+use tsp_core::instance::{TSPSymInstance, UnTour, distance::Distance, matrix::Matrix, node::Node};
+use tsp_solvers::{
+    HeldKarpConfig, SearchOrder, construction::nearest_neighbor, held_karp_with_initial_tour,
+};
+
+fn nearest_neighbor_tour(distances: &Matrix<Distance>) -> UnTour {
+    nearest_neighbor(distances, Node(0)).to_un_tour(distances)
+}
+
+fn check_search_orders(instance_name: &str, optimal_length: i32) {
+    let instance: TSPSymInstance<Matrix<Distance>> = tsp_parser::parse_tsp_instance(format!(
+        "../../instances/tsplib_symmetric/{instance_name}.tsp"
+    ))
+    .unwrap();
+    let distances = instance.distance_matrix();
+
+    for search_order in [SearchOrder::DepthFirst, SearchOrder::BestFirst] {
+        let config = HeldKarpConfig {
+            search_order,
+            ..HeldKarpConfig::default()
+        };
+        let solution =
+            held_karp_with_initial_tour(distances, &config, nearest_neighbor_tour).unwrap();
+        assert_eq!(solution.tour.cost, Distance(optimal_length));
+        assert_eq!(solution.tour.len(), distances.dimension());
+    }
+}
+
+#[test]
+fn test_search_orders_gr24() {
+    check_search_orders("gr24", 1272);
+}
+
+#[test]
+fn test_search_orders_att48() {
+    check_search_orders("att48", 10628);
+}
@@ -0,0 +1,47 @@
+use tsp_core::{
+    instance::{UnTour, distance::Distance, edge::UnEdge, node::Node},
+    test_support::line_distances,
+};
+use tsp_solvers::{held_karp, held_karp_mod::held_karp_parallel};
+
+#[test]
+fn test_held_karp_single_node() {
+    let empty_tour = UnTour {
+        edges: Vec::new(),
+        cost: Distance(0),
+    };
+    assert_eq!(held_karp(&line_distances(&[])).unwrap(), empty_tour);
+    assert_eq!(held_karp(&line_distances(&[0])).unwrap(), empty_tour);
+    assert_eq!(
+        held_karp_parallel(&line_distances(&[])).unwrap(),
+        empty_tour
+    );
+    assert_eq!(
+        held_karp_parallel(&line_distances(&[0])).unwrap(),
+        empty_tour
+    );
+}
+
+#[test]
+fn test_held_karp_two_nodes() {
+    let tour = held_karp(&line_distances(&[0, 1])).unwrap();
+    assert_eq!(
+        tour.edges,
+        vec![UnEdge::new(Node(0), Node(1)), UnEdge::new(Node(1), Node(0))]
+    );
+    assert_eq!(tour.cost, Distance(2));
+    assert_eq!(held_karp_parallel(&line_distances(&[0, 1])).unwrap(), tour);
+}
+
+#[test]
+fn test_held_karp_three_nodes() {
+    let tour = held_karp(&line_distances(&[0, 1, 2])).unwrap();
+    assert_eq!(tour.edges.len(), 3);
+    assert_eq!(tour.cost, Distance(4));
+    assert_eq!(
+        held_karp_parallel(&line_distances(&[0, 1, 2]))
+            .unwrap()
+            .cost,
+        Distance(4)
+    );
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use tsp_core::{
+    instance::{InstanceMetadata, TSPSymInstance, distance::Distance, matrix::Matrix, node::Node},
+    tsp_lib_spec::EdgeWeightType,
+};
+use tsp_solvers::{Algorithm, SolveConfig, SolverError, dynamic_programming::MAX_DIMENSION, solve};
+
+fn parse(instance_name: &str) -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance(format!("../../instances/{instance_name}.tsp")).unwrap()
+}
+
+/// Asserts that the tour visits every node of the instance exactly once.
+fn assert_valid_tour(
+    instance: &TSPSymInstance<Matrix<Distance>>,
+    result: &tsp_solvers::SolveResult,
+) {
+    let dimension = instance.distance_matrix().dimension();
+    assert_eq!(result.tour.len(), dimension);
+    assert!((0..dimension).all(|node| result.tour.degree(Node(node)) == 2));
+}
+
+#[test]
+fn test_solve_chooses_algorithm_by_size() {
+    for (instance_name, algorithm, optimal_length) in [
+        ("tsp_rust/12", Algorithm::DynamicProgramming, Some(1200)),
+        (
+            "tsplib_symmetric/gr24",
+            Algorithm::BranchAndBound,
+            Some(1272),
+        ),
+        ("tsplib_symmetric/kroA100", Algorithm::Heuristic, None),
+    ] {
+        let instance = parse(instance_name);
+        let result = solve(&instance, &SolveConfig::default()).unwrap();
+        assert_eq!(result.algorithm, algorithm);
+        assert_eq!(result.is_optimal, optimal_length.is_some());
+        if let Some(optimal_length) = optimal_length {
+            assert_eq!(result.tour.cost, Distance(optimal_length));
+        }
+        assert_valid_tour(&instance, &result);
+    }
+}
+
+#[test]
+fn test_solve_with_overridden_algorithm() {
+    let instance = parse("tsp_rust/12");
+    let exact = solve(&instance, &SolveConfig::default()).unwrap();
+    let branch_and_bound = solve(
+        &instance,
+        &SolveConfig {
+            algorithm: Some(Algorithm::BranchAndBound),
+            ..SolveConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(exact.algorithm, Algorithm::DynamicProgramming);
+    assert_eq!(branch_and_bound.algorithm, Algorithm::BranchAndBound);
+    assert!(branch_and_bound.is_optimal);
+    assert_eq!(branch_and_bound.tour.cost, exact.tour.cost);
+}
+
+#[test]
+fn test_solve_time_limit() {
+    // Without any time, the search stops before proving the warm start tour optimal
+    let instance = parse("tsplib_symmetric/att48");
+    let result = solve(
+        &instance,
+        &SolveConfig {
+            time_limit: Some(Duration::ZERO),
+            ..SolveConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(result.algorithm, Algorithm::BranchAndBound);
+    assert!(!result.is_optimal);
+    assert_valid_tour(&instance, &result);
+
+    // Iterated local search can only improve the heuristic tour
+    let instance = parse("tsplib_symmetric/kroA100");
+    let without_time = solve(&instance, &SolveConfig::default()).unwrap();
+    let with_time = solve(
+        &instance,
+        &SolveConfig {
+            time_limit: Some(Duration::from_millis(200)),
+            ..SolveConfig::default()
+        },
+    )
+    .unwrap();
+    assert!(with_time.tour.cost <= without_time.tour.cost);
+    assert_valid_tour(&instance, &with_time);
+}
+
+#[test]
+fn test_solve_invalid_input_returns_errors() {
+    let instance = parse("tsplib_symmetric/gr24");
+    let result = solve(
+        &instance,
+        &SolveConfig {
+            algorithm: Some(Algorithm::DynamicProgramming),
+            ..SolveConfig::default()
+        },
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        SolverError::DimensionTooLarge(24, MAX_DIMENSION)
+    );
+
+    // Node 4 has no finite edges, so no tour exists
+    let distances = Matrix::new_from_distance_function(5, |from, to| {
+        if from.0 == 4 || to.0 == 4 {
+            Distance::MAX
+        } else {
+            Distance(1)
+        }
+    });
+    let instance = TSPSymInstance::new(
+        distances,
+        InstanceMetadata::minimal("missing_edges".to_string(), 5, EdgeWeightType::EXPLICIT),
+    );
+    for algorithm in [
+        Algorithm::DynamicProgramming,
+        Algorithm::BranchAndBound,
+        Algorithm::Heuristic,
+    ] {
+        let config = SolveConfig {
+            algorithm: Some(algorithm),
+            ..SolveConfig::default()
+        };
+        assert_eq!(
+            solve(&instance, &config).unwrap_err(),
+            SolverError::NodeDegreeTooSmall(Node(4)),
+            "{algorithm:?}"
+        );
+    }
+}
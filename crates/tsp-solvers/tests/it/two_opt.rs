@@ -0,0 +1,43 @@
+use tsp_core::instance::{
+    TSPSymInstance, distance::Distance, edge_set::FixedEdgeSet, matrix::Matrix,
+};
+use tsp_solvers::{
+    christofides::christofides_greedy,
+    local_search::{two_opt, two_opt_parallel},
+};
+
+fn parse_instance(path: &str) -> TSPSymInstance<Matrix<Distance>> {
+    tsp_parser::parse_tsp_instance(path).unwrap()
+}
+
+#[test]
+fn test_two_opt_improves_christofides_eil76() {
+    let tsp_instance = parse_instance("../../instances/tsplib_symmetric/eil76.tsp");
+    let distances = tsp_instance.distance_matrix();
+
+    let mut tour = christofides_greedy(distances);
+    let initial_cost = tour.cost(distances);
+    let cost = two_opt(distances, &mut tour, &FixedEdgeSet::new());
+
+    assert_eq!(cost, tour.cost(distances));
+    assert!(cost <= initial_cost);
+    // The optimal tour has length 538
+    assert!(cost >= Distance(538));
+}
+
+#[test]
+fn test_two_opt_parallel_valid_tour_d493() {
+    let tsp_instance = parse_instance("../../instances/tsplib_symmetric/d493.tsp");
+    let distances = tsp_instance.distance_matrix();
+
+    let mut tour = christofides_greedy(distances);
+    let initial_cost = tour.cost(distances);
+    // TourOrder guarantees that the result is still a permutation of the nodes
+    let cost = two_opt_parallel(distances, &mut tour, 4, &FixedEdgeSet::new());
+
+    assert_eq!(tour.len(), distances.dimension());
+    assert_eq!(cost, tour.cost(distances));
+    assert!(cost <= initial_cost);
+    // The optimal tour has length 35002
+    assert!(cost >= Distance(35002));
+}